@@ -1,7 +1,9 @@
 #![allow(clippy::unused_self)]
 
+use std::rc::Rc;
+
 use oxc_allocator::{Allocator, Box, Vec};
-use oxc_ast::ast;
+use oxc_ast::{ast, Trivias};
 use oxc_hir::{hir, HirBuilder};
 use oxc_semantic::{
     Reference, ReferenceFlag, ReferenceId, ScopeFlags, Semantic, SemanticBuilder, SymbolFlags,
@@ -31,8 +33,15 @@ pub struct AstLowerReturn<'a> {
 pub struct AstLower<'a> {
     hir: HirBuilder<'a>,
     semantic: SemanticBuilder<'a>,
+    trivias: Rc<Trivias>,
+    /// Start of the last `/*#__PURE__*/` comment attached to a call or `new`,
+    /// `/*#__PURE__*/ a().b()` only annotates the outer call.
+    last_pure_annotation: Option<u32>,
 }
 
+const PURE_ANNOTATIONS: [&str; 2] = ["#__PURE__", "@__PURE__"];
+const NO_SIDE_EFFECTS_ANNOTATIONS: [&str; 2] = ["#__NO_SIDE_EFFECTS__", "@__NO_SIDE_EFFECTS__"];
+
 impl<'a> AstLower<'a> {
     pub fn enter_binding_identifier(
         &mut self,
@@ -119,6 +128,71 @@ impl<'a> AstLower<'a> {
         Self {
             hir: HirBuilder::new(allocator),
             semantic: SemanticBuilder::new(source_text, source_type),
+            trivias: Rc::new(Trivias::default()),
+            last_pure_annotation: None,
+        }
+    }
+
+    /// The comments of the program, for reading `/*#__PURE__*/` and `/*@__NO_SIDE_EFFECTS__*/` annotations
+    #[must_use]
+    pub fn with_trivias(mut self, trivias: &Rc<Trivias>) -> Self {
+        self.trivias = Rc::clone(trivias);
+        self.semantic = self.semantic.with_trivias(trivias);
+        self
+    }
+
+    /// Find an annotation comment such as `/*#__PURE__*/` in front of the node starting at `start`,
+    /// only whitespace and parentheses are allowed in between. Returns the start of the comment.
+    fn find_annotation(&self, start: u32, annotations: &[&str]) -> Option<u32> {
+        let source_text = self.semantic.source_text;
+        let (&comment_start, comment) = self.trivias.comments().range(..start).next_back()?;
+        if !comment.is_multi_line() {
+            return None;
+        }
+        // `comment.end()` is before the trailing `*/`
+        let between = source_text.get(comment.end() as usize + 2..start as usize)?;
+        if !between.chars().all(|c| c.is_whitespace() || c == '(') {
+            return None;
+        }
+        let text = source_text[comment_start as usize..comment.end() as usize].trim();
+        annotations.contains(&text).then_some(comment_start)
+    }
+
+    fn has_pure_annotation(&mut self, start: u32) -> bool {
+        let Some(comment_start) = self.find_annotation(start, &PURE_ANNOTATIONS) else {
+            return false;
+        };
+        if self.last_pure_annotation == Some(comment_start) {
+            return false;
+        }
+        self.last_pure_annotation = Some(comment_start);
+        true
+    }
+
+    fn has_no_side_effects_annotation(&self, start: u32) -> bool {
+        self.find_annotation(start, &NO_SIDE_EFFECTS_ANNOTATIONS).is_some()
+    }
+
+    /// Mark the functions declared by `decl` as annotated with `/*@__NO_SIDE_EFFECTS__*/`,
+    /// for `/*@__NO_SIDE_EFFECTS__*/ export function f() {}`
+    /// and `/*@__NO_SIDE_EFFECTS__*/ const f = () => {}`
+    fn mark_no_side_effects(decl: &mut hir::Declaration<'a>) {
+        match decl {
+            hir::Declaration::FunctionDeclaration(func) => func.pure = true,
+            hir::Declaration::VariableDeclaration(decl) => {
+                Self::mark_no_side_effects_declarators(decl);
+            }
+            _ => {}
+        }
+    }
+
+    fn mark_no_side_effects_declarators(decl: &mut hir::VariableDeclaration<'a>) {
+        for declarator in decl.declarations.iter_mut() {
+            match &mut declarator.init {
+                Some(hir::Expression::FunctionExpression(func)) => func.pure = true,
+                Some(hir::Expression::ArrowExpression(arrow)) => arrow.pure = true,
+                _ => {}
+            }
         }
     }
 
@@ -532,6 +606,7 @@ impl<'a> AstLower<'a> {
     }
 
     fn lower_arrow_expression(&mut self, expr: &ast::ArrowExpression<'a>) -> hir::Expression<'a> {
+        let pure = self.has_no_side_effects_annotation(expr.span.start);
         self.enter_function_scope();
         let params = self.lower_formal_parameters(&expr.params);
         let body = self.lower_function_body(&expr.body);
@@ -543,6 +618,7 @@ impl<'a> AstLower<'a> {
             expr.r#async,
             params,
             body,
+            pure,
         )
     }
 
@@ -558,9 +634,10 @@ impl<'a> AstLower<'a> {
     }
 
     fn lower_call_expression(&mut self, expr: &ast::CallExpression<'a>) -> hir::Expression<'a> {
+        let pure = self.has_pure_annotation(expr.span.start);
         let callee = self.lower_expression(&expr.callee);
         let arguments = self.lower_vec(&expr.arguments, Self::lower_argument);
-        self.hir.call_expression(expr.span, callee, arguments, expr.optional)
+        self.hir.call_expression(expr.span, callee, arguments, expr.optional, pure)
     }
 
     fn lower_chain_expression(&mut self, expr: &ast::ChainExpression<'a>) -> hir::Expression<'a> {
@@ -663,9 +740,10 @@ impl<'a> AstLower<'a> {
     }
 
     fn lower_new_expression(&mut self, expr: &ast::NewExpression<'a>) -> hir::Expression<'a> {
+        let pure = self.has_pure_annotation(expr.span.start);
         let callee = self.lower_expression(&expr.callee);
         let arguments = self.lower_vec(&expr.arguments, Self::lower_argument);
-        self.hir.new_expression(expr.span, callee, arguments)
+        self.hir.new_expression(expr.span, callee, arguments, pure)
     }
 
     fn lower_object_expression(&mut self, expr: &ast::ObjectExpression<'a>) -> hir::Expression<'a> {
@@ -1323,9 +1401,12 @@ impl<'a> AstLower<'a> {
                 let expr = self.lower_expression(expr);
                 hir::ExportDefaultDeclarationKind::Expression(expr)
             }
-            ast::ExportDefaultDeclarationKind::FunctionDeclaration(decl) => {
-                let decl = self.lower_function(decl);
-                hir::ExportDefaultDeclarationKind::FunctionDeclaration(decl)
+            ast::ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                let mut func = self.lower_function(func);
+                if self.has_no_side_effects_annotation(decl.span.start) {
+                    func.pure = true;
+                }
+                hir::ExportDefaultDeclarationKind::FunctionDeclaration(func)
             }
             ast::ExportDefaultDeclarationKind::ClassDeclaration(class) => {
                 let class = self.lower_class(class);
@@ -1345,7 +1426,13 @@ impl<'a> AstLower<'a> {
         &mut self,
         decl: &ast::ExportNamedDeclaration<'a>,
     ) -> Box<'a, hir::ExportNamedDeclaration<'a>> {
-        let declaration = decl.declaration.as_ref().and_then(|decl| self.lower_declaration(decl));
+        let mut declaration =
+            decl.declaration.as_ref().and_then(|decl| self.lower_declaration(decl));
+        if let Some(declaration) = &mut declaration {
+            if self.has_no_side_effects_annotation(decl.span.start) {
+                Self::mark_no_side_effects(declaration);
+            }
+        }
         let specifiers = self.lower_vec(&decl.specifiers, Self::lower_export_specifier);
         let source = decl.source.as_ref().map(|source| self.lower_string_literal(source));
         let export_kind = match decl.export_kind {
@@ -1392,7 +1479,11 @@ impl<'a> AstLower<'a> {
             ast::VariableDeclarationKind::Let => hir::VariableDeclarationKind::Let,
         };
         let declarations = self.lower_vec(&decl.declarations, Self::lower_variable_declarator);
-        self.hir.variable_declaration(decl.span, kind, declarations)
+        let mut declaration = self.hir.variable_declaration(decl.span, kind, declarations);
+        if self.has_no_side_effects_annotation(decl.span.start) {
+            Self::mark_no_side_effects_declarators(&mut declaration);
+        }
+        declaration
     }
 
     fn lower_variable_declarator(
@@ -1430,6 +1521,7 @@ impl<'a> AstLower<'a> {
         let includes = includes | SymbolFlags::Function;
         let id =
             func.id.as_ref().map(|ident| self.lower_binding_identifier(ident, includes, excludes));
+        let pure = self.has_no_side_effects_annotation(func.span.start);
         self.enter_function_scope();
         let params = self.lower_formal_parameters(&func.params);
         let body = func.body.as_ref().map(|body| self.lower_function_body(body));
@@ -1443,6 +1535,7 @@ impl<'a> AstLower<'a> {
             func.r#async,
             params,
             body,
+            pure,
         )
     }
 
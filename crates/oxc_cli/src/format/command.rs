@@ -0,0 +1,73 @@
+use clap::{builder::ValueParser, Arg, ArgAction, Command};
+
+pub fn format_command() -> Command {
+    Command::new("format")
+        .about("Format this repository.")
+        .arg_required_else_help(true)
+        .after_help(
+            "Formatting options are read from the file given by --config,
+or from .prettierrc.json in the current directory when it exists.
+
+The supported options are:
+  * tabWidth       - number of spaces per indentation level
+  * printWidth     - the line width the formatter tries to stay within
+  * singleQuote    - prefer single quotes in string literals
+  * trailingComma  - \"none\", \"es5\" or \"all\"
+  * semi           - print semicolons at the end of statements
+  * bracketSpacing - print spaces between brackets in object literals",
+        )
+        .arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .num_args(1..)
+                .required_unless_present("stdin-filepath")
+                .conflicts_with("stdin-filepath")
+                .value_parser(ValueParser::path_buf())
+                .help("File or Directory paths to format. Directories are scanned recursively."),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("write")
+                .help("Check if the files are formatted, exits with an error status if any file would change."),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Format the files in place instead of printing the formatted code."),
+        )
+        .arg(
+            Arg::new("stdin-filepath")
+                .long("stdin-filepath")
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .conflicts_with_all(["check", "write"])
+                .help("Format the code from stdin, the path is used for inferring the source type."),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .short('c')
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("This option allows you to specify the JSON config file with the formatting options."),
+        )
+        .arg(
+            Arg::new("ignore-path")
+                .long("ignore-path")
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("This option allows you to specify the file to use as your .prettierignore."),
+        )
+        .arg(
+            Arg::new("no-ignore")
+                .long("no-ignore")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Disables excluding of files from .prettierignore files and --ignore-path flags."),
+        )
+}
@@ -0,0 +1,80 @@
+use std::{fmt, fs, io, path::Path};
+
+use oxc_formatter::{FormatterOptions, QuoteStyle, TrailingComma};
+use serde::Deserialize;
+
+/// Formatting options from a JSON config file, using the same keys as `.prettierrc.json`.
+/// Options missing from the file keep the values of [FormatterOptions::default].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormatConfig {
+    tab_width: Option<u8>,
+    print_width: Option<usize>,
+    single_quote: Option<bool>,
+    trailing_comma: Option<TrailingCommaConfig>,
+    semi: Option<bool>,
+    bracket_spacing: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TrailingCommaConfig {
+    None,
+    Es5,
+    All,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Json(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl FormatConfig {
+    /// # Errors
+    ///
+    /// * When the file cannot be read
+    /// * When the file is not a valid config
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        let json = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_json(&json)
+    }
+
+    /// # Errors
+    ///
+    /// * When `json` is not a valid config
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(json).map_err(ConfigError::Json)
+    }
+
+    pub fn formatter_options(&self) -> FormatterOptions {
+        let default = FormatterOptions::default();
+        FormatterOptions {
+            indentation: self.tab_width.unwrap_or(default.indentation),
+            print_width: self.print_width.unwrap_or(default.print_width),
+            quote_style: self.single_quote.map_or(default.quote_style, |single_quote| {
+                if single_quote { QuoteStyle::Single } else { QuoteStyle::Double }
+            }),
+            trailing_comma: self.trailing_comma.map_or(default.trailing_comma, |trailing_comma| {
+                match trailing_comma {
+                    TrailingCommaConfig::None => TrailingComma::None,
+                    TrailingCommaConfig::Es5 => TrailingComma::Es5,
+                    TrailingCommaConfig::All => TrailingComma::All,
+                }
+            }),
+            semi: self.semi.unwrap_or(default.semi),
+            bracket_spacing: self.bracket_spacing.unwrap_or(default.bracket_spacing),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+mod command;
+mod config;
+mod runner;
+
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+pub use self::{
+    command::format_command,
+    config::{ConfigError, FormatConfig},
+    runner::FormatRunner,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FormatMode {
+    /// Print the formatted code to stdout
+    Print,
+    /// Report the files that would change
+    Check,
+    /// Format the files in place
+    Write,
+}
+
+#[derive(Debug)]
+pub struct FormatOptions {
+    pub paths: Vec<PathBuf>,
+    pub mode: FormatMode,
+    /// Format stdin instead of `paths`, the path is used for inferring the source type
+    pub stdin_filepath: Option<PathBuf>,
+    /// The JSON config file, defaults to `.prettierrc.json` if it exists
+    pub config: Option<PathBuf>,
+    pub ignore_path: PathBuf,
+    pub no_ignore: bool,
+}
+
+impl<'a> From<&'a ArgMatches> for FormatOptions {
+    fn from(matches: &'a ArgMatches) -> Self {
+        let mode = if matches.get_flag("check") {
+            FormatMode::Check
+        } else if matches.get_flag("write") {
+            FormatMode::Write
+        } else {
+            FormatMode::Print
+        };
+        let stdin_filepath = matches.get_one::<PathBuf>("stdin-filepath").cloned();
+
+        Self {
+            paths: matches.get_many("path").map_or_else(
+                || if stdin_filepath.is_some() { vec![] } else { vec![PathBuf::from(".")] },
+                |paths| paths.into_iter().cloned().collect(),
+            ),
+            mode,
+            stdin_filepath,
+            config: matches.get_one::<PathBuf>("config").cloned().or_else(|| {
+                let path = PathBuf::from(".prettierrc.json");
+                path.is_file().then_some(path)
+            }),
+            ignore_path: matches
+                .get_one::<PathBuf>("ignore-path")
+                .map_or_else(|| PathBuf::from(".prettierignore"), Clone::clone),
+            no_ignore: matches.get_flag("no-ignore"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use oxc_formatter::{QuoteStyle, TrailingComma};
+
+    use super::{format_command, FormatConfig, FormatMode, FormatOptions};
+
+    #[test]
+    fn verify_command() {
+        format_command().debug_assert();
+    }
+
+    fn get_format_options(arg: &str) -> FormatOptions {
+        let matches = format_command().try_get_matches_from(arg.split(' ')).unwrap();
+        FormatOptions::from(&matches)
+    }
+
+    #[test]
+    fn default() {
+        let options = get_format_options("format .");
+        assert_eq!(options.paths, vec![PathBuf::from(".")]);
+        assert_eq!(options.mode, FormatMode::Print);
+        assert_eq!(options.stdin_filepath, None);
+        assert_eq!(options.ignore_path, PathBuf::from(".prettierignore"));
+        assert!(!options.no_ignore);
+    }
+
+    #[test]
+    fn multiple_paths() {
+        let options = get_format_options("format foo bar");
+        assert_eq!(options.paths, [PathBuf::from("foo"), PathBuf::from("bar")]);
+    }
+
+    #[test]
+    fn check() {
+        let options = get_format_options("format --check foo.js");
+        assert_eq!(options.mode, FormatMode::Check);
+    }
+
+    #[test]
+    fn write() {
+        let options = get_format_options("format --write foo.js");
+        assert_eq!(options.mode, FormatMode::Write);
+    }
+
+    #[test]
+    fn check_conflicts_with_write() {
+        let result =
+            format_command().try_get_matches_from("format --check --write foo.js".split(' '));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn stdin_filepath() {
+        let options = get_format_options("format --stdin-filepath foo.ts");
+        assert!(options.paths.is_empty());
+        assert_eq!(options.stdin_filepath, Some(PathBuf::from("foo.ts")));
+    }
+
+    #[test]
+    fn config() {
+        let options = get_format_options("format --config fmt.json foo.js");
+        assert_eq!(options.config, Some(PathBuf::from("fmt.json")));
+    }
+
+    #[test]
+    fn config_options() {
+        let config = FormatConfig::from_json(
+            r#"{ "tabWidth": 2, "printWidth": 100, "singleQuote": true, "trailingComma": "es5", "semi": false, "plugins": [] }"#,
+        )
+        .unwrap();
+        let options = config.formatter_options();
+        assert_eq!(options.indentation, 2);
+        assert_eq!(options.print_width, 100);
+        assert_eq!(options.quote_style, QuoteStyle::Single);
+        assert_eq!(options.trailing_comma, TrailingComma::Es5);
+        assert!(!options.semi);
+        assert!(options.bracket_spacing);
+    }
+
+    #[test]
+    fn invalid_config() {
+        assert!(FormatConfig::from_json(r#"{ "trailingComma": "always" }"#).is_err());
+        assert!(FormatConfig::from_json("{").is_err());
+    }
+}
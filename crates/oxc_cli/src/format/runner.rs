@@ -0,0 +1,239 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+};
+
+use miette::NamedSource;
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{Error, GraphicalReportHandler};
+use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use super::{FormatConfig, FormatMode, FormatOptions};
+use crate::{
+    lint::{self, ErrorWithPath},
+    CliRunResult, Walk,
+};
+
+pub struct FormatRunner {
+    options: FormatOptions,
+}
+
+enum FileResult {
+    Unchanged(String),
+    Changed(String),
+    Errors(Vec<Error>),
+    IOError(lint::Error),
+}
+
+impl FormatRunner {
+    pub fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// # Panics
+    ///
+    /// * When `mpsc::channel` fails to send.
+    pub fn run(&self) -> CliRunResult {
+        let now = std::time::Instant::now();
+
+        let formatter_options = match &self.options.config {
+            Some(path) => match FormatConfig::from_path(path) {
+                Ok(config) => config.formatter_options(),
+                Err(error) => return CliRunResult::InvalidConfig { path: path.clone(), error },
+            },
+            None => FormatterOptions::default(),
+        };
+
+        if let Some(path) = &self.options.stdin_filepath {
+            let source_type = match Self::source_type(path) {
+                Ok(source_type) => source_type,
+                Err(error) => return CliRunResult::IOError(error),
+            };
+            let mut source_text = String::new();
+            if let Err(error) = io::stdin().read_to_string(&mut source_text).with_path(path) {
+                return CliRunResult::IOError(error);
+            }
+            let number_of_errors =
+                match Self::format_source(path, &source_text, source_type, formatter_options) {
+                    Ok(code) => {
+                        print!("{code}");
+                        0
+                    }
+                    Err(errors) => {
+                        Self::print_errors(errors);
+                        1
+                    }
+                };
+            return CliRunResult::FormatResult {
+                duration: now.elapsed(),
+                mode: FormatMode::Print,
+                number_of_files: 1,
+                number_of_changed_files: 0,
+                number_of_errors,
+            };
+        }
+
+        let number_of_files = Arc::new(AtomicUsize::new(0));
+        let (tx_result, rx_result) = mpsc::channel::<(Box<Path>, FileResult)>();
+
+        self.process_paths(&number_of_files, formatter_options, tx_result);
+        let (number_of_changed_files, number_of_errors) = self.process_results(&rx_result);
+
+        CliRunResult::FormatResult {
+            duration: now.elapsed(),
+            mode: self.options.mode,
+            number_of_files: number_of_files.load(Ordering::Relaxed),
+            number_of_changed_files,
+            number_of_errors,
+        }
+    }
+
+    fn process_paths(
+        &self,
+        number_of_files: &Arc<AtomicUsize>,
+        formatter_options: FormatterOptions,
+        tx_result: mpsc::Sender<(Box<Path>, FileResult)>,
+    ) {
+        let (tx_path, rx_path) = mpsc::channel::<Box<Path>>();
+
+        let ignore_path = (!self.options.no_ignore).then_some(self.options.ignore_path.as_path());
+        let walk = Walk::from_paths(&self.options.paths, ignore_path, &[]);
+        let number_of_files = Arc::clone(number_of_files);
+        rayon::spawn(move || {
+            let mut count = 0;
+            walk.iter().for_each(|path| {
+                count += 1;
+                tx_path.send(path).unwrap();
+            });
+            number_of_files.store(count, Ordering::Relaxed);
+        });
+
+        let mode = self.options.mode;
+        rayon::spawn(move || {
+            while let Ok(path) = rx_path.recv() {
+                let tx_result = tx_result.clone();
+                rayon::spawn(move || {
+                    let result = Self::format_path(&path, mode, formatter_options);
+                    tx_result.send((path, result)).unwrap();
+                    drop(tx_result);
+                });
+            }
+        });
+    }
+
+    /// Returns the number of changed files and the number of files with errors
+    fn process_results(
+        &self,
+        rx_result: &mpsc::Receiver<(Box<Path>, FileResult)>,
+    ) -> (usize, usize) {
+        let mut number_of_changed_files = 0;
+        let mut number_of_errors = 0;
+        let mut buf_writer = BufWriter::new(io::stdout());
+        // Formatted code is printed in a stable order
+        let mut formatted = vec![];
+
+        while let Ok((path, result)) = rx_result.recv() {
+            match result {
+                FileResult::Unchanged(code) => {
+                    if self.options.mode == FormatMode::Print {
+                        formatted.push((path, code));
+                    }
+                }
+                FileResult::Changed(code) => {
+                    number_of_changed_files += 1;
+                    match self.options.mode {
+                        FormatMode::Print => formatted.push((path, code)),
+                        FormatMode::Check | FormatMode::Write => {
+                            writeln!(buf_writer, "{}", path.display()).unwrap();
+                        }
+                    }
+                }
+                FileResult::Errors(errors) => {
+                    number_of_errors += 1;
+                    Self::print_errors(errors);
+                }
+                FileResult::IOError(error) => {
+                    number_of_errors += 1;
+                    eprintln!("IO Error: {error}");
+                }
+            }
+        }
+
+        formatted.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        for (_, code) in formatted {
+            buf_writer.write_all(code.as_bytes()).unwrap();
+        }
+        buf_writer.flush().unwrap();
+        (number_of_changed_files, number_of_errors)
+    }
+
+    fn format_path(path: &Path, mode: FormatMode, options: FormatterOptions) -> FileResult {
+        let source = Self::source_type(path).and_then(|source_type| {
+            fs::read_to_string(path).with_path(path).map(|source_text| (source_type, source_text))
+        });
+        let (source_type, source_text) = match source {
+            Ok(source) => source,
+            Err(error) => return FileResult::IOError(error),
+        };
+        match Self::format_source(path, &source_text, source_type, options) {
+            Ok(formatted) if formatted == source_text => FileResult::Unchanged(formatted),
+            Ok(formatted) => {
+                if mode == FormatMode::Write {
+                    if let Err(error) = fs::write(path, formatted.as_bytes()).with_path(path) {
+                        return FileResult::IOError(error);
+                    }
+                }
+                FileResult::Changed(formatted)
+            }
+            Err(errors) => FileResult::Errors(errors),
+        }
+    }
+
+    /// The source type inferred from the file extension, unknown extensions are reported as IO errors
+    fn source_type(path: &Path) -> Result<SourceType, lint::Error> {
+        SourceType::from_path(path)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.0))
+            .with_path(path)
+    }
+
+    fn format_source(
+        path: &Path,
+        source_text: &str,
+        source_type: SourceType,
+        options: FormatterOptions,
+    ) -> Result<String, Vec<Error>> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+
+        if !ret.errors.is_empty() {
+            let source = Arc::new(NamedSource::new(path.to_string_lossy(), source_text.to_owned()));
+            return Err(ret
+                .errors
+                .into_iter()
+                .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+                .collect());
+        }
+
+        let mut formatter = Formatter::new(source_text.len(), options);
+        formatter.with_source_text(source_text);
+        formatter.with_comments(source_text, &ret.trivias);
+        Ok(formatter.build(&ret.program).code)
+    }
+
+    /// Errors are printed to stderr so they are never mixed with the formatted code
+    fn print_errors(errors: Vec<Error>) {
+        let handler = GraphicalReportHandler::new();
+        let mut output = String::new();
+        for error in errors {
+            handler.render_report(&mut output, error.as_ref()).unwrap();
+        }
+        eprint!("{output}");
+    }
+}
@@ -1,14 +1,21 @@
 // mod git;
+mod format;
 mod lint;
+mod minify;
 mod result;
 mod type_check;
 mod walk;
 
 use clap::{Arg, Command};
 
-use crate::{lint::lint_command, type_check::type_check_command};
+use crate::{
+    format::format_command, lint::lint_command, minify::minify_command,
+    type_check::type_check_command,
+};
 pub use crate::{
-    lint::{LintOptions, LintRunner, LintRunnerWithModuleTree},
+    format::{ConfigError, FormatConfig, FormatMode, FormatOptions, FormatRunner},
+    lint::{LintFormat, LintOptions, LintRunner, LintRunnerWithModuleTree},
+    minify::{MinifyOptions, MinifyRunner},
     result::CliRunResult,
     type_check::{TypeCheckOptions, TypeCheckRunner},
     walk::Walk,
@@ -24,6 +31,8 @@ pub fn command() -> Command {
         .arg_required_else_help(true)
         .subcommand(lint_command())
         .subcommand(type_check_command())
+        .subcommand(format_command())
+        .subcommand(minify_command())
         .arg(
             Arg::new("threads")
                 .long("threads")
@@ -1,19 +1,26 @@
 use clap::{builder::ValueParser, Arg, ArgAction, Command};
 
+#[allow(clippy::too_many_lines)]
 pub fn lint_command() -> Command {
     Command::new("lint")
             .about("Lint this repository.")
             .arg_required_else_help(true)
             .after_help(
-                "To allow or deny a rule, multiple -A <NAME> or -D <NAME>.
-For example: -D correctness -A no-debugger.
+                "To allow, warn on or deny a rule, multiple -A <NAME>, -W <NAME> or -D <NAME>.
+For example: -D correctness -A no-debugger -W no-bitwise.
+Denied rules report errors, which fail the run. Warned rules report warnings,
+which only fail the run when there are more than --max-warnings.
 
 The categories are:
   * correctness - code that is outright wrong or useless
   * nursery     - new lints that are still under development
   * all         - all the categories listed above
 
-The default category is -D correctness.")
+The default category is -D correctness.
+
+The nearest .oxlintrc.json, .eslintrc.json or .eslintrc of each file is used along with the
+config files of the parent directories, up to the one with \"root\": true.
+The -A / -W / -D flags apply on top of the config files.")
             .arg(
                 Arg::new("path")
                     .value_name("PATH")
@@ -30,13 +37,29 @@ The default category is -D correctness.")
                 .action(ArgAction::Append)
                 .help("Allow a rule or a category")
             )
+            .arg(
+                Arg::new("warn")
+                .long("warn")
+                .short('W')
+                .required(false)
+                .action(ArgAction::Append)
+                .help("Warn on a rule or a category, its diagnostics are reported as warnings")
+            )
             .arg(
                 Arg::new("deny")
                 .long("deny")
                 .short('D')
                 .required(false)
                 .action(ArgAction::Append)
-                .help("Deny a rule or a category")
+                .help("Deny a rule or a category, its diagnostics are reported as errors")
+            )
+            .arg(
+                Arg::new("config")
+                .long("config")
+                .short('c')
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("Use this config file instead of looking up .oxlintrc.json / .eslintrc.json files")
             )
             .arg(
                 Arg::new("fix")
@@ -45,6 +68,14 @@ The default category is -D correctness.")
                 .action(ArgAction::SetTrue)
                 .help("This option allows you to enable oxc to fix as many issues as possible. If enabled, only unfixed issues are reported in the output")
             )
+            .arg(
+                Arg::new("fix-dry-run")
+                .long("fix-dry-run")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("fix")
+                .help("Fix the issues like --fix, but print the changes as a unified diff instead of writing the files. The diff goes to stderr with a --format other than default. Suggestions are never applied")
+            )
             .arg(
               Arg::new("quiet")
                 .long("quiet")
@@ -73,6 +104,14 @@ The default category is -D correctness.")
                 .action(ArgAction::Append)
                 .help("This option allows you to specify patterns of files to ignore (in addition to those in .eslintignore).")
             )
+            .arg(
+                Arg::new("format")
+                .long("format")
+                .short('f')
+                .required(false)
+                .value_parser(["default", "json", "checkstyle", "junit", "sarif", "github"])
+                .help("Print the diagnostics in this format, json is the result schema of ESLint and github prints GitHub Actions workflow commands")
+            )
             .arg(
                 Arg::new("max-warnings")
                   .long("max-warnings")
@@ -0,0 +1,173 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use dashmap::{mapref::entry::Entry, DashMap};
+use oxc_linter::{AllowWarnDeny, FileConfig, LintConfig, Linter, RuleCategory, RuleEnum, RULES};
+
+use super::{LintDiagnostic, LintOptions};
+
+/// Config files in the order they are looked up in each directory
+const CONFIG_FILE_NAMES: [&str; 3] = [".oxlintrc.json", ".eslintrc.json", ".eslintrc"];
+
+/// The config files applying to a directory, from the root directory to the nearest one
+type ConfigChain = Arc<[Arc<LintConfig>]>;
+
+/// Finds the config files of the linted files and builds their linters.
+///
+/// The nearest config file of a directory applies to the files in it, along with the config files of
+/// the parent directories up to the one with `"root": true`. The `-A` / `-W` / `-D` flags apply on top.
+pub struct ConfigResolver {
+    /// `-A` / `-W` / `-D` flags in order
+    rules: Vec<(AllowWarnDeny, String)>,
+    /// Compute the fixes, with `--fix` or `--fix-dry-run`
+    fix: bool,
+    /// The `--config` file, no config files are looked up when it is set
+    config_path: Option<PathBuf>,
+    cwd: PathBuf,
+    /// Loaded config files, `None` when the config file failed to load
+    configs: DashMap<PathBuf, Option<Arc<LintConfig>>>,
+    /// Directory -> config chain, `None` when one of the config files failed to load
+    chains: DashMap<PathBuf, Option<ConfigChain>>,
+    /// The largest number of rules used for a file
+    number_of_rules: AtomicUsize,
+}
+
+impl ConfigResolver {
+    pub fn new(options: &LintOptions) -> Self {
+        let cwd = env::current_dir().unwrap_or_default();
+        Self {
+            rules: options.rules.clone(),
+            fix: options.fix || options.fix_dry_run,
+            config_path: options.config.as_ref().map(|path| cwd.join(path)),
+            cwd,
+            configs: DashMap::new(),
+            chains: DashMap::new(),
+            number_of_rules: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn number_of_rules(&self) -> usize {
+        self.number_of_rules.load(Ordering::Relaxed)
+    }
+
+    /// The linter for `path`, `None` when the file is ignored by `ignorePatterns`
+    /// or a config file failed to load.
+    /// The diagnostics of a config file are reported the first time it is loaded.
+    pub fn linter<F: Fn((PathBuf, Vec<LintDiagnostic>))>(
+        &self,
+        path: &Path,
+        report: F,
+    ) -> Option<Linter> {
+        let path = self.cwd.join(path);
+        let chain = match &self.config_path {
+            Some(config_path) => ConfigChain::from(vec![self.load(config_path, &report)?]),
+            None => self.chain(path.parent()?, &report)?,
+        };
+        if chain.iter().any(|config| config.is_ignored(&path)) {
+            return None;
+        }
+
+        // Deny the correctness rules by default, unless there are -A / -W / -D flags,
+        // the config files may change their severity
+        let mut file_config = FileConfig::default();
+        if self.rules.is_empty() {
+            apply_rule_flags(&[(AllowWarnDeny::Deny, "correctness".into())], &mut file_config);
+        }
+        for config in chain.iter() {
+            config.apply(&path, &mut file_config);
+        }
+        apply_rule_flags(&self.rules, &mut file_config);
+
+        let linter = Linter::from_file_config(file_config).with_fix(self.fix);
+        self.number_of_rules.fetch_max(linter.number_of_rules(), Ordering::Relaxed);
+        Some(linter)
+    }
+
+    fn chain<F: Fn((PathBuf, Vec<LintDiagnostic>))>(
+        &self,
+        dir: &Path,
+        report: &F,
+    ) -> Option<ConfigChain> {
+        if let Some(chain) = self.chains.get(dir) {
+            return chain.clone();
+        }
+        let config = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .map(|path| self.load(&path, report));
+        let chain = match config {
+            // The config file failed to load
+            Some(None) => None,
+            Some(Some(config)) if config.is_root() => Some(ConfigChain::from(vec![config])),
+            config => {
+                let parent = dir.parent().map_or_else(
+                    || Some(ConfigChain::from(vec![])),
+                    |parent| self.chain(parent, report),
+                );
+                parent.map(|parent| parent.iter().cloned().chain(config.flatten()).collect())
+            }
+        };
+        self.chains.insert(dir.to_path_buf(), chain.clone());
+        chain
+    }
+
+    fn load<F: Fn((PathBuf, Vec<LintDiagnostic>))>(
+        &self,
+        path: &Path,
+        report: &F,
+    ) -> Option<Arc<LintConfig>> {
+        match self.configs.entry(path.to_path_buf()) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => {
+                let config = match LintConfig::from_file(path) {
+                    Ok((config, errors)) => {
+                        if !errors.is_empty() {
+                            let errors = errors.into_iter().map(LintDiagnostic::from).collect();
+                            report((path.to_path_buf(), errors));
+                        }
+                        Some(Arc::new(config))
+                    }
+                    Err(error) => {
+                        report((path.to_path_buf(), vec![LintDiagnostic::from(error)]));
+                        None
+                    }
+                };
+                entry.insert(config).clone()
+            }
+        }
+    }
+}
+
+/// Apply the -A / -W / -D flags in order, e.g. `-A all -D no-var`.
+/// Warned rules report warnings and denied rules report errors, whatever the config files say
+fn apply_rule_flags(rules: &[(AllowWarnDeny, String)], file_config: &mut FileConfig) {
+    for (allow_warn_deny, name_or_category) in rules {
+        let maybe_category = RuleCategory::from(name_or_category.as_str());
+        let matches = |rule: &RuleEnum| {
+            maybe_category.map_or_else(
+                || name_or_category == "all" || rule.name() == name_or_category,
+                |category| rule.category() == category,
+            )
+        };
+        match allow_warn_deny {
+            AllowWarnDeny::Allow => file_config.rules.retain(|_, (_, rule)| !matches(rule)),
+            AllowWarnDeny::Warn | AllowWarnDeny::Deny => {
+                for rule in RULES.iter().filter(|rule| matches(rule)) {
+                    // The rule options from the config files are kept
+                    let (severity, _) = file_config
+                        .rules
+                        .entry(rule.name())
+                        .or_insert_with(|| (None, rule.clone()));
+                    *severity = Some(*allow_warn_deny);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Applying the fixes of the rules until no more fixes apply
+
+use std::{
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use oxc_allocator::Allocator;
+use oxc_linter::{Fixer, Linter};
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::SourceType;
+
+use super::LintDiagnostic;
+
+/// Same as ESLint, a fix can expose other issues which are fixed in the next pass
+const MAX_FIX_PASSES: usize = 10;
+
+pub struct LintedSource {
+    /// The code with the fixes applied, `None` when there are no fixes applied
+    pub fixed_code: Option<String>,
+    /// The diagnostics of the fixed code
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// The number of passes applying fixes
+    pub fix_passes: usize,
+}
+
+/// Lint `source_text`. When the linter fixes, the fixed code is parsed and linted again
+/// until no more fixes apply or there have been [`MAX_FIX_PASSES`] passes.
+pub fn lint_source(linter: &Linter, source_type: SourceType, source_text: &str) -> LintedSource {
+    let mut fixed_code: Option<String> = None;
+    let mut fix_passes = 0;
+    loop {
+        let code = fixed_code.as_deref().unwrap_or(source_text);
+        let fix = linter.has_fix() && fix_passes < MAX_FIX_PASSES;
+        match lint_pass(linter, source_type, code, fix) {
+            Pass::Fixed(code) => {
+                fixed_code = Some(code);
+                fix_passes += 1;
+            }
+            Pass::Done(diagnostics) => {
+                return LintedSource { fixed_code, diagnostics, fix_passes };
+            }
+        }
+    }
+}
+
+enum Pass {
+    /// The code with the fixes of the pass applied
+    Fixed(String),
+    /// No fixes applied, the diagnostics of the code
+    Done(Vec<LintDiagnostic>),
+}
+
+fn lint_pass(linter: &Linter, source_type: SourceType, source_text: &str, fix: bool) -> Pass {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    if !ret.errors.is_empty() {
+        return Pass::Done(ret.errors.into_iter().map(LintDiagnostic::from).collect());
+    }
+
+    let program = allocator.alloc(ret.program);
+    let semantic_ret = SemanticBuilder::new(source_text, source_type)
+        .with_trivias(&ret.trivias)
+        .with_check_syntax_error(true)
+        .build(program);
+    if !semantic_ret.errors.is_empty() {
+        return Pass::Done(semantic_ret.errors.into_iter().map(LintDiagnostic::from).collect());
+    }
+
+    let mut messages = linter.run(&Rc::new(semantic_ret.semantic));
+    if fix {
+        let fix_result = Fixer::new(source_text, messages).fix();
+        if fix_result.fixed {
+            return Pass::Fixed(fix_result.fixed_code.into_owned());
+        }
+        messages = fix_result.messages;
+    }
+    Pass::Done(
+        messages
+            .into_iter()
+            .map(|message| LintDiagnostic::from_message(message, source_text))
+            .collect(),
+    )
+}
+
+/// The fixed files of a run
+#[derive(Debug, Default)]
+pub struct FixStats {
+    number_of_fixed_files: AtomicUsize,
+    /// The largest number of fix passes of a file
+    number_of_fix_passes: AtomicUsize,
+}
+
+impl FixStats {
+    pub fn add(&self, fix_passes: usize) {
+        if fix_passes > 0 {
+            self.number_of_fixed_files.fetch_add(1, Ordering::Relaxed);
+            self.number_of_fix_passes.fetch_max(fix_passes, Ordering::Relaxed);
+        }
+    }
+
+    pub fn number_of_fixed_files(&self) -> usize {
+        self.number_of_fixed_files.load(Ordering::Relaxed)
+    }
+
+    pub fn number_of_fix_passes(&self) -> usize {
+        self.number_of_fix_passes.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_linter::{Linter, RULES};
+    use oxc_span::SourceType;
+
+    use super::lint_source;
+
+    fn eq_eq_eq(fix: bool) -> Linter {
+        let rule = RULES.iter().find(|rule| rule.name() == "eq-eq-eq").unwrap();
+        Linter::from_rules(vec![rule.clone()]).with_fix(fix)
+    }
+
+    #[test]
+    fn fix_until_stable() {
+        // The fixes overlap, the outer comparison is fixed in the second pass
+        let source_text = "a == b == c";
+        let ret = lint_source(&eq_eq_eq(true), SourceType::default(), source_text);
+        assert_eq!(ret.fixed_code.as_deref(), Some("a === b === c"));
+        assert_eq!(ret.fix_passes, 2);
+        assert!(ret.diagnostics.is_empty());
+
+        let ret = lint_source(&eq_eq_eq(false), SourceType::default(), source_text);
+        assert_eq!(ret.fixed_code, None);
+        assert_eq!(ret.fix_passes, 0);
+        assert_eq!(ret.diagnostics.len(), 2);
+    }
+}
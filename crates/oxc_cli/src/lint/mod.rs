@@ -1,5 +1,8 @@
 mod command;
+mod config;
 mod error;
+mod fix;
+mod reporter;
 mod resolver;
 mod runner;
 mod runner_with_module_tree;
@@ -7,43 +10,63 @@ mod runner_with_module_tree;
 use std::{collections::BTreeMap, path::PathBuf};
 
 use clap::ArgMatches;
+pub use oxc_linter::AllowWarnDeny;
 
 pub use self::{
-    command::lint_command, error::Error, runner::LintRunner,
+    command::lint_command,
+    config::ConfigResolver,
+    error::{Error, ErrorWithPath},
+    reporter::{unified_diff, LintDiagnostic, Reporter},
+    runner::LintRunner,
     runner_with_module_tree::LintRunnerWithModuleTree,
 };
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LintFormat {
+    /// Render the diagnostics with their source code
+    Default,
+    /// The result schema of ESLint's `json` formatter
+    Json,
+    Checkstyle,
+    Junit,
+    Sarif,
+    /// GitHub Actions workflow commands
+    Github,
+}
+
+impl From<&str> for LintFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "json" => Self::Json,
+            "checkstyle" => Self::Checkstyle,
+            "junit" => Self::Junit,
+            "sarif" => Self::Sarif,
+            "github" => Self::Github,
+            _ => Self::Default,
+        }
+    }
+}
+
 #[derive(Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct LintOptions {
     pub paths: Vec<PathBuf>,
-    /// Allow / Deny rules in order. [("allow" / "deny", rule name)]
-    /// Defaults to [("deny", "correctness")]
+    /// Allow / Warn / Deny rules in order. [("allow" / "warn" / "deny", rule name)]
+    /// Empty when there are no flags, the correctness rules are denied by default
     pub rules: Vec<(AllowWarnDeny, String)>,
+    /// The config file to use instead of looking up `.oxlintrc.json` / `.eslintrc.json` files
+    pub config: Option<PathBuf>,
     pub list_rules: bool,
     pub fix: bool,
+    /// Print the fixes as a unified diff instead of writing the files
+    pub fix_dry_run: bool,
     pub quiet: bool,
     pub ignore_path: PathBuf,
     pub no_ignore: bool,
     pub ignore_pattern: Vec<String>,
     pub max_warnings: Option<usize>,
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum AllowWarnDeny {
-    Allow,
-    // Warn,
-    Deny,
-}
-
-impl From<&'static str> for AllowWarnDeny {
-    fn from(s: &'static str) -> Self {
-        match s {
-            "allow" => Self::Allow,
-            "deny" => Self::Deny,
-            _ => unreachable!(),
-        }
-    }
+    /// The diagnostics are printed to stdout in this format
+    pub format: LintFormat,
 }
 
 impl<'a> From<&'a ArgMatches> for LintOptions {
@@ -56,7 +79,9 @@ impl<'a> From<&'a ArgMatches> for LintOptions {
                 |paths| paths.into_iter().cloned().collect(),
             ),
             rules: Self::get_rules(matches),
+            config: matches.get_one::<PathBuf>("config").cloned(),
             fix: matches.get_flag("fix"),
+            fix_dry_run: matches.get_flag("fix-dry-run"),
             quiet: matches.get_flag("quiet"),
             ignore_path: matches
                 .get_one::<PathBuf>("ignore-path")
@@ -67,6 +92,9 @@ impl<'a> From<&'a ArgMatches> for LintOptions {
                 .map(|patterns| patterns.into_iter().cloned().collect())
                 .unwrap_or_default(),
             max_warnings: matches.get_one("max-warnings").copied(),
+            format: matches
+                .get_one::<String>("format")
+                .map_or(LintFormat::Default, |format| LintFormat::from(format.as_str())),
             list_rules,
         }
     }
@@ -74,11 +102,11 @@ impl<'a> From<&'a ArgMatches> for LintOptions {
 
 impl LintOptions {
     /// Get all rules in order, e.g.
-    /// `-A all -D no-var -D -eqeqeq` => [("allow", "all"), ("deny", "no-var"), ("deny", "eqeqeq")]
-    /// Defaults to [("deny", "correctness")];
+    /// `-A all -D no-var -W eqeqeq` => [("allow", "all"), ("deny", "no-var"), ("warn", "eqeqeq")]
+    /// Empty when there are no flags
     fn get_rules(matches: &ArgMatches) -> Vec<(AllowWarnDeny, String)> {
         let mut map: BTreeMap<usize, (AllowWarnDeny, String)> = BTreeMap::new();
-        for key in ["allow", "deny"] {
+        for key in ["allow", "warn", "deny"] {
             let allow_warn_deny = AllowWarnDeny::from(key);
             if let Some(values) = matches.get_many::<String>(key) {
                 let indices = matches.indices_of(key).unwrap();
@@ -87,11 +115,7 @@ impl LintOptions {
                 map.extend(zipped);
             }
         }
-        if map.is_empty() {
-            vec![(AllowWarnDeny::Deny, "correctness".into())]
-        } else {
-            map.into_values().collect()
-        }
+        map.into_values().collect()
     }
 }
 
@@ -99,7 +123,7 @@ impl LintOptions {
 mod test {
     use std::path::PathBuf;
 
-    use super::{lint_command, AllowWarnDeny, LintOptions};
+    use super::{lint_command, AllowWarnDeny, LintFormat, LintOptions};
 
     #[test]
     fn verify_command() {
@@ -121,6 +145,9 @@ mod test {
         assert!(!options.no_ignore);
         assert!(options.ignore_pattern.is_empty());
         assert_eq!(options.max_warnings, None);
+        assert!(options.rules.is_empty());
+        assert_eq!(options.config, None);
+        assert_eq!(options.format, LintFormat::Default);
     }
 
     #[test]
@@ -148,6 +175,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn rules_with_warn() {
+        let options = get_lint_options("lint src -W correctness -D no-debugger --warn no-var");
+        assert_eq!(
+            options.rules,
+            vec![
+                (AllowWarnDeny::Warn, "correctness".into()),
+                (AllowWarnDeny::Deny, "no-debugger".into()),
+                (AllowWarnDeny::Warn, "no-var".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn config() {
+        let options = get_lint_options("lint -c .eslintrc.json foo.js");
+        assert_eq!(options.config, Some(PathBuf::from(".eslintrc.json")));
+        let options = get_lint_options("lint --config oxlintrc.json foo.js");
+        assert_eq!(options.config, Some(PathBuf::from("oxlintrc.json")));
+    }
+
+    #[test]
+    fn format() {
+        let options = get_lint_options("lint --format json foo.js");
+        assert_eq!(options.format, LintFormat::Json);
+        let options = get_lint_options("lint -f github foo.js");
+        assert_eq!(options.format, LintFormat::Github);
+        assert!(lint_command().try_get_matches_from(["lint", "-f", "xml", "foo.js"]).is_err());
+    }
+
     #[test]
     fn quiet_true() {
         let options = get_lint_options("lint foo.js --quiet");
@@ -160,6 +217,14 @@ mod test {
         assert!(options.fix);
     }
 
+    #[test]
+    fn fix_dry_run() {
+        let options = get_lint_options("lint foo.js --fix-dry-run");
+        assert!(options.fix_dry_run);
+        assert!(!options.fix);
+        assert!(lint_command().try_get_matches_from(["lint", "--fix", "--fix-dry-run"]).is_err());
+    }
+
     #[test]
     fn max_warnings() {
         let options = get_lint_options("lint --max-warnings 10 foo.js");
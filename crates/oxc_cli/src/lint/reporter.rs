@@ -0,0 +1,580 @@
+//! Machine readable output formats of the lint diagnostics
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use miette::NamedSource;
+use oxc_diagnostics::{Error, Severity};
+use oxc_linter::Message;
+use oxc_span::Span;
+use serde::Serialize;
+use serde_json::json;
+use similar::TextDiff;
+
+use super::LintFormat;
+
+/// A diagnostic of a linted file
+#[derive(Debug)]
+pub struct LintDiagnostic {
+    pub error: Error,
+    /// The rule reporting the diagnostic, `None` for syntax errors and config diagnostics
+    pub rule_name: Option<&'static str>,
+    /// The rule has a fix for the diagnostic
+    pub fixable: bool,
+    /// The start and end of the labels of the diagnostic
+    pub range: Option<(Position, Position)>,
+    /// The edit suggested by the rule, which is not applied by `--fix`
+    pub suggestion: Option<(Span, String)>,
+}
+
+/// 1-based line and column
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Error> for LintDiagnostic {
+    fn from(error: Error) -> Self {
+        Self { error, rule_name: None, fixable: false, range: None, suggestion: None }
+    }
+}
+
+impl LintDiagnostic {
+    /// The diagnostic of a rule, `source_text` is the code the rule ran on
+    pub fn from_message(message: Message<'_>, source_text: &str) -> Self {
+        let rule_name = message.rule_name();
+        let fixable = message.is_fixable();
+        let suggestion = message
+            .suggestion()
+            .and_then(|suggestion| suggestion.merge(source_text))
+            .map(|fix| (fix.span, fix.content.into_owned()));
+        Self { error: message.error, rule_name, fixable, range: None, suggestion }
+    }
+
+    /// Attach the source code and compute the range of the labels
+    #[must_use]
+    pub fn with_source(self, source: &Arc<NamedSource>, source_text: &str) -> Self {
+        let range = self.error.labels().and_then(|labels| {
+            let (start, end) = labels.fold((usize::MAX, 0), |(start, end), label| {
+                (start.min(label.offset()), end.max(label.offset() + label.len()))
+            });
+            (start <= end).then(|| (position(source_text, start), position(source_text, end)))
+        });
+        Self { error: self.error.with_source_code(Arc::clone(source)), range, ..self }
+    }
+
+    /// The suggestion as a help line of the default output,
+    /// e.g. `help: Suggestion: replace `!a in b` with `!(a in b)``
+    pub fn suggestion_help(&self) -> Option<String> {
+        let (span, text) = self.suggestion.as_ref()?;
+        let original = self.error.source_code()?.read_span(&(*span).into(), 0, 0).ok()?;
+        let original = String::from_utf8_lossy(original.data());
+        Some(if text.is_empty() {
+            format!("  help: Suggestion: remove `{original}`\n")
+        } else {
+            format!("  help: Suggestion: replace `{original}` with `{text}`\n")
+        })
+    }
+
+    fn severity(&self) -> Severity {
+        match self.error.severity() {
+            Some(Severity::Warning | Severity::Advice) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    fn is_warning(&self) -> bool {
+        self.severity() == Severity::Warning
+    }
+
+    fn severity_name(&self) -> &'static str {
+        if self.is_warning() { "warning" } else { "error" }
+    }
+}
+
+fn position(source_text: &str, offset: usize) -> Position {
+    let before = source_text.get(..offset).unwrap_or(source_text);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// The unified diff of the fixes of a file, for `--fix-dry-run`
+pub fn unified_diff(path: &Path, source_text: &str, fixed_code: &str) -> String {
+    let path = path.to_string_lossy();
+    TextDiff::from_lines(source_text, fixed_code).unified_diff().header(&path, &path).to_string()
+}
+
+/// Collects the diagnostics of all files and writes them at once
+pub struct Reporter {
+    format: LintFormat,
+    files: Vec<(PathBuf, Vec<LintDiagnostic>)>,
+}
+
+impl Reporter {
+    pub fn new(format: LintFormat) -> Self {
+        Self { format, files: vec![] }
+    }
+
+    pub fn add(&mut self, path: PathBuf, diagnostics: Vec<LintDiagnostic>) {
+        if !diagnostics.is_empty() {
+            self.files.push((path, diagnostics));
+        }
+    }
+
+    /// # Errors
+    ///
+    /// * When writing to `writer` fails.
+    pub fn write<W: Write>(mut self, writer: &mut W) -> io::Result<()> {
+        // Files are reported in a stable order
+        self.files.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        match self.format {
+            LintFormat::Default => Ok(()),
+            LintFormat::Json => self.write_json(writer),
+            LintFormat::Checkstyle => self.write_checkstyle(writer),
+            LintFormat::Junit => self.write_junit(writer),
+            LintFormat::Sarif => self.write_sarif(writer),
+            LintFormat::Github => self.write_github(writer),
+        }
+    }
+
+    /// The result schema of ESLint's `json` formatter
+    fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct JsonMessage<'a> {
+            rule_id: Option<&'a str>,
+            severity: u8,
+            message: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            line: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            column: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            end_line: Option<usize>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            end_column: Option<usize>,
+            fixable: bool,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            suggestions: Vec<JsonSuggestion<'a>>,
+        }
+
+        /// `range` is in byte offsets
+        #[derive(Serialize)]
+        struct JsonSuggestion<'a> {
+            fix: JsonFix<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct JsonFix<'a> {
+            range: [u32; 2],
+            text: &'a str,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct JsonResult<'a> {
+            file_path: String,
+            messages: Vec<JsonMessage<'a>>,
+            error_count: usize,
+            warning_count: usize,
+            fixable_error_count: usize,
+            fixable_warning_count: usize,
+        }
+
+        let results = self
+            .files
+            .iter()
+            .map(|(path, diagnostics)| {
+                let count = |warning: bool, fixable: bool| {
+                    diagnostics
+                        .iter()
+                        .filter(|d| d.is_warning() == warning && (!fixable || d.fixable))
+                        .count()
+                };
+                let messages = diagnostics
+                    .iter()
+                    .map(|d| JsonMessage {
+                        rule_id: d.rule_name,
+                        severity: if d.is_warning() { 1 } else { 2 },
+                        message: d.error.to_string(),
+                        line: d.range.map(|(start, _)| start.line),
+                        column: d.range.map(|(start, _)| start.column),
+                        end_line: d.range.map(|(_, end)| end.line),
+                        end_column: d.range.map(|(_, end)| end.column),
+                        fixable: d.fixable,
+                        suggestions: d
+                            .suggestion
+                            .iter()
+                            .map(|(span, text)| JsonSuggestion {
+                                fix: JsonFix { range: [span.start, span.end], text },
+                            })
+                            .collect(),
+                    })
+                    .collect();
+                JsonResult {
+                    file_path: path.to_string_lossy().to_string(),
+                    messages,
+                    error_count: count(false, false),
+                    warning_count: count(true, false),
+                    fixable_error_count: count(false, true),
+                    fixable_warning_count: count(true, true),
+                }
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_writer(&mut *writer, &results)?;
+        writeln!(writer)
+    }
+
+    /// The format of ESLint's `checkstyle` formatter
+    fn write_checkstyle<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(writer, r#"<checkstyle version="4.3">"#)?;
+        for (path, diagnostics) in &self.files {
+            writeln!(writer, r#"  <file name="{}">"#, xml_escape(&path.to_string_lossy()))?;
+            for d in diagnostics {
+                let (start, end) = d.range.unzip();
+                writeln!(
+                    writer,
+                    r#"    <error line="{}" column="{}" endLine="{}" endColumn="{}" severity="{}" message="{}" source="{}" fixable="{}" />"#,
+                    start.map_or(0, |p| p.line),
+                    start.map_or(0, |p| p.column),
+                    end.map_or(0, |p| p.line),
+                    end.map_or(0, |p| p.column),
+                    d.severity_name(),
+                    xml_escape(&d.error.to_string()),
+                    d.rule_name.map_or_else(String::new, |name| format!("oxc.rules.{name}")),
+                    d.fixable
+                )?;
+            }
+            writeln!(writer, "  </file>")?;
+        }
+        writeln!(writer, "</checkstyle>")
+    }
+
+    /// The format of ESLint's `junit` formatter, one test suite per file
+    fn write_junit<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(writer, "<testsuites>")?;
+        for (path, diagnostics) in &self.files {
+            let path = path.to_string_lossy();
+            let classname = path.rsplit_once('.').map_or(&*path, |(name, _)| name);
+            writeln!(
+                writer,
+                r#"  <testsuite package="org.oxc" time="0" tests="{0}" errors="{0}" name="{1}">"#,
+                diagnostics.len(),
+                xml_escape(&path)
+            )?;
+            for d in diagnostics {
+                let message = d.error.to_string();
+                let location = d.range.map_or_else(String::new, |(start, end)| {
+                    format!(
+                        "line {}, col {}, end line {}, end col {}, ",
+                        start.line, start.column, end.line, end.column
+                    )
+                });
+                let severity = if d.is_warning() { "Warning" } else { "Error" };
+                let fixable = if d.fixable { ", fixable" } else { "" };
+                writeln!(
+                    writer,
+                    r#"    <testcase time="0" name="org.oxc.{}" classname="{}">"#,
+                    d.rule_name.unwrap_or("unknown"),
+                    xml_escape(classname)
+                )?;
+                writeln!(
+                    writer,
+                    r#"      <failure message="{}"><![CDATA[{location}{severity} - {}{fixable}]]></failure>"#,
+                    xml_escape(&message),
+                    message.replace("]]>", "]]]]><![CDATA[>")
+                )?;
+                writeln!(writer, "    </testcase>")?;
+            }
+            writeln!(writer, "  </testsuite>")?;
+        }
+        writeln!(writer, "</testsuites>")
+    }
+
+    /// SARIF 2.1.0, fix availability is in the `fixable` property of the results
+    fn write_sarif<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut rule_names = self
+            .files
+            .iter()
+            .flat_map(|(_, diagnostics)| diagnostics.iter().filter_map(|d| d.rule_name))
+            .collect::<Vec<_>>();
+        rule_names.sort_unstable();
+        rule_names.dedup();
+        let rules = rule_names.iter().map(|name| json!({ "id": name })).collect::<Vec<_>>();
+
+        let results = self
+            .files
+            .iter()
+            .flat_map(|(path, diagnostics)| {
+                let uri = path.to_string_lossy().trim_start_matches("./").replace('\\', "/");
+                diagnostics.iter().map(move |d| {
+                    let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+                    if let Some((start, end)) = d.range {
+                        physical_location["region"] = json!({
+                            "startLine": start.line,
+                            "startColumn": start.column,
+                            "endLine": end.line,
+                            "endColumn": end.column,
+                        });
+                    }
+                    let mut result = json!({
+                        "level": d.severity_name(),
+                        "message": { "text": d.error.to_string() },
+                        "locations": [{ "physicalLocation": physical_location }],
+                        "properties": { "fixable": d.fixable },
+                    });
+                    if let Some(name) = d.rule_name {
+                        result["ruleId"] = json!(name);
+                    }
+                    result
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let sarif = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "oxc",
+                        "informationUri": "https://github.com/Boshen/oxc",
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_writer_pretty(&mut *writer, &sarif)?;
+        writeln!(writer)
+    }
+
+    /// GitHub Actions workflow commands, shown as annotations of the pull request
+    fn write_github<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (path, diagnostics) in &self.files {
+            for d in diagnostics {
+                let mut properties =
+                    format!("file={}", github_escape_property(&path.to_string_lossy()));
+                if let Some((start, end)) = d.range {
+                    properties.push_str(&format!(
+                        ",line={},endLine={},col={},endColumn={}",
+                        start.line, end.line, start.column, end.column
+                    ));
+                }
+                if let Some(name) = d.rule_name {
+                    let fixable = if d.fixable { " (fixable)" } else { "" };
+                    properties.push_str(&format!(
+                        ",title={}",
+                        github_escape_property(&format!("{name}{fixable}"))
+                    ));
+                }
+                writeln!(
+                    writer,
+                    "::{} {properties}::{}",
+                    d.severity_name(),
+                    github_escape_data(&d.error.to_string())
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn github_escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn github_escape_property(s: &str) -> String {
+    github_escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use miette::NamedSource;
+    use oxc_diagnostics::{
+        miette::{self, Diagnostic},
+        thiserror::Error,
+    };
+    use oxc_span::Span;
+
+    use super::{unified_diff, LintDiagnostic, Position, Reporter};
+    use crate::lint::LintFormat;
+
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("eslint(no-debugger): `debugger` statement is not allowed")]
+    #[diagnostic(severity(warning))]
+    struct NoDebugger(#[label] Span);
+
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("Unexpected token")]
+    struct SyntaxError(#[label] Span);
+
+    fn report(format: LintFormat) -> String {
+        let source_text = "let a = 1;\ndebugger;\nlet <b";
+        let source = Arc::new(NamedSource::new("src/a.js", source_text.to_string()));
+        let diagnostics = vec![
+            LintDiagnostic {
+                rule_name: Some("no-debugger"),
+                fixable: true,
+                suggestion: Some((Span::new(11, 20), String::new())),
+                ..LintDiagnostic::from(miette::Error::new(NoDebugger(Span::new(11, 20))))
+            },
+            LintDiagnostic::from(miette::Error::new(SyntaxError(Span::new(25, 26)))),
+        ];
+        let diagnostics =
+            diagnostics.into_iter().map(|d| d.with_source(&source, source_text)).collect();
+        let mut reporter = Reporter::new(format);
+        reporter.add(PathBuf::from("src/a.js"), diagnostics);
+        let mut output = vec![];
+        reporter.write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn range() {
+        let source_text = "a\nbc";
+        let source = Arc::new(NamedSource::new("a.js", source_text.to_string()));
+        let diagnostic = LintDiagnostic::from(miette::Error::new(SyntaxError(Span::new(3, 4))))
+            .with_source(&source, source_text);
+        assert_eq!(
+            diagnostic.range,
+            Some((Position { line: 2, column: 2 }, Position { line: 2, column: 3 }))
+        );
+    }
+
+    #[test]
+    fn suggestion_help() {
+        let source_text = "let a = 1;\ndebugger;";
+        let source = Arc::new(NamedSource::new("a.js", source_text.to_string()));
+        let suggest = |text: &str| {
+            LintDiagnostic {
+                suggestion: Some((Span::new(11, 20), text.to_string())),
+                ..LintDiagnostic::from(miette::Error::new(NoDebugger(Span::new(11, 20))))
+            }
+            .with_source(&source, source_text)
+            .suggestion_help()
+        };
+        assert_eq!(suggest("").unwrap(), "  help: Suggestion: remove `debugger;`\n");
+        assert_eq!(
+            suggest("foo;").unwrap(),
+            "  help: Suggestion: replace `debugger;` with `foo;`\n"
+        );
+        let diagnostic = LintDiagnostic::from(miette::Error::new(SyntaxError(Span::new(3, 4))))
+            .with_source(&source, source_text);
+        assert!(diagnostic.suggestion_help().is_none());
+    }
+
+    #[test]
+    fn json() {
+        let output = report(LintFormat::Json);
+        let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let result = &results[0];
+        assert_eq!(result["filePath"], "src/a.js");
+        assert_eq!(result["errorCount"], 1);
+        assert_eq!(result["warningCount"], 1);
+        assert_eq!(result["fixableWarningCount"], 1);
+        assert_eq!(result["fixableErrorCount"], 0);
+        let message = &result["messages"][0];
+        assert_eq!(message["ruleId"], "no-debugger");
+        assert_eq!(message["severity"], 1);
+        assert_eq!(
+            [&message["line"], &message["column"], &message["endLine"], &message["endColumn"]],
+            [2, 1, 2, 10]
+        );
+        assert_eq!(message["fixable"], true);
+        assert_eq!(message["suggestions"][0]["fix"]["range"], serde_json::json!([11, 20]));
+        assert_eq!(message["suggestions"][0]["fix"]["text"], "");
+        assert_eq!(result["messages"][1]["ruleId"], serde_json::Value::Null);
+        assert!(result["messages"][1].get("suggestions").is_none());
+        assert_eq!(result["messages"][1]["severity"], 2);
+    }
+
+    #[test]
+    fn checkstyle() {
+        let output = report(LintFormat::Checkstyle);
+        assert!(output.starts_with(r#"<?xml version="1.0" encoding="utf-8"?>"#));
+        assert!(output.contains(r#"<file name="src/a.js">"#));
+        assert!(output.contains(
+            r#"<error line="2" column="1" endLine="2" endColumn="10" severity="warning" message="eslint(no-debugger): `debugger` statement is not allowed" source="oxc.rules.no-debugger" fixable="true" />"#
+        ));
+        assert!(output.contains(r#"<error line="3" column="5" endLine="3" endColumn="6" severity="error" message="Unexpected token" source="" fixable="false" />"#));
+    }
+
+    #[test]
+    fn junit() {
+        let output = report(LintFormat::Junit);
+        assert!(output.contains(
+            r#"<testsuite package="org.oxc" time="0" tests="2" errors="2" name="src/a.js">"#
+        ));
+        assert!(
+            output.contains(r#"<testcase time="0" name="org.oxc.no-debugger" classname="src/a">"#)
+        );
+        assert!(output.contains(
+            "<![CDATA[line 2, col 1, end line 2, end col 10, Warning - eslint(no-debugger): `debugger` statement is not allowed, fixable]]>"
+        ));
+    }
+
+    #[test]
+    fn sarif() {
+        let output = report(LintFormat::Sarif);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "no-debugger");
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "no-debugger");
+        assert_eq!(result["level"], "warning");
+        assert_eq!(result["properties"]["fixable"], true);
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/a.js");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["endColumn"], 10);
+        assert_eq!(run["results"][1]["level"], "error");
+    }
+
+    #[test]
+    fn github() {
+        let output = report(LintFormat::Github);
+        assert_eq!(
+            output,
+            "::warning file=src/a.js,line=2,endLine=2,col=1,endColumn=10,title=no-debugger (fixable)::eslint(no-debugger): `debugger` statement is not allowed\n::error file=src/a.js,line=3,endLine=3,col=5,endColumn=6::Unexpected token\n"
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let diff = unified_diff(Path::new("src/a.js"), "let a = 1;\ndebugger;\n", "let a = 1;\n");
+        assert_eq!(diff, "--- src/a.js\n+++ src/a.js\n@@ -1,2 +1 @@\n let a = 1;\n-debugger;\n");
+    }
+}
@@ -1,8 +1,7 @@
 use std::{
     fs,
-    io::{BufWriter, Write},
+    io::{self, BufWriter, Write},
     path::{Path, PathBuf},
-    rc::Rc,
     sync::{
         atomic::{AtomicUsize, Ordering},
         mpsc, Arc,
@@ -10,25 +9,26 @@ use std::{
 };
 
 use miette::NamedSource;
-use oxc_allocator::Allocator;
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::Error,
     Error, GraphicalReportHandler, Severity,
 };
-use oxc_linter::{Fixer, Linter, RuleCategory, RuleEnum, RULES};
-use oxc_parser::Parser;
-use oxc_semantic::SemanticBuilder;
+use oxc_linter::Linter;
 use oxc_span::SourceType;
-use rustc_hash::FxHashSet;
 
-use super::{AllowWarnDeny, LintOptions};
+use super::{
+    fix::{lint_source, FixStats, LintedSource},
+    unified_diff, ConfigResolver, LintDiagnostic, LintFormat, LintOptions, Reporter,
+};
 use crate::{CliRunResult, Walk};
 
 pub struct LintRunner {
     options: LintOptions,
 
-    linter: Arc<Linter>,
+    configs: Arc<ConfigResolver>,
+
+    fix_stats: Arc<FixStats>,
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -38,8 +38,8 @@ pub struct MinifiedFileError(pub PathBuf);
 
 impl LintRunner {
     pub fn new(options: LintOptions) -> Self {
-        let linter = Linter::from_rules(Self::derive_rules(&options)).with_fix(options.fix);
-        Self { options, linter: Arc::new(linter) }
+        let configs = ConfigResolver::new(&options);
+        Self { options, configs: Arc::new(configs), fix_stats: Arc::default() }
     }
 
     pub fn print_rules() {
@@ -47,52 +47,6 @@ impl LintRunner {
         Linter::print_rules(&mut stdout);
     }
 
-    fn derive_rules(options: &LintOptions) -> Vec<RuleEnum> {
-        let mut rules: FxHashSet<RuleEnum> = FxHashSet::default();
-
-        for (allow_warn_deny, name_or_category) in &options.rules {
-            let maybe_category = RuleCategory::from(name_or_category.as_str());
-            match allow_warn_deny {
-                AllowWarnDeny::Deny => {
-                    match maybe_category {
-                        Some(category) => rules.extend(
-                            RULES.iter().filter(|rule| rule.category() == category).cloned(),
-                        ),
-                        None => {
-                            if name_or_category == "all" {
-                                rules.extend(RULES.iter().cloned());
-                            } else {
-                                rules.extend(
-                                    RULES
-                                        .iter()
-                                        .filter(|rule| rule.name() == name_or_category)
-                                        .cloned(),
-                                );
-                            }
-                        }
-                    };
-                }
-                AllowWarnDeny::Allow => {
-                    match maybe_category {
-                        Some(category) => rules.retain(|rule| rule.category() != category),
-                        None => {
-                            if name_or_category == "all" {
-                                rules.clear();
-                            } else {
-                                rules.retain(|rule| rule.name() == name_or_category);
-                            }
-                        }
-                    };
-                }
-            }
-        }
-
-        let mut rules = rules.into_iter().collect::<Vec<_>>();
-        // for stable diagnostics output ordering
-        rules.sort_unstable_by_key(|rule| rule.name());
-        rules
-    }
-
     /// # Panics
     ///
     /// * When `mpsc::channel` fails to send.
@@ -100,28 +54,34 @@ impl LintRunner {
         let now = std::time::Instant::now();
 
         let number_of_files = Arc::new(AtomicUsize::new(0));
-        let (tx_error, rx_error) = mpsc::channel::<(PathBuf, Vec<Error>)>();
+        let (tx_error, rx_error) = mpsc::channel::<(PathBuf, Vec<LintDiagnostic>)>();
+        let (tx_diff, rx_diff) = mpsc::channel::<(PathBuf, String)>();
 
-        self.process_paths(&number_of_files, tx_error);
-        let (number_of_warnings, number_of_diagnostics) = self.process_diagnostics(&rx_error);
+        self.process_paths(&number_of_files, tx_error, tx_diff);
+        let (number_of_warnings, number_of_errors) = self.process_diagnostics(&rx_error, &rx_diff);
 
         CliRunResult::LintResult {
             duration: now.elapsed(),
-            number_of_rules: self.linter.number_of_rules(),
+            number_of_rules: self.configs.number_of_rules(),
             number_of_files: number_of_files.load(Ordering::Relaxed),
-            number_of_diagnostics,
             number_of_warnings,
+            number_of_errors,
             max_warnings_exceeded: self
                 .options
                 .max_warnings
                 .map_or(false, |max_warnings| number_of_warnings > max_warnings),
+            number_of_fixed_files: self.fix_stats.number_of_fixed_files(),
+            number_of_fix_passes: self.fix_stats.number_of_fix_passes(),
+            fix_dry_run: self.options.fix_dry_run,
+            format: self.options.format,
         }
     }
 
     fn process_paths(
         &self,
         number_of_files: &Arc<AtomicUsize>,
-        tx_error: mpsc::Sender<(PathBuf, Vec<Error>)>,
+        tx_error: mpsc::Sender<(PathBuf, Vec<LintDiagnostic>)>,
+        tx_diff: mpsc::Sender<(PathBuf, String)>,
     ) {
         let (tx_path, rx_path) = mpsc::channel::<Box<Path>>();
 
@@ -136,13 +96,21 @@ impl LintRunner {
             number_of_files.store(count, Ordering::Relaxed);
         });
 
-        let linter = Arc::clone(&self.linter);
+        let configs = Arc::clone(&self.configs);
+        let fix_stats = Arc::clone(&self.fix_stats);
+        let fix_dry_run = self.options.fix_dry_run;
         rayon::spawn(move || {
             while let Ok(path) = rx_path.recv() {
                 let tx_error = tx_error.clone();
-                let linter = Arc::clone(&linter);
+                let tx_diff = tx_diff.clone();
+                let configs = Arc::clone(&configs);
+                let fix_stats = Arc::clone(&fix_stats);
                 rayon::spawn(move || {
-                    if let Some(diagnostics) = Self::lint_path(&linter, &path) {
+                    let report = |diagnostics| tx_error.send(diagnostics).unwrap();
+                    let Some(linter) = configs.linter(&path, report) else { return };
+                    let tx_diff = fix_dry_run.then_some(&tx_diff);
+                    if let Some(diagnostics) = Self::lint_path(&linter, &path, &fix_stats, tx_diff)
+                    {
                         tx_error.send(diagnostics).unwrap();
                     }
                     drop(tx_error);
@@ -153,17 +121,31 @@ impl LintRunner {
 
     fn process_diagnostics(
         &self,
-        rx_error: &mpsc::Receiver<(PathBuf, Vec<Error>)>,
+        rx_error: &mpsc::Receiver<(PathBuf, Vec<LintDiagnostic>)>,
+        rx_diff: &mpsc::Receiver<(PathBuf, String)>,
     ) -> (usize, usize) {
         let mut number_of_warnings = 0;
-        let mut number_of_diagnostics = 0;
+        let mut number_of_errors = 0;
         let mut buf_writer = BufWriter::new(std::io::stdout());
         let handler = GraphicalReportHandler::new();
+        let mut reporter = (self.options.format != LintFormat::Default)
+            .then(|| Reporter::new(self.options.format));
+
+        while let Ok((path, mut diagnostics)) = rx_error.recv() {
+            let is_warning = |d: &LintDiagnostic| d.error.severity() == Some(Severity::Warning);
+            number_of_errors += diagnostics.iter().filter(|d| !is_warning(d)).count();
+            if let Some(reporter) = &mut reporter {
+                number_of_warnings += diagnostics.iter().filter(|d| is_warning(d)).count();
+                if self.options.quiet {
+                    diagnostics.retain(|d| !is_warning(d));
+                }
+                reporter.add(path, diagnostics);
+                continue;
+            }
 
-        while let Ok((path, diagnostics)) = rx_error.recv() {
-            number_of_diagnostics += diagnostics.len();
             let mut output = String::new();
-            for diagnostic in diagnostics {
+            for lint_diagnostic in diagnostics {
+                let diagnostic = &lint_diagnostic.error;
                 if diagnostic.severity() == Some(Severity::Warning) {
                     number_of_warnings += 1;
                     // The --quiet flag follows ESLint's --quiet behavior as documented here: https://eslint.org/docs/latest/use/command-line-interface#--quiet
@@ -181,6 +163,9 @@ impl LintRunner {
 
                 let mut err = String::new();
                 handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
+                if let Some(help) = lint_diagnostic.suggestion_help() {
+                    err.push_str(&help);
+                }
                 // Skip large output and print only once
                 if err.lines().any(|line| line.len() >= 400) {
                     let minified_diagnostic = Error::new(MinifiedFileError(path.clone()));
@@ -193,57 +178,70 @@ impl LintRunner {
             buf_writer.write_all(output.as_bytes()).unwrap();
         }
 
+        if let Some(reporter) = reporter {
+            reporter.write(&mut buf_writer).unwrap();
+        }
+
+        // The files are linted once `rx_error` is closed, so all the diffs have been sent
+        let mut diffs = rx_diff.try_iter().collect::<Vec<_>>();
+        diffs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        // Machine readable formats keep stdout for the diagnostics
+        let mut stderr = io::stderr();
+        for (_, diff) in diffs {
+            if self.options.format == LintFormat::Default {
+                buf_writer.write_all(diff.as_bytes()).unwrap();
+            } else {
+                stderr.write_all(diff.as_bytes()).unwrap();
+            }
+        }
         buf_writer.flush().unwrap();
-        (number_of_warnings, number_of_diagnostics)
+        (number_of_warnings, number_of_errors)
     }
 
-    fn lint_path(linter: &Linter, path: &Path) -> Option<(PathBuf, Vec<Error>)> {
+    /// The fixes are written to the file, or sent to `tx_diff` as a diff with `--fix-dry-run`
+    fn lint_path(
+        linter: &Linter,
+        path: &Path,
+        fix_stats: &FixStats,
+        tx_diff: Option<&mpsc::Sender<(PathBuf, String)>>,
+    ) -> Option<(PathBuf, Vec<LintDiagnostic>)> {
         let source_text = fs::read_to_string(path).unwrap_or_else(|_| panic!("{path:?} not found"));
-        let allocator = Allocator::default();
         let source_type =
             SourceType::from_path(path).unwrap_or_else(|_| panic!("incorrect {path:?}"));
-        let ret = Parser::new(&allocator, &source_text, source_type).parse();
-
-        if !ret.errors.is_empty() {
-            return Some(Self::wrap_diagnostics(path, &source_text, ret.errors));
-        };
-
-        let program = allocator.alloc(ret.program);
-        let semantic_ret = SemanticBuilder::new(&source_text, source_type)
-            .with_trivias(&ret.trivias)
-            .with_check_syntax_error(true)
-            .build(program);
-
-        if !semantic_ret.errors.is_empty() {
-            return Some(Self::wrap_diagnostics(path, &source_text, semantic_ret.errors));
+        let LintedSource { fixed_code, diagnostics, fix_passes } =
+            lint_source(linter, source_type, &source_text);
+        fix_stats.add(fix_passes);
+
+        // The diagnostics are of the fixed code
+        let source_text = match fixed_code {
+            Some(fixed_code) => {
+                tx_diff.map_or_else(
+                    || fs::write(path, fixed_code.as_bytes()).unwrap(),
+                    |tx_diff| {
+                        let diff = unified_diff(path, &source_text, &fixed_code);
+                        tx_diff.send((path.to_path_buf(), diff)).unwrap();
+                    },
+                );
+                fixed_code
+            }
+            None => source_text,
         };
 
-        let result = linter.run(&Rc::new(semantic_ret.semantic));
-
-        if result.is_empty() {
+        if diagnostics.is_empty() {
             return None;
         }
-
-        if linter.has_fix() {
-            let fix_result = Fixer::new(&source_text, result).fix();
-            fs::write(path, fix_result.fixed_code.as_bytes()).unwrap();
-            let errors = fix_result.messages.into_iter().map(|m| m.error).collect();
-            return Some(Self::wrap_diagnostics(path, &source_text, errors));
-        }
-
-        let errors = result.into_iter().map(|diagnostic| diagnostic.error).collect();
-        Some(Self::wrap_diagnostics(path, &source_text, errors))
+        Some(Self::wrap_diagnostics(path, &source_text, diagnostics))
     }
 
     fn wrap_diagnostics(
         path: &Path,
         source_text: &str,
-        diagnostics: Vec<Error>,
-    ) -> (PathBuf, Vec<Error>) {
+        diagnostics: Vec<LintDiagnostic>,
+    ) -> (PathBuf, Vec<LintDiagnostic>) {
         let source = Arc::new(NamedSource::new(path.to_string_lossy(), source_text.to_owned()));
         let diagnostics = diagnostics
             .into_iter()
-            .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+            .map(|diagnostic| diagnostic.with_source(&source, source_text))
             .collect();
         (path.to_path_buf(), diagnostics)
     }
@@ -15,25 +15,28 @@ use oxc_diagnostics::{
     thiserror::Error,
     Error, GraphicalReportHandler, Severity,
 };
-use oxc_linter::{FixResult, Fixer, Linter, RuleCategory, RuleEnum, RULES};
+use oxc_linter::Linter;
 use oxc_parser::{Parser, ParserReturn};
 use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
 use oxc_span::{SourceType, VALID_EXTENSIONS};
 use rayon::prelude::*;
-use rustc_hash::FxHashSet;
 
 use super::{
     error::{ErrorWithPath, Result},
+    fix::{lint_source, FixStats, LintedSource},
     resolver::{ResolveResult, Resolver, Resource},
-    AllowWarnDeny, LintOptions,
+    unified_diff, ConfigResolver, LintDiagnostic, LintFormat, LintOptions, Reporter,
 };
 use crate::CliRunResult;
 
 #[derive(Clone)]
 struct LinterRuntimeData {
-    linter: Arc<Linter>,
+    configs: Arc<ConfigResolver>,
+    fix_stats: Arc<FixStats>,
     visited: Arc<DashSet<PathBuf>>,
-    tx_error: Sender<(PathBuf, Vec<Error>)>,
+    tx_error: Sender<(PathBuf, Vec<LintDiagnostic>)>,
+    /// The diffs of the fixes with `--fix-dry-run`, the fixes are written to the files otherwise
+    tx_diff: Option<Sender<(PathBuf, String)>>,
 }
 
 pub struct LintRunnerWithModuleTree {
@@ -56,62 +59,16 @@ impl LintRunnerWithModuleTree {
         Linter::print_rules(&mut stdout);
     }
 
-    fn derive_rules(options: &LintOptions) -> Vec<RuleEnum> {
-        let mut rules: FxHashSet<RuleEnum> = FxHashSet::default();
-
-        for (allow_warn_deny, name_or_category) in &options.rules {
-            let maybe_category = RuleCategory::from(name_or_category.as_str());
-            match allow_warn_deny {
-                AllowWarnDeny::Deny => {
-                    match maybe_category {
-                        Some(category) => rules.extend(
-                            RULES.iter().filter(|rule| rule.category() == category).cloned(),
-                        ),
-                        None => {
-                            if name_or_category == "all" {
-                                rules.extend(RULES.iter().cloned());
-                            } else {
-                                rules.extend(
-                                    RULES
-                                        .iter()
-                                        .filter(|rule| rule.name() == name_or_category)
-                                        .cloned(),
-                                );
-                            }
-                        }
-                    };
-                }
-                AllowWarnDeny::Allow => {
-                    match maybe_category {
-                        Some(category) => rules.retain(|rule| rule.category() != category),
-                        None => {
-                            if name_or_category == "all" {
-                                rules.clear();
-                            } else {
-                                rules.retain(|rule| rule.name() == name_or_category);
-                            }
-                        }
-                    };
-                }
-            }
-        }
-
-        let mut rules = rules.into_iter().collect::<Vec<_>>();
-        // for stable diagnostics output ordering
-        rules.sort_unstable_by_key(|rule| rule.name());
-        rules
-    }
-
     pub fn run(&self) -> CliRunResult {
         let now = std::time::Instant::now();
 
-        let linter =
-            Linter::from_rules(Self::derive_rules(&self.options)).with_fix(self.options.fix);
-        let linter = Arc::new(linter);
+        let configs = Arc::new(ConfigResolver::new(&self.options));
+        let fix_stats = Arc::new(FixStats::default());
 
         // Unless other panic happens, calling `Sender::send` can't fail, because we hold the
         // receiver until all senders are dropped. This allows us to safely unwrap all calls to send.
         let (tx_error, rx_error) = unbounded();
+        let (tx_diff, rx_diff) = unbounded();
 
         // we can ignore the result because nothing bad happens if the resolver is already set
         // TODO: make sure this is still true once we allow options to be set
@@ -124,13 +81,15 @@ impl LintRunnerWithModuleTree {
         let result = process_paths(
             &self.options.paths,
             LinterRuntimeData {
-                linter: Arc::clone(&linter),
+                configs: Arc::clone(&configs),
+                fix_stats: Arc::clone(&fix_stats),
                 visited: Arc::clone(&visited),
                 tx_error,
+                tx_diff: self.options.fix_dry_run.then_some(tx_diff),
             },
         );
 
-        let (number_of_warnings, number_of_diagnostics) = self.process_diagnostics(&rx_error);
+        let (number_of_warnings, number_of_errors) = self.process_diagnostics(&rx_error, &rx_diff);
 
         if let Err(err) = result {
             return CliRunResult::IOError(err);
@@ -138,29 +97,50 @@ impl LintRunnerWithModuleTree {
 
         CliRunResult::LintResult {
             duration: now.elapsed(),
-            number_of_rules: linter.number_of_rules(),
+            number_of_rules: configs.number_of_rules(),
             number_of_files: visited.len(),
-            number_of_diagnostics,
             number_of_warnings,
+            number_of_errors,
             max_warnings_exceeded: self
                 .options
                 .max_warnings
                 .map_or(false, |max_warnings| number_of_warnings > max_warnings),
+            number_of_fixed_files: fix_stats.number_of_fixed_files(),
+            number_of_fix_passes: fix_stats.number_of_fix_passes(),
+            fix_dry_run: self.options.fix_dry_run,
+            format: self.options.format,
         }
     }
 
-    fn process_diagnostics(&self, rx_error: &Receiver<(PathBuf, Vec<Error>)>) -> (usize, usize) {
+    fn process_diagnostics(
+        &self,
+        rx_error: &Receiver<(PathBuf, Vec<LintDiagnostic>)>,
+        rx_diff: &Receiver<(PathBuf, String)>,
+    ) -> (usize, usize) {
         let mut number_of_warnings = 0;
-        let mut number_of_diagnostics = 0;
+        let mut number_of_errors = 0;
         let mut buf_writer = BufWriter::new(io::stdout());
         let handler = GraphicalReportHandler::new();
+        let mut reporter = (self.options.format != LintFormat::Default)
+            .then(|| Reporter::new(self.options.format));
 
-        for (path, diagnostics) in rx_error.iter() {
-            number_of_diagnostics += diagnostics.len();
+        for (path, mut diagnostics) in rx_error.iter() {
+            let is_warning = |d: &LintDiagnostic| d.error.severity() == Some(Severity::Warning);
+            number_of_errors += diagnostics.iter().filter(|d| !is_warning(d)).count();
+
+            if let Some(reporter) = &mut reporter {
+                number_of_warnings += diagnostics.iter().filter(|d| is_warning(d)).count();
+                if self.options.quiet {
+                    diagnostics.retain(|d| !is_warning(d));
+                }
+                reporter.add(path, diagnostics);
+                continue;
+            }
 
             let mut output = String::new();
 
-            for diagnostic in diagnostics {
+            for lint_diagnostic in diagnostics {
+                let diagnostic = &lint_diagnostic.error;
                 if diagnostic.severity() == Some(Severity::Warning) {
                     number_of_warnings += 1;
                     // The --quiet flag follows ESLint's --quiet behavior as documented here: https://eslint.org/docs/latest/use/command-line-interface#--quiet
@@ -180,6 +160,9 @@ impl LintRunnerWithModuleTree {
                 handler
                     .render_report(&mut err, diagnostic.as_ref())
                     .expect("Writing to a string can't fail");
+                if let Some(help) = lint_diagnostic.suggestion_help() {
+                    err.push_str(&help);
+                }
 
                 if err.lines().all(|line| line.len() < 400) {
                     output.push_str(&err);
@@ -196,9 +179,26 @@ impl LintRunnerWithModuleTree {
             buf_writer.write_all(output.as_bytes()).expect("Writing to stdout can't fail");
         }
 
+        if let Some(reporter) = reporter {
+            reporter.write(&mut buf_writer).expect("Writing to stdout can't fail");
+        }
+
+        // All the files are linted once `rx_error` is closed
+        let mut diffs = rx_diff.try_iter().collect::<Vec<_>>();
+        diffs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        // Machine readable formats keep stdout for the diagnostics
+        let mut stderr = io::stderr();
+        for (_, diff) in diffs {
+            if self.options.format == LintFormat::Default {
+                buf_writer.write_all(diff.as_bytes()).expect("Writing to stdout can't fail");
+            } else {
+                stderr.write_all(diff.as_bytes()).expect("Writing to stderr can't fail");
+            }
+        }
+
         // see comment above
         buf_writer.flush().expect("Flushing stdout can't fail");
-        (number_of_warnings, number_of_diagnostics)
+        (number_of_warnings, number_of_errors)
     }
 }
 
@@ -219,13 +219,13 @@ fn process_paths(paths: &[PathBuf], runtime_data: LinterRuntimeData) -> Result<(
 fn wrap_diagnostics(
     path: &Path,
     source_text: &str,
-    diagnostics: Vec<Error>,
-) -> (PathBuf, Vec<Error>) {
+    diagnostics: Vec<LintDiagnostic>,
+) -> (PathBuf, Vec<LintDiagnostic>) {
     let source = Arc::new(NamedSource::new(path.to_string_lossy(), source_text.to_owned()));
 
     let diagnostics = diagnostics
         .into_iter()
-        .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+        .map(|diagnostic| diagnostic.with_source(&source, source_text))
         .collect();
 
     (path.to_path_buf(), diagnostics)
@@ -250,7 +250,7 @@ fn run_for_dir(path: &Path, runtime_data: &LinterRuntimeData) -> Result<()> {
 static RESOLVER: OnceLock<Resolver> = OnceLock::new();
 
 fn run_for_file(path: &Path, runtime_data: &LinterRuntimeData) -> Result<()> {
-    let LinterRuntimeData { linter, visited, tx_error } = &runtime_data;
+    let LinterRuntimeData { configs, fix_stats, visited, tx_error, tx_diff } = &runtime_data;
 
     if visited.contains(path) {
         return Ok(());
@@ -272,6 +272,7 @@ fn run_for_file(path: &Path, runtime_data: &LinterRuntimeData) -> Result<()> {
             Parser::new(&allocator, &source, source_type).parse();
 
         if !errors.is_empty() {
+            let errors = errors.into_iter().map(LintDiagnostic::from).collect();
             tx_error.send(wrap_diagnostics(path, &source, errors)).unwrap();
             return Ok(());
         };
@@ -287,6 +288,7 @@ fn run_for_file(path: &Path, runtime_data: &LinterRuntimeData) -> Result<()> {
             .build(program);
 
         if !errors.is_empty() {
+            let errors = errors.into_iter().map(LintDiagnostic::from).collect();
             tx_error.send(wrap_diagnostics(path, &source, errors)).unwrap();
             return Ok(());
         };
@@ -325,22 +327,41 @@ fn run_for_file(path: &Path, runtime_data: &LinterRuntimeData) -> Result<()> {
         .filter(|path| !visited.contains(path))
         .try_for_each(|path| run_for_file(&path, runtime_data))?;
 
+    let report = |diagnostics| tx_error.send(diagnostics).unwrap();
+    let Some(linter) = configs.linter(path, report) else { return Ok(()) };
+    if linter.has_fix() {
+        // The fixed code is parsed and linted again until no more fixes apply
+        let LintedSource { fixed_code, diagnostics, fix_passes } =
+            lint_source(&linter, source_type, &source);
+        fix_stats.add(fix_passes);
+        let source = match fixed_code {
+            Some(fixed_code) => {
+                match tx_diff {
+                    Some(tx_diff) => {
+                        let diff = unified_diff(path, &source, &fixed_code);
+                        tx_diff.send((path.to_path_buf(), diff)).unwrap();
+                    }
+                    None => fs::write(path, fixed_code.as_bytes()).with_path(path)?,
+                }
+                fixed_code
+            }
+            None => source,
+        };
+        if !diagnostics.is_empty() {
+            tx_error.send(wrap_diagnostics(path, &source, diagnostics)).unwrap();
+        }
+        return Ok(());
+    }
+
     let result = linter.run(&Rc::new(semantic));
 
     if result.is_empty() {
         return Ok(());
     }
 
-    let messages = if linter.has_fix() {
-        let FixResult { messages, fixed_code, .. } = Fixer::new(&source, result).fix();
-        fs::write(path, fixed_code.as_bytes()).with_path(path)?;
-        messages
-    } else {
-        result
-    };
-
-    let errors = messages.into_iter().map(|m| m.error).collect();
-    let diagnostic = wrap_diagnostics(path, &source, errors);
+    let diagnostics =
+        result.into_iter().map(|message| LintDiagnostic::from_message(message, &source)).collect();
+    let diagnostic = wrap_diagnostics(path, &source, diagnostics);
     tx_error.send(diagnostic).unwrap();
 
     Ok(())
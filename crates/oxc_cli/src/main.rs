@@ -11,8 +11,8 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 use std::env;
 
 use oxc_cli::{
-    command, CliRunResult, LintOptions, LintRunner, LintRunnerWithModuleTree, TypeCheckOptions,
-    TypeCheckRunner,
+    command, CliRunResult, FormatOptions, FormatRunner, LintOptions, LintRunner,
+    LintRunnerWithModuleTree, MinifyOptions, MinifyRunner, TypeCheckOptions, TypeCheckRunner,
 };
 
 fn main() -> CliRunResult {
@@ -45,6 +45,14 @@ fn main() -> CliRunResult {
             let options = TypeCheckOptions::from(matches);
             TypeCheckRunner::new(options).run()
         }
+        "format" => {
+            let options = FormatOptions::from(matches);
+            FormatRunner::new(options).run()
+        }
+        "minify" => {
+            let options = MinifyOptions::from(matches);
+            MinifyRunner::new(options).run()
+        }
         _ => CliRunResult::None,
     }
 }
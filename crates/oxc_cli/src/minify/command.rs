@@ -0,0 +1,217 @@
+use clap::{builder::ValueParser, Arg, ArgAction, Command};
+
+use super::{parse_compress_option, parse_define, parse_regex};
+
+#[allow(clippy::too_many_lines)]
+pub fn minify_command() -> Command {
+    Command::new("minify")
+        .about("Minify files.")
+        .arg_required_else_help(true)
+        .after_help(
+            "To configure the compressor, multiple --compress <NAME>[=<BOOL>] separated by commas.
+For example: --compress join_vars=false,loops=false.
+
+The compress options are:
+  * booleans      - optimizations for boolean context, e.g. `!!a ? b : c` -> `a ? b : c`
+  * collapse_vars - move the initializer of variables used once into the next statement
+  * conditionals  - transform `if` statements into expressions, e.g. `if (a) b()` -> `a && b()`
+  * dead_code     - remove unreachable code and unused declarations
+  * drop_console  - remove calls to `console` methods, their arguments are not evaluated
+  * drop_debugger - remove `debugger;` statements
+  * if_return     - optimizations for `if` and `return`, e.g. `if (a) return b; return c`
+  * join_vars     - join consecutive var statements
+  * loops         - optimizations for loops with a statically known condition
+  * reduce_vars   - inline `const` declarations of literals
+  * sequences     - join consecutive expression statements, e.g. `a(); b()` -> `a(), b()`
+  * side_effects  - remove unused expressions without side effects, e.g. `/*#__PURE__*/ foo()`
+  * typeofs       - transform `typeof foo == \"undefined\"` into `foo === void 0`
+
+To replace global constants, --define <KEY>=<VALUE> where KEY is a global name or dotted path
+and VALUE is a literal or a global name. For example: --define process.env.NODE_ENV='\"production\"'.",
+        )
+        .arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .num_args(1..)
+                .required_unless_present("stdin-filepath")
+                .conflicts_with("stdin-filepath")
+                .value_parser(ValueParser::path_buf())
+                .help("File or Directory paths to minify. Directories are scanned recursively."),
+        )
+        .arg(
+            Arg::new("stdin-filepath")
+                .long("stdin-filepath")
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("Minify the code from stdin, the path is used for inferring the source type and naming the output."),
+        )
+        .arg(
+            Arg::new("module")
+                .long("module")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("script")
+                .help("Minify the files as ES modules, the default for files other than .js and .cjs"),
+        )
+        .arg(
+            Arg::new("script")
+                .long("script")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Minify the files as scripts, the default for .js and .cjs files"),
+        )
+        .arg(
+            Arg::new("out-dir")
+                .long("out-dir")
+                .short('o')
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("Write the minified files into this directory instead of printing them, keeping their relative paths."),
+        )
+        .arg(
+            Arg::new("compress")
+                .long("compress")
+                .short('c')
+                .required(false)
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .value_parser(parse_compress_option)
+                .help("Enable or disable a compress option"),
+        )
+        .arg(
+            Arg::new("no-compress")
+                .long("no-compress")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("compress")
+                .help("Disable all compress options"),
+        )
+        .arg(
+            Arg::new("passes")
+                .long("passes")
+                .value_name("N")
+                .required(false)
+                .value_parser(clap::value_parser!(usize))
+                .help("Maximum number of compress passes, stops early once a pass no longer shrinks the output"),
+        )
+        .arg(
+            Arg::new("define")
+                .long("define")
+                .short('d')
+                .value_name("KEY=VALUE")
+                .required(false)
+                .action(ArgAction::Append)
+                .value_parser(parse_define)
+                .help("Replace a global constant, e.g. process.env.NODE_ENV='\"production\"'"),
+        )
+        .arg(
+            Arg::new("pure-funcs")
+                .long("pure-funcs")
+                .value_name("NAME")
+                .required(false)
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .help("Functions without side effects, unused calls are removed, e.g. Math.floor"),
+        )
+        .arg(
+            Arg::new("no-mangle")
+                .long("no-mangle")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Do not mangle variable names"),
+        )
+        .arg(
+            Arg::new("reserved")
+                .long("reserved")
+                .value_name("NAME")
+                .required(false)
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .help("Variable names which are never mangled, e.g. $,require"),
+        )
+        .arg(
+            Arg::new("keep-fnames")
+                .long("keep-fnames")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Do not mangle the names of function declarations"),
+        )
+        .arg(
+            Arg::new("keep-classnames")
+                .long("keep-classnames")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Do not mangle the names of class declarations"),
+        )
+        .arg(
+            Arg::new("top-level")
+                .long("top-level")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Mangle the top level variables of scripts, they are global variables shared with other scripts"),
+        )
+        .arg(
+            Arg::new("mangle-props")
+                .long("mangle-props")
+                .value_name("REGEX")
+                .required(false)
+                .value_parser(parse_regex)
+                .help("Mangle property names matching the regex, e.g. '^_'"),
+        )
+        .arg(
+            Arg::new("reserve-props")
+                .long("reserve-props")
+                .value_name("NAME")
+                .required(false)
+                .action(ArgAction::Append)
+                .value_delimiter(',')
+                .requires("mangle-props")
+                .help("Property names which are never mangled"),
+        )
+        .arg(
+            Arg::new("name-cache")
+                .long("name-cache")
+                .value_name("FILE")
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("Read the mangled names of a previous run from this JSON file and write the updated names back.\nFiles are minified one after another so they agree on the mangled names."),
+        )
+        .arg(
+            Arg::new("beautify")
+                .long("beautify")
+                .short('b')
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Print one statement per line with indentation, for debugging the minified code"),
+        )
+        .arg(
+            Arg::new("ascii-only")
+                .long("ascii-only")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Escape non-ASCII characters in strings, identifiers, templates and regexes"),
+        )
+        .arg(
+            Arg::new("comments")
+                .long("comments")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Keep legal comments, i.e. /*! ... */ and comments containing @license or @preserve"),
+        )
+        .arg(
+            Arg::new("max-line-len")
+                .long("max-line-len")
+                .value_name("LENGTH")
+                .required(false)
+                .value_parser(clap::value_parser!(usize))
+                .help("Break lines longer than LENGTH after a comma or semicolon"),
+        )
+        .arg(
+            Arg::new("source-map")
+                .long("source-map")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .requires("out-dir")
+                .help("Write a source map next to each minified file"),
+        )
+}
@@ -0,0 +1,401 @@
+mod command;
+mod runner;
+
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+use oxc_minifier::{
+    CompressOptions, MangleOptions, ManglePropertiesOptions, MinifierOptions, PrinterOptions,
+};
+use oxc_span::ModuleKind;
+use regex::Regex;
+
+pub use self::{command::minify_command, runner::MinifyRunner};
+
+#[derive(Debug)]
+pub struct MinifyOptions {
+    pub paths: Vec<PathBuf>,
+    /// Minify stdin instead of `paths`, the path is used for inferring the source type
+    pub stdin_filepath: Option<PathBuf>,
+    /// Parse the files as scripts or modules,
+    /// `None` infers it from the file extension: `.js` and `.cjs` files are scripts
+    pub module_kind: Option<ModuleKind>,
+    /// Write the minified files into this directory instead of printing them to stdout
+    pub out_dir: Option<PathBuf>,
+    /// JSON file of mangled names read before and written after minifying
+    pub name_cache: Option<PathBuf>,
+    pub minifier: MinifierOptions,
+}
+
+impl<'a> From<&'a ArgMatches> for MinifyOptions {
+    fn from(matches: &'a ArgMatches) -> Self {
+        let stdin_filepath = matches.get_one::<PathBuf>("stdin-filepath").cloned();
+
+        let mut compress = if matches.get_flag("no-compress") {
+            CompressOption::ALL.iter().fold(CompressOptions::default(), |mut options, option| {
+                option.set(&mut options, false);
+                options
+            })
+        } else {
+            CompressOptions::default()
+        };
+        if let Some(values) = matches.get_many::<(CompressOption, bool)>("compress") {
+            for (option, value) in values {
+                option.set(&mut compress, *value);
+            }
+        }
+        if let Some(passes) = matches.get_one::<usize>("passes") {
+            compress.passes = *passes;
+        }
+        if let Some(defines) = matches.get_many::<(String, String)>("define") {
+            compress.define.extend(defines.cloned());
+        }
+        if let Some(pure_funcs) = matches.get_many::<String>("pure-funcs") {
+            compress.pure_funcs.extend(pure_funcs.cloned());
+        }
+
+        let mangle_options = MangleOptions {
+            reserved: matches
+                .get_many::<String>("reserved")
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+            keep_fnames: matches.get_flag("keep-fnames"),
+            keep_classnames: matches.get_flag("keep-classnames"),
+            top_level: matches.get_flag("top-level"),
+        };
+        let mangle_properties = matches.get_one::<Regex>("mangle-props").map(|regex| {
+            let mut options = ManglePropertiesOptions::new(regex.clone());
+            if let Some(reserved) = matches.get_many::<String>("reserve-props") {
+                options.reserved.extend(reserved.cloned());
+            }
+            options
+        });
+
+        let print = PrinterOptions {
+            beautify: matches.get_flag("beautify"),
+            ascii_only: matches.get_flag("ascii-only"),
+            comments: matches.get_flag("comments"),
+            max_line_len: matches.get_one::<usize>("max-line-len").copied().unwrap_or_default(),
+        };
+
+        Self {
+            paths: matches.get_many("path").map_or_else(
+                || if stdin_filepath.is_some() { vec![] } else { vec![PathBuf::from(".")] },
+                |paths| paths.into_iter().cloned().collect(),
+            ),
+            stdin_filepath,
+            module_kind: if matches.get_flag("module") {
+                Some(ModuleKind::Module)
+            } else if matches.get_flag("script") {
+                Some(ModuleKind::Script)
+            } else {
+                None
+            },
+            out_dir: matches.get_one::<PathBuf>("out-dir").cloned(),
+            name_cache: matches.get_one::<PathBuf>("name-cache").cloned(),
+            minifier: MinifierOptions {
+                mangle: !matches.get_flag("no-mangle"),
+                mangle_options,
+                mangle_properties,
+                compress,
+                print,
+                source_map: matches.get_flag("source-map"),
+                ..MinifierOptions::default()
+            },
+        }
+    }
+}
+
+/// The fields of [CompressOptions] that can be set from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompressOption {
+    Booleans,
+    CollapseVars,
+    Conditionals,
+    DeadCode,
+    DropConsole,
+    DropDebugger,
+    IfReturn,
+    JoinVars,
+    Loops,
+    ReduceVars,
+    Sequences,
+    SideEffects,
+    Typeofs,
+}
+
+impl CompressOption {
+    const ALL: [Self; 13] = [
+        Self::Booleans,
+        Self::CollapseVars,
+        Self::Conditionals,
+        Self::DeadCode,
+        Self::DropConsole,
+        Self::DropDebugger,
+        Self::IfReturn,
+        Self::JoinVars,
+        Self::Loops,
+        Self::ReduceVars,
+        Self::Sequences,
+        Self::SideEffects,
+        Self::Typeofs,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Booleans => "booleans",
+            Self::CollapseVars => "collapse_vars",
+            Self::Conditionals => "conditionals",
+            Self::DeadCode => "dead_code",
+            Self::DropConsole => "drop_console",
+            Self::DropDebugger => "drop_debugger",
+            Self::IfReturn => "if_return",
+            Self::JoinVars => "join_vars",
+            Self::Loops => "loops",
+            Self::ReduceVars => "reduce_vars",
+            Self::Sequences => "sequences",
+            Self::SideEffects => "side_effects",
+            Self::Typeofs => "typeofs",
+        }
+    }
+
+    fn set(self, options: &mut CompressOptions, value: bool) {
+        match self {
+            Self::Booleans => options.booleans = value,
+            Self::CollapseVars => options.collapse_vars = value,
+            Self::Conditionals => options.conditionals = value,
+            Self::DeadCode => options.dead_code = value,
+            Self::DropConsole => options.drop_console = value,
+            Self::DropDebugger => options.drop_debugger = value,
+            Self::IfReturn => options.if_return = value,
+            Self::JoinVars => options.join_vars = value,
+            Self::Loops => options.loops = value,
+            Self::ReduceVars => options.reduce_vars = value,
+            Self::Sequences => options.sequences = value,
+            Self::SideEffects => options.side_effects = value,
+            Self::Typeofs => options.typeofs = value,
+        }
+    }
+}
+
+fn parse_regex(s: &str) -> Result<Regex, String> {
+    Regex::new(s).map_err(|error| error.to_string())
+}
+
+/// Parse `name` or `name=true` or `name=false`
+fn parse_compress_option(s: &str) -> Result<(CompressOption, bool), String> {
+    let (name, value) = s.split_once('=').unwrap_or((s, "true"));
+    let option = CompressOption::ALL
+        .into_iter()
+        .find(|option| option.name() == name)
+        .ok_or_else(|| format!("unknown compress option `{name}`"))?;
+    let value =
+        value.parse::<bool>().map_err(|_| format!("invalid value `{value}` for `{name}`"))?;
+    Ok((option, value))
+}
+
+/// Parse `KEY=VALUE`, e.g. `process.env.NODE_ENV='production'`
+fn parse_define(s: &str) -> Result<(String, String), String> {
+    let (key, value) =
+        s.split_once('=').ok_or_else(|| format!("expected `KEY=VALUE`, found `{s}`"))?;
+    CompressOptions::check_define(key, value)?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use oxc_span::ModuleKind;
+
+    use super::{minify_command, MinifyOptions};
+
+    #[test]
+    fn verify_command() {
+        minify_command().debug_assert();
+    }
+
+    fn get_minify_options(arg: &str) -> MinifyOptions {
+        let matches = minify_command().try_get_matches_from(arg.split(' ')).unwrap();
+        MinifyOptions::from(&matches)
+    }
+
+    #[test]
+    fn default() {
+        let options = get_minify_options("minify foo.js");
+        assert_eq!(options.paths, vec![PathBuf::from("foo.js")]);
+        assert_eq!(options.stdin_filepath, None);
+        assert_eq!(options.out_dir, None);
+        assert!(options.minifier.mangle);
+        assert!(!options.minifier.source_map);
+        assert!(options.minifier.compress.join_vars);
+    }
+
+    #[test]
+    fn stdin_filepath() {
+        let options = get_minify_options("minify --stdin-filepath foo.js");
+        assert!(options.paths.is_empty());
+        assert_eq!(options.stdin_filepath, Some(PathBuf::from("foo.js")));
+    }
+
+    #[test]
+    fn module_kind() {
+        assert_eq!(get_minify_options("minify foo.js").module_kind, None);
+        assert_eq!(
+            get_minify_options("minify --module foo.js").module_kind,
+            Some(ModuleKind::Module)
+        );
+        assert_eq!(
+            get_minify_options("minify --script foo.js").module_kind,
+            Some(ModuleKind::Script)
+        );
+        let result =
+            minify_command().try_get_matches_from("minify --module --script foo.js".split(' '));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn compress() {
+        let options =
+            get_minify_options("minify --compress join_vars=false,loops=false -c typeofs foo.js");
+        let compress = options.minifier.compress;
+        assert!(compress.dead_code);
+        assert!(!compress.join_vars);
+        assert!(!compress.loops);
+        assert!(compress.typeofs);
+        assert!(compress.booleans);
+    }
+
+    #[test]
+    fn no_compress() {
+        let options = get_minify_options("minify --no-compress foo.js");
+        let compress = options.minifier.compress;
+        assert!(!compress.booleans);
+        assert!(!compress.collapse_vars);
+        assert!(!compress.conditionals);
+        assert!(!compress.dead_code);
+        assert!(!compress.drop_console);
+        assert!(!compress.drop_debugger);
+        assert!(!compress.if_return);
+        assert!(!compress.join_vars);
+        assert!(!compress.loops);
+        assert!(!compress.reduce_vars);
+        assert!(!compress.sequences);
+        assert!(!compress.side_effects);
+        assert!(!compress.typeofs);
+    }
+
+    #[test]
+    fn passes() {
+        assert_eq!(get_minify_options("minify foo.js").minifier.compress.passes, 1);
+        assert_eq!(get_minify_options("minify --passes 3 foo.js").minifier.compress.passes, 3);
+        assert!(
+            minify_command().try_get_matches_from("minify --passes x foo.js".split(' ')).is_err()
+        );
+    }
+
+    #[test]
+    fn invalid_compress() {
+        let command = minify_command();
+        assert!(
+            command.clone().try_get_matches_from("minify -c unknown foo.js".split(' ')).is_err()
+        );
+        assert!(command.try_get_matches_from("minify -c loops=no foo.js".split(' ')).is_err());
+    }
+
+    #[test]
+    fn define() {
+        let options = get_minify_options(
+            "minify --define process.env.NODE_ENV='production' -d __DEV__=false foo.js",
+        );
+        let define = options.minifier.compress.define;
+        assert_eq!(define.len(), 2);
+        assert_eq!(define["process.env.NODE_ENV"], "'production'");
+        assert_eq!(define["__DEV__"], "false");
+        let command = minify_command();
+        assert!(
+            command.clone().try_get_matches_from("minify -d __DEV__ foo.js".split(' ')).is_err()
+        );
+        assert!(command.try_get_matches_from("minify -d a()=1 foo.js".split(' ')).is_err());
+    }
+
+    #[test]
+    fn drop_console_and_pure_funcs() {
+        let options = get_minify_options(
+            "minify -c drop_console --pure-funcs Math.floor,log --pure-funcs debug foo.js",
+        );
+        let compress = options.minifier.compress;
+        assert!(compress.drop_console);
+        assert_eq!(compress.pure_funcs, vec!["Math.floor", "log", "debug"]);
+        assert!(!get_minify_options("minify foo.js").minifier.compress.drop_console);
+    }
+
+    #[test]
+    fn no_mangle() {
+        let options = get_minify_options("minify --no-mangle foo.js");
+        assert!(!options.minifier.mangle);
+    }
+
+    #[test]
+    fn mangle_options() {
+        let options = get_minify_options("minify foo.js");
+        assert!(options.minifier.mangle_options.reserved.is_empty());
+        assert!(!options.minifier.mangle_options.keep_fnames);
+        assert!(!options.minifier.mangle_options.keep_classnames);
+        assert!(!options.minifier.mangle_options.top_level);
+        assert_eq!(options.name_cache, None);
+        let options = get_minify_options(
+            "minify --reserved $,require --keep-fnames --keep-classnames --top-level --name-cache cache.json foo.js",
+        );
+        assert_eq!(options.minifier.mangle_options.reserved, vec!["$", "require"]);
+        assert!(options.minifier.mangle_options.keep_fnames);
+        assert!(options.minifier.mangle_options.keep_classnames);
+        assert!(options.minifier.mangle_options.top_level);
+        assert_eq!(options.name_cache, Some(PathBuf::from("cache.json")));
+    }
+
+    #[test]
+    fn mangle_props() {
+        let options = get_minify_options("minify foo.js");
+        assert!(options.minifier.mangle_properties.is_none());
+        let options = get_minify_options("minify --mangle-props ^_ --reserve-props _a,_b foo.js");
+        let mangle_properties = options.minifier.mangle_properties.unwrap();
+        assert_eq!(mangle_properties.regex.as_str(), "^_");
+        assert_eq!(mangle_properties.reserved, vec!["_a", "_b"]);
+        let command = minify_command();
+        assert!(
+            command
+                .clone()
+                .try_get_matches_from("minify --mangle-props ( foo.js".split(' '))
+                .is_err()
+        );
+        assert!(
+            command.try_get_matches_from("minify --reserve-props _a foo.js".split(' ')).is_err()
+        );
+    }
+
+    #[test]
+    fn print_options() {
+        let print = get_minify_options("minify foo.js").minifier.print;
+        assert!(!print.beautify && !print.ascii_only && !print.comments);
+        assert_eq!(print.max_line_len, 0);
+        let print =
+            get_minify_options("minify -b --ascii-only --comments --max-line-len 80 foo.js")
+                .minifier
+                .print;
+        assert!(print.beautify && print.ascii_only && print.comments);
+        assert_eq!(print.max_line_len, 80);
+    }
+
+    #[test]
+    fn source_map() {
+        let options = get_minify_options("minify --source-map --out-dir dist foo.js");
+        assert!(options.minifier.source_map);
+        assert_eq!(options.out_dir, Some(PathBuf::from("dist")));
+        // Source maps are only written next to the minified files
+        let result = minify_command().try_get_matches_from("minify --source-map foo.js".split(' '));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+};
+
+use flate2::{write::GzEncoder, Compression};
+use humansize::{format_size, DECIMAL};
+use miette::NamedSource;
+use oxc_diagnostics::{Error, GraphicalReportHandler};
+use oxc_minifier::{Minifier, MinifierOptions, NameCache};
+use oxc_span::{ModuleKind, SourceType};
+
+use super::MinifyOptions;
+use crate::{
+    lint::{self, ErrorWithPath},
+    CliRunResult, Walk,
+};
+
+pub struct MinifyRunner {
+    options: MinifyOptions,
+}
+
+struct MinifiedFile {
+    original_size: usize,
+    minified_size: usize,
+    gzip_size: usize,
+    /// The minified code when it is printed to stdout
+    code: Option<String>,
+    /// The name cache updated with the names mangled in this file
+    name_cache: Option<NameCache>,
+}
+
+enum FileResult {
+    Minified(MinifiedFile),
+    Errors(Vec<Error>),
+    IOError(lint::Error),
+}
+
+impl MinifyRunner {
+    pub fn new(options: MinifyOptions) -> Self {
+        Self { options }
+    }
+
+    /// # Panics
+    ///
+    /// * When `mpsc::channel` fails to send.
+    pub fn run(&self) -> CliRunResult {
+        let now = std::time::Instant::now();
+
+        let number_of_files = Arc::new(AtomicUsize::new(0));
+        let (tx_result, rx_result) = mpsc::channel::<(Box<Path>, FileResult)>();
+
+        if let Some(name_cache_path) = &self.options.name_cache {
+            if let Err(error) =
+                self.process_with_name_cache(name_cache_path, &number_of_files, &tx_result)
+            {
+                return CliRunResult::IOError(error);
+            }
+            drop(tx_result);
+        } else if let Some(path) = &self.options.stdin_filepath {
+            let source_text = match Self::read_stdin(path) {
+                Ok(source_text) => source_text,
+                Err(error) => return CliRunResult::IOError(error),
+            };
+            number_of_files.store(1, Ordering::Relaxed);
+            let result = Self::minify_source(
+                path,
+                &source_text,
+                self.options.module_kind,
+                self.options.out_dir.as_deref(),
+                &self.options.minifier,
+            );
+            tx_result.send((path.clone().into_boxed_path(), result)).unwrap();
+            drop(tx_result);
+        } else {
+            self.process_paths(&number_of_files, tx_result);
+        }
+        let number_of_errors = self.process_results(&rx_result);
+
+        CliRunResult::MinifyResult {
+            duration: now.elapsed(),
+            number_of_files: number_of_files.load(Ordering::Relaxed),
+            number_of_errors,
+            to_stdout: self.options.out_dir.is_none(),
+        }
+    }
+
+    fn process_paths(
+        &self,
+        number_of_files: &Arc<AtomicUsize>,
+        tx_result: mpsc::Sender<(Box<Path>, FileResult)>,
+    ) {
+        let (tx_path, rx_path) = mpsc::channel::<Box<Path>>();
+
+        let walk = Walk::from_paths(&self.options.paths, None, &[]);
+        let number_of_files = Arc::clone(number_of_files);
+        rayon::spawn(move || {
+            let mut count = 0;
+            walk.iter().for_each(|path| {
+                count += 1;
+                tx_path.send(path).unwrap();
+            });
+            number_of_files.store(count, Ordering::Relaxed);
+        });
+
+        let module_kind = self.options.module_kind;
+        let out_dir = self.options.out_dir.clone().map(Arc::new);
+        let options = Arc::new(self.options.minifier.clone());
+        rayon::spawn(move || {
+            while let Ok(path) = rx_path.recv() {
+                let tx_result = tx_result.clone();
+                let out_dir = out_dir.clone();
+                let options = Arc::clone(&options);
+                rayon::spawn(move || {
+                    let result = match fs::read_to_string(&path).with_path(&path) {
+                        Ok(source_text) => Self::minify_source(
+                            &path,
+                            &source_text,
+                            module_kind,
+                            out_dir.as_deref().map(PathBuf::as_path),
+                            &options,
+                        ),
+                        Err(error) => FileResult::IOError(error),
+                    };
+                    tx_result.send((path, result)).unwrap();
+                    drop(tx_result);
+                });
+            }
+        });
+    }
+
+    /// Minify the files one after another in a stable order,
+    /// each file reuses the names mangled by the previous ones
+    fn process_with_name_cache(
+        &self,
+        name_cache_path: &Path,
+        number_of_files: &AtomicUsize,
+        tx_result: &mpsc::Sender<(Box<Path>, FileResult)>,
+    ) -> Result<(), lint::Error> {
+        let mut options = self.options.minifier.clone();
+        if name_cache_path.exists() {
+            let json = fs::read_to_string(name_cache_path).with_path(name_cache_path)?;
+            let name_cache =
+                NameCache::from_json(&json).map_err(io::Error::from).with_path(name_cache_path)?;
+            options.name_cache = Some(name_cache);
+        }
+
+        let paths = self.options.stdin_filepath.as_ref().map_or_else(
+            || {
+                let mut paths =
+                    Walk::from_paths(&self.options.paths, None, &[]).iter().collect::<Vec<_>>();
+                paths.sort_unstable();
+                paths
+            },
+            |path| vec![path.clone().into_boxed_path()],
+        );
+        number_of_files.store(paths.len(), Ordering::Relaxed);
+
+        for path in paths {
+            let source_text = if self.options.stdin_filepath.is_some() {
+                Self::read_stdin(&path)
+            } else {
+                fs::read_to_string(&path).with_path(&path)
+            };
+            let mut result = match source_text {
+                Ok(source_text) => Self::minify_source(
+                    &path,
+                    &source_text,
+                    self.options.module_kind,
+                    self.options.out_dir.as_deref(),
+                    &options,
+                ),
+                Err(error) => FileResult::IOError(error),
+            };
+            if let FileResult::Minified(file) = &mut result {
+                if let Some(name_cache) = file.name_cache.take() {
+                    options.name_cache = Some(name_cache);
+                }
+            }
+            tx_result.send((path, result)).unwrap();
+        }
+
+        let name_cache = options.name_cache.unwrap_or_default();
+        fs::write(name_cache_path, name_cache.to_json() + "\n").with_path(name_cache_path)
+    }
+
+    fn read_stdin(path: &Path) -> Result<String, lint::Error> {
+        let mut source_text = String::new();
+        io::stdin().read_to_string(&mut source_text).with_path(path)?;
+        Ok(source_text)
+    }
+
+    /// Print the minified code or the sizes of the minified files,
+    /// returns the number of files with errors
+    fn process_results(&self, rx_result: &mpsc::Receiver<(Box<Path>, FileResult)>) -> usize {
+        let mut number_of_errors = 0;
+        let handler = GraphicalReportHandler::new();
+        // Files are reported in a stable order
+        let mut minified = vec![];
+
+        while let Ok((path, result)) = rx_result.recv() {
+            match result {
+                FileResult::Minified(file) => minified.push((path, file)),
+                FileResult::Errors(errors) => {
+                    number_of_errors += 1;
+                    let mut output = String::new();
+                    for error in errors {
+                        handler.render_report(&mut output, error.as_ref()).unwrap();
+                    }
+                    eprint!("{output}");
+                }
+                FileResult::IOError(error) => {
+                    number_of_errors += 1;
+                    eprintln!("IO Error: {error}");
+                }
+            }
+        }
+        minified.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        // The report goes to stderr when the minified code is printed to stdout
+        let mut report: Box<dyn Write> = if self.options.out_dir.is_none() {
+            let mut stdout = BufWriter::new(io::stdout());
+            for (_, file) in &minified {
+                if let Some(code) = &file.code {
+                    writeln!(stdout, "{code}").unwrap();
+                }
+            }
+            stdout.flush().unwrap();
+            Box::new(io::stderr())
+        } else {
+            Box::new(BufWriter::new(io::stdout()))
+        };
+        if !minified.is_empty() {
+            Self::print_sizes(&mut report, &minified).unwrap();
+        }
+        report.flush().unwrap();
+
+        number_of_errors
+    }
+
+    /// Print the sizes in the same format as `tasks/minsize`
+    fn print_sizes(out: &mut dyn Write, minified: &[(Box<Path>, MinifiedFile)]) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:width$} -> {:width$} -> {:width$}",
+            "Original",
+            "Minified",
+            "Gzip",
+            width = 10
+        )?;
+        for (path, file) in minified {
+            writeln!(
+                out,
+                "{:width$} -> {:width$} -> {:width$} {}",
+                format_size(file.original_size, DECIMAL),
+                format_size(file.minified_size, DECIMAL),
+                format_size(file.gzip_size, DECIMAL),
+                path.display(),
+                width = 10
+            )?;
+        }
+        Ok(())
+    }
+
+    fn minify_source(
+        path: &Path,
+        source_text: &str,
+        module_kind: Option<ModuleKind>,
+        out_dir: Option<&Path>,
+        options: &MinifierOptions,
+    ) -> FileResult {
+        let source_type = match Self::source_type(path, module_kind) {
+            Ok(source_type) => source_type,
+            Err(error) => return FileResult::IOError(error),
+        };
+        let source_name = path.to_string_lossy();
+        let ret = Minifier::new(source_text, source_type, options.clone())
+            .with_source_name(&source_name)
+            .build();
+
+        if !ret.errors.is_empty() {
+            let source = Arc::new(NamedSource::new(source_name, source_text.to_owned()));
+            let errors = ret
+                .errors
+                .into_iter()
+                .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+                .collect();
+            return FileResult::Errors(errors);
+        }
+
+        let mut file = MinifiedFile {
+            original_size: source_text.len(),
+            minified_size: ret.code.len(),
+            gzip_size: gzip_size(&ret.code),
+            code: None,
+            name_cache: ret.name_cache,
+        };
+
+        let Some(out_dir) = out_dir else {
+            file.code = Some(ret.code);
+            return FileResult::Minified(file);
+        };
+
+        let output_path = Self::output_path(out_dir, path);
+        let mut code = ret.code;
+        let written = output_path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|()| {
+            if let Some(source_map) = ret.source_map {
+                let mut map_path = output_path.clone().into_os_string();
+                map_path.push(".map");
+                let map_path = PathBuf::from(map_path);
+                fs::write(&map_path, source_map.to_json_string())?;
+                if let Some(map_name) = map_path.file_name() {
+                    code.push_str("\n//# sourceMappingURL=");
+                    code.push_str(&map_name.to_string_lossy());
+                }
+            }
+            code.push('\n');
+            fs::write(&output_path, code)
+        });
+        match written.with_path(&output_path) {
+            Ok(()) => FileResult::Minified(file),
+            Err(error) => FileResult::IOError(error),
+        }
+    }
+
+    /// The source type inferred from the file extension, unknown extensions are reported as IO errors.
+    /// Without an explicit `module_kind`, `.js` and `.cjs` files are scripts and other files are modules.
+    fn source_type(
+        path: &Path,
+        module_kind: Option<ModuleKind>,
+    ) -> Result<SourceType, lint::Error> {
+        let source_type = SourceType::from_path(path)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error.0))
+            .with_path(path)?;
+        let module_kind = module_kind.unwrap_or_else(|| {
+            match path.extension().and_then(std::ffi::OsStr::to_str) {
+                Some("js" | "cjs") => ModuleKind::Script,
+                _ => ModuleKind::Module,
+            }
+        });
+        Ok(source_type.with_script(module_kind == ModuleKind::Script))
+    }
+
+    /// `path` inside `out_dir`, e.g. `../src/a.js` -> `out_dir/src/a.js`
+    fn output_path(out_dir: &Path, path: &Path) -> PathBuf {
+        path.components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .fold(out_dir.to_path_buf(), |output_path, component| output_path.join(component))
+    }
+}
+
+fn gzip_size(s: &str) -> usize {
+    let mut e = GzEncoder::new(Vec::new(), Compression::best());
+    e.write_all(s.as_bytes()).unwrap();
+    let s = e.finish().unwrap();
+    s.len()
+}
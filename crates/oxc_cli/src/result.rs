@@ -15,16 +15,44 @@ pub enum CliRunResult {
         number_of_rules: usize,
         number_of_files: usize,
         number_of_warnings: usize,
-        number_of_diagnostics: usize,
+        /// Diagnostics which are not warnings, they fail the run
+        number_of_errors: usize,
         max_warnings_exceeded: bool,
+        number_of_fixed_files: usize,
+        /// The largest number of fix passes of a file
+        number_of_fix_passes: usize,
+        /// The fixes are printed as diffs, the files are not written
+        fix_dry_run: bool,
+        format: crate::LintFormat,
     },
     TypeCheckResult {
         duration: std::time::Duration,
         number_of_diagnostics: usize,
     },
+    InvalidConfig {
+        path: PathBuf,
+        error: crate::ConfigError,
+    },
+    FormatResult {
+        duration: std::time::Duration,
+        mode: crate::FormatMode,
+        number_of_files: usize,
+        number_of_changed_files: usize,
+        /// Number of files with syntax or IO errors
+        number_of_errors: usize,
+    },
+    MinifyResult {
+        duration: std::time::Duration,
+        number_of_files: usize,
+        /// Number of files with syntax or IO errors
+        number_of_errors: usize,
+        /// The minified code is printed to stdout
+        to_stdout: bool,
+    },
 }
 
 impl Termination for CliRunResult {
+    #[allow(clippy::too_many_lines)]
     fn report(self) -> ExitCode {
         match self {
             Self::None => ExitCode::from(0),
@@ -36,30 +64,55 @@ impl Termination for CliRunResult {
                 println!("IO Error: {e}");
                 ExitCode::from(1)
             }
+            Self::InvalidConfig { path, error } => {
+                println!("Invalid config {}: {error}", path.display());
+                ExitCode::from(1)
+            }
             Self::LintResult {
                 duration,
                 number_of_rules,
                 number_of_files,
                 number_of_warnings,
-                number_of_diagnostics,
+                number_of_errors,
                 max_warnings_exceeded,
+                number_of_fixed_files,
+                number_of_fix_passes,
+                fix_dry_run,
+                format,
             } => {
+                // The diagnostics are printed to stdout in a machine readable format, keep it clean
+                if format != crate::LintFormat::Default {
+                    return ExitCode::from(u8::from(max_warnings_exceeded || number_of_errors > 0));
+                }
+
                 let ms = duration.as_millis();
                 let cpus = num_cpus::get();
                 println!(
                     "Finished in {ms}ms on {number_of_files} files with {number_of_rules} rules using {cpus} cores."
                 );
 
+                if number_of_fixed_files > 0 {
+                    let fixed = if fix_dry_run { "Would fix" } else { "Fixed" };
+                    println!(
+                        "{fixed} {number_of_fixed_files} files in up to {number_of_fix_passes} passes."
+                    );
+                }
+
                 if max_warnings_exceeded {
                     println!("Exceeded maximum number of warnings. Found {number_of_warnings}.");
                     return ExitCode::from(1);
                 }
 
-                if number_of_diagnostics > 0 {
-                    println!("Found {number_of_diagnostics} errors.");
+                if number_of_errors > 0 {
+                    println!("Found {number_of_errors} errors and {number_of_warnings} warnings.");
                     return ExitCode::from(1);
                 }
 
+                if number_of_warnings > 0 {
+                    println!("Found {number_of_warnings} warnings.");
+                    return ExitCode::from(0);
+                }
+
                 println!("Found no errors.");
                 ExitCode::from(0)
             }
@@ -76,6 +129,57 @@ impl Termination for CliRunResult {
                 // println!("Found no errors.");
                 ExitCode::from(0)
             }
+            Self::FormatResult {
+                duration,
+                mode,
+                number_of_files,
+                number_of_changed_files,
+                number_of_errors,
+            } => {
+                // The formatted code is printed to stdout, keep it clean
+                if mode == crate::FormatMode::Print {
+                    return ExitCode::from(u8::from(number_of_errors > 0));
+                }
+
+                let ms = duration.as_millis();
+                let cpus = num_cpus::get();
+                println!("Finished in {ms}ms on {number_of_files} files using {cpus} cores.");
+
+                if number_of_errors > 0 {
+                    println!("Found {number_of_errors} files with errors.");
+                    return ExitCode::from(1);
+                }
+
+                if mode == crate::FormatMode::Write {
+                    println!("Formatted {number_of_changed_files} files.");
+                    return ExitCode::from(0);
+                }
+
+                if number_of_changed_files > 0 {
+                    println!("Found {number_of_changed_files} files that are not formatted.");
+                    return ExitCode::from(1);
+                }
+
+                println!("All files are formatted.");
+                ExitCode::from(0)
+            }
+            Self::MinifyResult { duration, number_of_files, number_of_errors, to_stdout } => {
+                // The minified code is printed to stdout, keep it clean
+                if to_stdout {
+                    return ExitCode::from(u8::from(number_of_errors > 0));
+                }
+
+                let ms = duration.as_millis();
+                let cpus = num_cpus::get();
+                println!("Finished in {ms}ms on {number_of_files} files using {cpus} cores.");
+
+                if number_of_errors > 0 {
+                    println!("Found {number_of_errors} files with errors.");
+                    return ExitCode::from(1);
+                }
+
+                ExitCode::from(0)
+            }
         }
     }
 }
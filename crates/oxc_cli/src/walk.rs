@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use ignore::{overrides::OverrideBuilder, DirEntry, WalkBuilder};
 use oxc_span::VALID_EXTENSIONS;
@@ -10,22 +10,34 @@ pub struct Walk {
 }
 
 impl Walk {
-    /// # Panics
     pub fn new(options: &LintOptions) -> Self {
-        let mut inner = WalkBuilder::new(&options.paths[0]);
+        let ignore_path = (!options.no_ignore).then_some(options.ignore_path.as_path());
+        Self::from_paths(&options.paths, ignore_path, &options.ignore_pattern)
+    }
+
+    /// Walk `paths` recursively.
+    /// `ignore_path` is the custom ignore file, ignore files and `ignore_pattern` are skipped when it is `None`.
+    ///
+    /// # Panics
+    pub fn from_paths(
+        paths: &[PathBuf],
+        ignore_path: Option<&Path>,
+        ignore_pattern: &[String],
+    ) -> Self {
+        let mut inner = WalkBuilder::new(&paths[0]);
 
-        if let Some(paths) = options.paths.get(1..) {
+        if let Some(paths) = paths.get(1..) {
             for path in paths {
                 inner.add(path);
             }
         }
 
-        if !options.no_ignore {
-            inner.add_custom_ignore_filename(&options.ignore_path);
+        if let Some(ignore_path) = ignore_path {
+            inner.add_custom_ignore_filename(ignore_path);
 
-            if !options.ignore_pattern.is_empty() {
+            if !ignore_pattern.is_empty() {
                 let mut override_builder = OverrideBuilder::new(Path::new("/"));
-                for pattern in &options.ignore_pattern {
+                for pattern in ignore_pattern {
                     // TODO: check this command arg parser
                     override_builder.add(pattern).unwrap();
                 }
@@ -27,6 +27,9 @@ fn main() {
     }
 
     let formatter_options = FormatterOptions::default();
-    let printed = Formatter::new(source_text.len(), formatter_options).build(&ret.program);
+    let mut formatter = Formatter::new(source_text.len(), formatter_options);
+    formatter.with_source_text(&source_text);
+    formatter.with_comments(&source_text, &ret.trivias);
+    let printed = formatter.build(&ret.program).code;
     println!("{printed}");
 }
@@ -0,0 +1,187 @@
+//! Verification of the formatted code
+//!
+//! The formatted code is parsed again and its AST is compared with the AST of the original code,
+//! ignoring spans and the differences the formatter is allowed to make:
+//! parentheses (kept as `ParenthesizedExpression` by the parser), trailing commas,
+//! braces around the consequent of `if` and insignificant whitespace in JSX text.
+//! Formatting the formatted code again must not change it.
+
+use std::fmt;
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Program;
+use oxc_parser::{Parser, ParserReturn};
+use oxc_span::SourceType;
+use serde_json::Value;
+
+use crate::{Formatter, FormatterOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckError {
+    /// The formatted code has syntax errors
+    ParseError,
+    /// The formatted code parses to a different AST
+    AstChanged,
+    /// Formatting the formatted code changes it again
+    NotIdempotent,
+    /// The AST could not be serialized for comparison
+    SerializeError,
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError => write!(f, "Formatted code has syntax errors"),
+            Self::AstChanged => write!(f, "Formatted code has a different AST"),
+            Self::NotIdempotent => write!(f, "Formatting the formatted code changes it again"),
+            Self::SerializeError => write!(f, "The AST could not be serialized for comparison"),
+        }
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+/// Format `source_text` with its comments and verify the formatted code, see [CheckError].
+/// Returns the formatted code.
+///
+/// # Errors
+///
+/// * [CheckError::ParseError] when the formatted code does not parse
+/// * [CheckError::AstChanged] when the formatted code parses to a different AST
+/// * [CheckError::NotIdempotent] when formatting the formatted code changes it
+/// * [CheckError::SerializeError] when either AST cannot be serialized for the comparison
+pub fn check(
+    source_text: &str,
+    source_type: SourceType,
+    options: FormatterOptions,
+) -> Result<String, CheckError> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let formatted = format(source_text, &ret, options);
+
+    {
+        let formatted_ret = Parser::new(&allocator, &formatted, source_type).parse();
+        if !formatted_ret.errors.is_empty() {
+            return Err(CheckError::ParseError);
+        }
+        if normalized_ast(&ret.program)? != normalized_ast(&formatted_ret.program)? {
+            return Err(CheckError::AstChanged);
+        }
+        if format(&formatted, &formatted_ret, options) != formatted {
+            return Err(CheckError::NotIdempotent);
+        }
+    }
+    Ok(formatted)
+}
+
+fn format(source_text: &str, ret: &ParserReturn<'_>, options: FormatterOptions) -> String {
+    let mut formatter = Formatter::new(source_text.len(), options);
+    formatter.with_source_text(source_text);
+    formatter.with_comments(source_text, &ret.trivias);
+    formatter.build(&ret.program).code
+}
+
+/// The ESTree JSON of `program` without positions and the differences allowed in formatted code
+fn normalized_ast(program: &Program<'_>) -> Result<Value, CheckError> {
+    let mut value = serde_json::to_value(program).map_err(|_| CheckError::SerializeError)?;
+    normalize(&mut value);
+    Ok(value)
+}
+
+fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("ParenthesizedExpression")
+                && let Some(expression) = object.remove("expression")
+            {
+                *value = expression;
+                normalize(value);
+                return;
+            }
+            object.remove("start");
+            object.remove("end");
+            object.remove("trailing_comma");
+            // The formatter always prints braces around the consequent of `if`
+            if object.get("type").and_then(Value::as_str) == Some("IfStatement")
+                && let Some(consequent) = object.get_mut("consequent")
+                && consequent.get("type").and_then(Value::as_str) == Some("BlockStatement")
+                && let Some(Value::Array(body)) = consequent.get_mut("body")
+                && body.len() == 1
+                && let Some(statement) = body.pop()
+            {
+                *consequent = statement;
+            }
+            if matches!(
+                object.get("type").and_then(Value::as_str),
+                Some("JSXElement" | "JSXFragment")
+            ) && let Some(Value::Array(children)) = object.get_mut("children")
+            {
+                normalize_jsx_children(children);
+            }
+            object.values_mut().for_each(normalize);
+        }
+        Value::Array(values) => values.iter_mut().for_each(normalize),
+        _ => {}
+    }
+}
+
+/// Replace text and `{" "}` children by the text they render,
+/// with whitespace collapsed because the formatter prints single spaces.
+fn normalize_jsx_children(children: &mut Vec<Value>) {
+    let mut normalized = vec![];
+    let mut text = String::new();
+    for child in children.drain(..) {
+        let rendered = match child.get("type").and_then(Value::as_str) {
+            Some("JSXText") => child.get("value").and_then(Value::as_str).map(jsx_text_content),
+            Some("JSXExpressionContainer")
+                if child.pointer("/expression/type").and_then(Value::as_str)
+                    == Some("StringLiteral")
+                    && child.pointer("/expression/value").and_then(Value::as_str) == Some(" ") =>
+            {
+                Some(" ".to_string())
+            }
+            _ => None,
+        };
+        if let Some(rendered) = rendered {
+            text.push_str(&rendered);
+            continue;
+        }
+        push_jsx_text(&mut normalized, &mut text);
+        normalized.push(child);
+    }
+    push_jsx_text(&mut normalized, &mut text);
+    *children = normalized;
+}
+
+fn push_jsx_text(children: &mut Vec<Value>, text: &mut String) {
+    let collapsed = text.split(' ').filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" ");
+    let collapsed = match (text.starts_with(' '), text.ends_with(' ')) {
+        _ if collapsed.is_empty() && !text.is_empty() => " ".to_string(),
+        (true, true) => format!(" {collapsed} "),
+        (true, false) => format!(" {collapsed}"),
+        (false, true) => format!("{collapsed} "),
+        (false, false) => collapsed,
+    };
+    text.clear();
+    if !collapsed.is_empty() {
+        children.push(serde_json::json!({ "type": "JSXText", "value": collapsed }));
+    }
+}
+
+/// The text rendered by JSX text: lines are trimmed, empty lines are removed and the rest is joined with spaces.
+fn jsx_text_content(value: &str) -> String {
+    let lines = value.split('\n').collect::<Vec<_>>();
+    let last = lines.len() - 1;
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.replace(['\t', '\r'], " ");
+            let line = if index == 0 { line.as_str() } else { line.trim_start_matches(' ') };
+            let line = if index == last { line } else { line.trim_end_matches(' ') };
+            line.to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,210 @@
+//! Comment re-attachment
+//!
+//! The AST does not own comments, they are stored separately in [Trivias] keyed by position.
+//! Comments are kept in a queue sorted by position and flushed while printing:
+//!
+//! * leading comments are printed on their own lines before the statement, class element,
+//!   object property or switch case that follows them
+//! * trailing comments are printed after the node that ends on the same line
+//! * dangling comments (e.g. inside an empty block) are printed before the closing `}`
+//! * comments inside expressions are printed inline before the expression that follows them
+//! * comments around parameters, binding patterns and import / export specifiers are printed
+//!   inline before or after the node they are next to
+
+use std::collections::VecDeque;
+
+use oxc_ast::Trivias;
+use oxc_span::Span;
+
+use crate::Formatter;
+
+#[derive(Debug)]
+pub struct PendingComment {
+    /// Span including the `//` or `/* */` delimiters
+    span: Span,
+    single_line: bool,
+    /// The comment text including the delimiters
+    text: String,
+    /// The end offsets of the code before this comment when the comment is on the same line,
+    /// without and with a trailing `,` or `;`
+    trailing_of: Option<(u32, u32)>,
+}
+
+pub fn collect_comments(source_text: &str, trivias: &Trivias) -> VecDeque<PendingComment> {
+    let mut comments: VecDeque<PendingComment> = VecDeque::new();
+    for (start, comment) in trivias.comments() {
+        let single_line = comment.is_single_line();
+        // Trivias store the comment content, without the delimiters
+        let span = if single_line {
+            Span::new(start - 2, comment.end())
+        } else {
+            Span::new(start - 2, comment.end() + 2)
+        };
+        let trailing_of = trailing_of(source_text, span.start, comments.back());
+        let text = span.source_text(source_text).trim_end().to_string();
+        comments.push_back(PendingComment { span, single_line, text, trailing_of });
+    }
+    comments
+}
+
+/// Scan backwards from `start` on the same line, skipping whitespace, a single `,` or `;`,
+/// and a previous comment on the same line.
+fn trailing_of(source_text: &str, start: u32, prev: Option<&PendingComment>) -> Option<(u32, u32)> {
+    let before = &source_text[..start as usize];
+    let trimmed = before.trim_end_matches([' ', '\t']);
+    #[allow(clippy::cast_possible_truncation)]
+    let end = trimmed.len() as u32;
+    if trimmed.is_empty() || trimmed.ends_with(['\n', '\r', '\u{2028}', '\u{2029}']) {
+        return None;
+    }
+    if let Some(prev) = prev && prev.span.end == end {
+        return prev.trailing_of;
+    }
+    let without_punct =
+        trimmed.strip_suffix([',', ';']).map_or(trimmed, |s| s.trim_end_matches([' ', '\t']));
+    #[allow(clippy::cast_possible_truncation)]
+    Some((without_punct.len() as u32, end))
+}
+
+impl Formatter {
+    /// Keep the comments from `trivias` in the output.
+    pub fn with_comments(&mut self, source_text: &str, trivias: &Trivias) {
+        self.comments = collect_comments(source_text, trivias);
+    }
+
+    /// Print all comments ending before `position`, each on its own line.
+    /// The cursor is expected to be at the start of a line.
+    pub fn print_leading_comments(&mut self, position: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| comment.span.end <= position) {
+            self.print_indent();
+            self.print_comment_text(&comment);
+            self.print_newline();
+        }
+    }
+
+    /// Print comments that start on the same line right after a node ending at `end`.
+    /// Inside of a list, a line comment breaks the enclosing group,
+    /// the caller is expected to print a line after it.
+    pub fn print_trailing_comments(&mut self, end: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| {
+            comment.trailing_of.is_some_and(|(a, b)| a == end || b == end)
+        }) {
+            let had_newline = self.pop_newline();
+            self.print_space();
+            self.print_comment_text(&comment);
+            if had_newline {
+                self.print_newline();
+            } else if comment.single_line {
+                self.mark_hard_break();
+            }
+        }
+    }
+
+    /// Print comments before `position` inline, used inside expressions.
+    pub fn print_inline_comments(&mut self, position: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| comment.span.end <= position) {
+            self.print_comment_text(&comment);
+            if comment.single_line {
+                self.print_newline();
+                self.print_indent();
+            } else {
+                self.print_space();
+            }
+        }
+    }
+
+    /// Print block comments right after a node ending at `end` inline,
+    /// e.g. `/* b */` in `function f(a /* b */, c) {}`.
+    /// Line comments are left to the next node, the `,` after them would be commented out.
+    pub fn print_inline_trailing_comments(&mut self, end: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| {
+            !comment.single_line && comment.trailing_of == Some((end, end))
+        }) {
+            self.print_space();
+            self.print_comment_text(&comment);
+        }
+    }
+
+    /// Print comments before `position` inside of a group, e.g. before an object property,
+    /// each followed by a line.
+    pub fn print_leading_comments_in_group(&mut self, position: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| comment.span.end <= position) {
+            self.print_comment_text(&comment);
+            if comment.single_line {
+                self.mark_hard_break();
+            }
+            self.print_line();
+        }
+    }
+
+    /// Print comments before `position` at the end of a group, e.g. before the closing `}` of an object,
+    /// each preceded by a line.
+    pub fn print_dangling_comments(&mut self, position: u32) {
+        while let Some(comment) = self.pop_comment_if(|comment| comment.span.end <= position) {
+            self.print_line();
+            self.print_comment_text(&comment);
+            if comment.single_line {
+                self.mark_hard_break();
+            }
+        }
+    }
+
+    /// Print comments before `position` separated by spaces, e.g. the only content of `{/* comment */}` in JSX.
+    pub fn print_comments_before_close(&mut self, position: u32) {
+        let mut first = true;
+        while let Some(comment) = self.pop_comment_if(|comment| comment.span.end <= position) {
+            if !first {
+                self.print_space();
+            }
+            first = false;
+            self.print_comment_text(&comment);
+            if comment.single_line {
+                self.print_newline();
+                self.print_indent();
+            }
+        }
+    }
+
+    /// Drop the comments outside of `span`, used when formatting only a part of the source.
+    pub(crate) fn retain_comments_within(&mut self, span: Span) {
+        self.comments
+            .retain(|comment| span.start <= comment.span.start && comment.span.end <= span.end);
+    }
+
+    /// Whether there are comments left before `position`.
+    pub fn has_comments_before(&self, position: u32) -> bool {
+        self.comments.front().is_some_and(|comment| comment.span.end <= position)
+    }
+
+    /// Print all remaining comments, used at the end of the program.
+    pub fn print_remaining_comments(&mut self) {
+        self.print_leading_comments(u32::MAX);
+    }
+
+    fn pop_comment_if(
+        &mut self,
+        predicate: impl FnOnce(&PendingComment) -> bool,
+    ) -> Option<PendingComment> {
+        if self.comments.front().is_some_and(predicate) { self.comments.pop_front() } else { None }
+    }
+
+    /// Multi-line comments such as JSDoc blocks are re-indented
+    /// when every continuation line starts with `*`.
+    fn print_comment_text(&mut self, comment: &PendingComment) {
+        let mut lines = comment.text.lines();
+        let Some(first) = lines.next() else { return };
+        self.print_str(first.as_bytes());
+        let rest = lines.collect::<Vec<_>>();
+        let reindent = rest.iter().all(|line| line.trim_start().starts_with('*'));
+        for line in rest {
+            self.print_newline();
+            if reindent {
+                self.print_indent();
+                self.print_space();
+                self.print_str(line.trim_start().as_bytes());
+            } else {
+                self.print_str(line.as_bytes());
+            }
+        }
+    }
+}
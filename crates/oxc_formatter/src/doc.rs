@@ -0,0 +1,338 @@
+//! Document IR for line width aware printing
+//!
+//! `Gen` implementations do not write into the output directly,
+//! everything is recorded as a flat stream of [Doc] commands.
+//! Groups are delimited by [Doc::GroupStart] and [Doc::GroupEnd],
+//! a group is printed flat (all [Doc::Line]s as their flat text) when it fits in the remaining width,
+//! otherwise the group is broken and its direct lines become line breaks.
+//! This is the algorithm from Wadler's "A prettier printer", as used by Prettier.
+//!
+//! The recorded commands are laid out every time a line break is printed outside of all groups,
+//! so the rest of the current line is known when deciding whether a group fits.
+
+use crate::Formatter;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Doc {
+    /// Text in [Formatter::doc_text]
+    Text {
+        start: usize,
+        end: usize,
+    },
+    /// The current indentation, printed at the start of a line
+    Indentation,
+    /// A line break when the enclosing group is broken, otherwise the flat text
+    Line(&'static str),
+    /// Text only printed when the enclosing group is broken
+    IfBreak(&'static str),
+    Indent,
+    Dedent,
+    /// Indent only when the enclosing group, or the given group, is broken
+    IndentIfBreak(Option<GroupId>),
+    DedentIfBreak,
+    GroupStart {
+        end: usize,
+        should_break: bool,
+    },
+    GroupEnd,
+    /// Source map position of the following text
+    Mapping(u32),
+}
+
+/// Identifies a group printed by [Formatter::print_group]
+#[derive(Debug, Clone, Copy)]
+pub struct GroupId(usize);
+
+/// An open group while printing
+#[derive(Debug)]
+pub struct Group {
+    start: usize,
+    /// A line break was printed inside this group, it can never be printed flat
+    has_hard_break: bool,
+    /// See [Formatter::hug]
+    hug: bool,
+}
+
+impl Formatter {
+    /// Print everything printed by `f` as a group.
+    ///
+    /// Lines inside the group are printed flat if the whole group fits on the current line,
+    /// otherwise they are line breaks.
+    /// A group containing a hard line break or marked with `should_break` is always broken,
+    /// and so are its parent groups.
+    pub fn print_group<F: FnOnce(&mut Self)>(&mut self, should_break: bool, f: F) -> GroupId {
+        let start = self.docs.len();
+        self.docs.push(Doc::GroupStart { end: start, should_break });
+        self.groups.push(Group { start, has_hard_break: false, hug: false });
+        f(self);
+        let end = self.docs.len();
+        self.docs.push(Doc::GroupEnd);
+        let Some(group) = self.groups.pop() else { return GroupId(start) };
+        if let Doc::GroupStart { end: group_end, should_break } = &mut self.docs[group.start] {
+            *group_end = end;
+            *should_break |= group.has_hard_break;
+        }
+        if group.has_hard_break || should_break {
+            self.mark_hard_break();
+        }
+        GroupId(start)
+    }
+
+    /// Hard line breaks printed from now on do not break the current group.
+    ///
+    /// Used for hugging the last call argument, e.g. the function body in `foo(function () { .. })`
+    /// is printed over multiple lines while the arguments stay on the same line,
+    /// and for multi-line JSX in parentheses.
+    pub fn hug(&mut self) {
+        if let Some(group) = self.groups.last_mut() {
+            group.hug = true;
+        }
+    }
+
+    /// Nothing when the enclosing group is printed flat, a line break otherwise.
+    #[inline]
+    pub fn print_soft_line(&mut self) {
+        self.docs.push(Doc::Line(""));
+    }
+
+    /// A space when the enclosing group is printed flat, a line break otherwise.
+    #[inline]
+    pub fn print_line(&mut self) {
+        self.docs.push(Doc::Line(" "));
+    }
+
+    /// `flat` when the enclosing group is printed flat, a line break otherwise.
+    #[inline]
+    pub fn print_line_with(&mut self, flat: &'static str) {
+        self.docs.push(Doc::Line(flat));
+    }
+
+    #[inline]
+    pub fn print_if_break(&mut self, text: &'static str) {
+        self.docs.push(Doc::IfBreak(text));
+    }
+
+    /// Indent the following lines only when the enclosing group is broken.
+    #[inline]
+    pub fn indent_if_break(&mut self) {
+        self.docs.push(Doc::IndentIfBreak(None));
+    }
+
+    /// Indent the following lines only when `group` is broken.
+    #[inline]
+    pub fn indent_if_group_breaks(&mut self, group: GroupId) {
+        self.docs.push(Doc::IndentIfBreak(Some(group)));
+    }
+
+    #[inline]
+    pub fn dedent_if_break(&mut self) {
+        self.docs.push(Doc::DedentIfBreak);
+    }
+
+    pub(crate) fn push_text(&mut self, text: &[u8]) {
+        if text.is_empty() {
+            return;
+        }
+        if text.contains(&b'\n') {
+            self.mark_hard_break();
+        }
+        let start = self.doc_text.len();
+        self.doc_text.extend_from_slice(text);
+        let end = self.doc_text.len();
+        if let Some(Doc::Text { end: last_end, .. }) = self.docs.last_mut() && *last_end == start {
+            *last_end = end;
+        } else {
+            self.docs.push(Doc::Text { start, end });
+        }
+    }
+
+    /// Position in the recorded commands, see [Formatter::has_hard_break_since]
+    pub(crate) fn doc_position(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Whether a line break was printed since `position`, including hugged ones
+    pub(crate) fn has_hard_break_since(&self, position: usize) -> bool {
+        self.docs.get(position..).unwrap_or_default().iter().any(|doc| match doc {
+            Doc::GroupStart { should_break, .. } => *should_break,
+            Doc::Text { start, end } => self.doc_text[*start..*end].contains(&b'\n'),
+            _ => false,
+        })
+    }
+
+    pub(crate) fn mark_hard_break(&mut self) {
+        if let Some(group) = self.groups.last_mut() && !group.hug {
+            group.has_hard_break = true;
+        }
+    }
+
+    pub(crate) fn last_printed_char(&self) -> Option<u8> {
+        for doc in self.docs.iter().rev() {
+            match doc {
+                Doc::Text { end, .. } => return Some(self.doc_text[end - 1]),
+                Doc::Indentation | Doc::Line(_) | Doc::IfBreak(_) => return None,
+                _ => {}
+            }
+        }
+        self.code.last().copied()
+    }
+
+    /// Remove a line break at the end of the output, returns whether there was one.
+    pub(crate) fn pop_newline(&mut self) -> bool {
+        for index in (0..self.docs.len()).rev() {
+            match &mut self.docs[index] {
+                Doc::Text { start, end } => {
+                    if self.doc_text[*end - 1] != b'\n' {
+                        return false;
+                    }
+                    *end -= 1;
+                    if *end == self.doc_text.len() - 1 {
+                        self.doc_text.pop();
+                    }
+                    if start == end {
+                        self.docs.remove(index);
+                    }
+                    return true;
+                }
+                Doc::Indentation | Doc::Line(_) | Doc::IfBreak(_) => return false,
+                _ => {}
+            }
+        }
+        if self.code.last() == Some(&b'\n') {
+            self.code.pop();
+            return true;
+        }
+        false
+    }
+
+    /// Lay out all recorded commands into the output.
+    pub(crate) fn flush(&mut self) {
+        if self.docs.is_empty() {
+            return;
+        }
+        let mut docs = std::mem::take(&mut self.docs);
+        // Whether each open group is broken, everything outside of groups is broken
+        let mut modes: Vec<bool> = vec![];
+        for index in 0..docs.len() {
+            let broken = modes.last().copied().unwrap_or(true);
+            let doc = docs[index];
+            match doc {
+                Doc::Text { start, end } => {
+                    self.code.extend_from_slice(&self.doc_text[start..end]);
+                }
+                Doc::Indentation => self.write_indentation(),
+                Doc::Line(flat) => {
+                    if broken {
+                        while self.code.last() == Some(&b' ') {
+                            self.code.pop();
+                        }
+                        self.code.push(b'\n');
+                        self.write_indentation();
+                    } else {
+                        self.code.extend_from_slice(flat.as_bytes());
+                    }
+                }
+                Doc::IfBreak(text) => {
+                    if broken {
+                        self.code.extend_from_slice(text.as_bytes());
+                    }
+                }
+                Doc::Indent => self.indentation += usize::from(self.options.indentation),
+                Doc::Dedent => self.indentation -= usize::from(self.options.indentation),
+                Doc::IndentIfBreak(group) => {
+                    let broken = match group {
+                        Some(GroupId(start)) => {
+                            matches!(
+                                docs.get(start),
+                                Some(Doc::GroupStart { should_break: true, .. })
+                            )
+                        }
+                        None => broken,
+                    };
+                    if broken {
+                        self.indentation += usize::from(self.options.indentation);
+                    }
+                    self.if_break_indents.push(broken);
+                }
+                Doc::DedentIfBreak => {
+                    if self.if_break_indents.pop() == Some(true) {
+                        self.indentation -= usize::from(self.options.indentation);
+                    }
+                }
+                Doc::GroupStart { end, should_break } => {
+                    // A group with a hard line break is broken even inside of a flat group,
+                    // which happens when the hard line break is hugged
+                    let group_broken =
+                        should_break || (broken && !self.fits(&docs, index + 1, end));
+                    // Remember the decision for `Doc::IndentIfBreak(Some(group))`
+                    docs[index] = Doc::GroupStart { end, should_break: group_broken };
+                    modes.push(group_broken);
+                }
+                Doc::GroupEnd => {
+                    modes.pop();
+                }
+                Doc::Mapping(position) => {
+                    if let Some(source_map) = &mut self.source_map {
+                        let output = unsafe { std::str::from_utf8_unchecked(&self.code) };
+                        source_map.add_mapping(output, position, None);
+                    }
+                }
+            }
+        }
+        docs.clear();
+        self.docs = docs;
+        self.doc_text.clear();
+    }
+
+    /// Whether the group `docs[start..end]` printed flat,
+    /// followed by the rest of the line, fits into the remaining width.
+    fn fits(&self, docs: &[Doc], start: usize, end: usize) -> bool {
+        let line_start = self.code.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let column = text_width(&self.code[line_start..]);
+        let mut remaining = self.options.print_width.saturating_sub(column);
+        // Whether each nested group is broken, e.g. a hugged group containing a hard line break
+        let mut nested: Vec<bool> = vec![];
+        for (index, doc) in docs.iter().enumerate().skip(start) {
+            let in_group = index < end;
+            let in_broken_group = nested.last() == Some(&true);
+            let width = match doc {
+                Doc::GroupStart { should_break, .. } => {
+                    nested.push(*should_break);
+                    0
+                }
+                Doc::GroupEnd => {
+                    nested.pop();
+                    0
+                }
+                Doc::Text { start, end } => {
+                    let text = &self.doc_text[*start..*end];
+                    if let Some(newline) = text.iter().position(|b| *b == b'\n') {
+                        return text_width(&text[..newline]) <= remaining;
+                    }
+                    text_width(text)
+                }
+                // The rest of the line ends at the first possible line break
+                Doc::Line(_) if !in_group || in_broken_group => return true,
+                Doc::Line(flat) => flat.len(),
+                Doc::IfBreak(text) if !in_group || in_broken_group => text.len(),
+                _ => 0,
+            };
+            if width > remaining {
+                return false;
+            }
+            remaining -= width;
+        }
+        true
+    }
+
+    fn write_indentation(&mut self) {
+        for _ in 0..self.indentation {
+            self.code.push(b' ');
+        }
+    }
+}
+
+/// Number of characters, which is good enough as an approximation of the display width
+fn text_width(text: &[u8]) -> usize {
+    text.iter().filter(|b| (**b & 0xC0) != 0x80).count()
+}
@@ -1,9 +1,22 @@
 use oxc_allocator::{Box, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::ast::*;
-use oxc_syntax::operator::BinaryOperator;
-
-use crate::{Formatter, Separator};
+use oxc_span::GetSpan;
+use oxc_syntax::{
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+    precedence::Precedence,
+};
+
+use crate::{
+    gen_jsx::{
+        is_jsx, print_expression_without_jsx_parens, print_jsx_conditional, print_jsx_in_parens,
+    },
+    gen_ts::{
+        print_accessibility, print_binding_pattern, print_declare, print_decorators,
+        print_return_type, print_type_annotation, print_type_arguments, print_type_parameters,
+    },
+    Formatter, TrailingComma,
+};
 
 pub trait Gen {
     fn gen(&self, p: &mut Formatter);
@@ -21,28 +34,37 @@ where
 impl<'a> Gen for Program<'a> {
     fn gen(&self, p: &mut Formatter) {
         for directive in &self.directives {
+            p.print_leading_comments(directive.span.start);
             directive.gen(p);
+            p.print_trailing_comments(directive.span.end);
         }
-        for stmt in &self.body {
-            p.print_semicolon_if_needed();
-            stmt.gen(p);
-        }
+        p.print_statements(&self.body);
+        p.print_remaining_comments();
     }
 }
 
 impl<'a> Gen for Directive<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
-        p.print(b'"');
-        p.print_str(self.directive.as_bytes());
-        p.print(b'"');
-        p.print_semicolon();
-        p.print_newline();
+        // Directives are printed as written unless the quotes can be changed without escaping,
+        // because escapes change the meaning of a directive
+        if self.directive.contains(['"', '\'']) && let Some(raw) = p.source_text_of(self.expression.span) {
+            let raw = raw.to_string();
+            p.print_str(raw.as_bytes());
+        } else {
+            let quote = if self.directive.contains('"') { b'\'' } else { p.options().quote_style.as_byte() };
+            p.print(quote);
+            p.print_str(self.directive.as_bytes());
+            p.print(quote);
+        }
+        p.print_semicolon_after_statement();
     }
 }
 
 impl<'a> Gen for Statement<'a> {
     fn gen(&self, p: &mut Formatter) {
+        let span = self.span();
+        p.print_leading_comments(span.start);
         match self {
             Self::BlockStatement(stmt) => stmt.gen(p),
             Self::BreakStatement(stmt) => stmt.gen(p),
@@ -56,22 +78,34 @@ impl<'a> Gen for Statement<'a> {
             Self::ForStatement(stmt) => stmt.gen(p),
             Self::IfStatement(stmt) => stmt.gen(p),
             Self::LabeledStatement(stmt) => stmt.gen(p),
-            Self::ModuleDeclaration(decl) => decl.gen(p),
+            Self::ModuleDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
             Self::ReturnStatement(stmt) => stmt.gen(p),
             Self::SwitchStatement(stmt) => stmt.gen(p),
             Self::ThrowStatement(stmt) => stmt.gen(p),
             Self::TryStatement(stmt) => stmt.gen(p),
             Self::WhileStatement(stmt) => stmt.gen(p),
             Self::WithStatement(stmt) => stmt.gen(p),
-            Self::Declaration(decl) => decl.gen(p),
+            Self::Declaration(decl) => {
+                p.print_indent();
+                p.add_source_mapping(span.start);
+                decl.gen(p);
+            }
         }
+        p.print_trailing_comments(span.end);
     }
 }
 
 impl<'a> Gen for ExpressionStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
-        self.expression.gen(p);
+        p.add_source_mapping(self.span.start);
+        if !p.options().semi && starts_with_asi_hazard(&self.expression) {
+            p.print_semicolon();
+        }
+        print_expression_without_jsx_parens(&self.expression, p);
         if let Expression::Identifier(ident) = &self.expression
         && ident.name == "let" {
             p.print_semicolon();
@@ -84,6 +118,7 @@ impl<'a> Gen for ExpressionStatement<'a> {
 impl Gen for EmptyStatement {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_semicolon();
         p.print_newline();
     }
@@ -92,16 +127,79 @@ impl Gen for EmptyStatement {
 impl<'a> Gen for IfStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         print_if(self, p);
     }
 }
 
+/// Whether a statement starting with `expr` continues the previous line when semicolons are omitted
+fn starts_with_asi_hazard(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::ParenthesizedExpression(_)
+        | Expression::ArrayExpression(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::ArrowExpression(_)
+        | Expression::JSXElement(_)
+        | Expression::JSXFragment(_) => true,
+        Expression::UnaryExpression(expr) => {
+            matches!(expr.operator, UnaryOperator::UnaryPlus | UnaryOperator::UnaryNegation)
+        }
+        Expression::UpdateExpression(expr) => {
+            expr.prefix
+                || matches!(&expr.argument, SimpleAssignmentTarget::MemberAssignmentTarget(member) if starts_with_asi_hazard(member.object()))
+        }
+        Expression::BinaryExpression(expr) => starts_with_asi_hazard(&expr.left),
+        Expression::LogicalExpression(expr) => starts_with_asi_hazard(&expr.left),
+        Expression::ConditionalExpression(expr) => starts_with_asi_hazard(&expr.test),
+        Expression::SequenceExpression(expr) => {
+            expr.expressions.first().is_some_and(starts_with_asi_hazard)
+        }
+        Expression::AssignmentExpression(expr) => match &expr.left {
+            AssignmentTarget::AssignmentTargetPattern(
+                AssignmentTargetPattern::ArrayAssignmentTarget(_),
+            ) => true,
+            AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::MemberAssignmentTarget(member),
+            ) => starts_with_asi_hazard(member.object()),
+            _ => false,
+        },
+        Expression::MemberExpression(expr) => starts_with_asi_hazard(expr.object()),
+        Expression::CallExpression(expr) => starts_with_asi_hazard(&expr.callee),
+        Expression::TaggedTemplateExpression(expr) => starts_with_asi_hazard(&expr.tag),
+        Expression::ChainExpression(expr) => match &expr.expression {
+            ChainElement::CallExpression(call) => starts_with_asi_hazard(&call.callee),
+            ChainElement::MemberExpression(member) => starts_with_asi_hazard(member.object()),
+        },
+        Expression::TSAsExpression(expr) => starts_with_asi_hazard(&expr.expression),
+        Expression::TSSatisfiesExpression(expr) => starts_with_asi_hazard(&expr.expression),
+        Expression::TSNonNullExpression(expr) => starts_with_asi_hazard(&expr.expression),
+        _ => false,
+    }
+}
+
+/// Print the parenthesized test of `if`, `while` etc.,
+/// which is broken onto its own line when it does not fit.
+fn print_test(test: &Expression<'_>, p: &mut Formatter) {
+    p.print(b'(');
+    p.print_group(false, |p| {
+        p.indent_if_break();
+        p.print_soft_line();
+        // The operands are broken together with the parentheses
+        match binaryish_operands(test) {
+            Some(operands) => print_binaryish_operands(operands, false, p),
+            None => test.gen(p),
+        }
+        p.dedent_if_break();
+        p.print_soft_line();
+    });
+    p.print(b')');
+}
+
 fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
     p.print_str(b"if");
     p.print_space();
-    p.print(b'(');
-    if_stmt.test.gen(p);
-    p.print(b')');
+    print_test(&if_stmt.test, p);
     p.print_space();
     if let Statement::BlockStatement(block) = &if_stmt.consequent {
         p.print_block1(block);
@@ -122,15 +220,15 @@ fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
     }
     if let Some(alternate) = if_stmt.alternate.as_ref() {
         p.print_semicolon_if_needed();
-        p.print_space();
         p.print_str(b"else");
-        p.print_space();
         match alternate {
             Statement::BlockStatement(block) => {
+                p.print_space();
                 p.print_block1(block);
                 p.print_newline();
             }
             Statement::IfStatement(if_stmt) => {
+                p.print_space();
                 print_if(if_stmt, p);
             }
             _ => {
@@ -146,6 +244,7 @@ fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
 impl<'a> Gen for BlockStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_block1(self);
         p.print_newline();
     }
@@ -154,6 +253,7 @@ impl<'a> Gen for BlockStatement<'a> {
 impl<'a> Gen for ForStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"for");
         p.print_space();
         p.print(b'(');
@@ -187,6 +287,7 @@ impl<'a> Gen for ForStatement<'a> {
 impl<'a> Gen for ForInStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"for");
         gen_for_statement_brack_content(&self.left, &self.right, &self.body, b"in", p);
     }
@@ -195,6 +296,7 @@ impl<'a> Gen for ForInStatement<'a> {
 impl<'a> Gen for ForOfStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"for");
         if self.r#await {
             p.print_str(b" await");
@@ -233,11 +335,10 @@ impl<'a> Gen for ForStatementLeft<'a> {
 impl<'a> Gen for WhileStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"while");
         p.print_space();
-        p.print(b'(');
-        self.test.gen(p);
-        p.print(b')');
+        print_test(&self.test, p);
         p.print_body(&self.body);
     }
 }
@@ -245,6 +346,7 @@ impl<'a> Gen for WhileStatement<'a> {
 impl<'a> Gen for DoWhileStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"do");
         p.print_space();
         if let Statement::BlockStatement(block) = &self.body {
@@ -261,9 +363,7 @@ impl<'a> Gen for DoWhileStatement<'a> {
         }
         p.print_str(b"while");
         p.print_space();
-        p.print(b'(');
-        self.test.gen(p);
-        p.print(b')');
+        print_test(&self.test, p);
         p.print_semicolon_after_statement();
     }
 }
@@ -271,6 +371,7 @@ impl<'a> Gen for DoWhileStatement<'a> {
 impl Gen for ContinueStatement {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"continue");
         if let Some(label) = &self.label {
             p.print_space();
@@ -283,6 +384,7 @@ impl Gen for ContinueStatement {
 impl Gen for BreakStatement {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"break");
         if let Some(label) = &self.label {
             p.print_space();
@@ -295,18 +397,19 @@ impl Gen for BreakStatement {
 impl<'a> Gen for SwitchStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"switch");
         p.print_space();
-        p.print(b'(');
-        self.discriminant.gen(p);
-        p.print(b')');
+        print_test(&self.discriminant, p);
         p.print_space();
         p.print(b'{');
         p.print_newline();
         p.indent();
         for case in &self.cases {
+            p.print_leading_comments(case.span.start);
             case.gen(p);
         }
+        p.print_leading_comments(self.span.end);
         p.dedent();
         p.print_indent();
         p.print(b'}');
@@ -330,10 +433,7 @@ impl<'a> Gen for SwitchCase<'a> {
         p.print_colon();
         p.print_newline();
         p.indent();
-        for item in &self.consequent {
-            p.print_semicolon_if_needed();
-            item.gen(p);
-        }
+        p.print_statements(&self.consequent);
         p.dedent();
     }
 }
@@ -341,18 +441,47 @@ impl<'a> Gen for SwitchCase<'a> {
 impl<'a> Gen for ReturnStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"return");
         if let Some(arg) = &self.argument {
             p.print_space();
-            arg.gen(p);
+            print_return_argument(arg, p);
         }
         p.print_semicolon_after_statement();
     }
 }
 
+/// Binary expressions and sequences are wrapped in parentheses when broken over multiple lines
+fn print_return_argument(arg: &Expression<'_>, p: &mut Formatter) {
+    // The parentheses from the original source are replaced by the ones printed when broken
+    let arg = match arg {
+        Expression::ParenthesizedExpression(expr)
+            if is_binaryish(&expr.expression)
+                || matches!(expr.expression, Expression::SequenceExpression(_)) =>
+        {
+            &expr.expression
+        }
+        _ => arg,
+    };
+    if is_binaryish(arg) || matches!(arg, Expression::SequenceExpression(_)) {
+        p.print_group(false, |p| {
+            p.print_if_break("(");
+            p.indent_if_break();
+            p.print_soft_line();
+            print_expression_without_indent(arg, p);
+            p.dedent_if_break();
+            p.print_soft_line();
+            p.print_if_break(")");
+        });
+    } else {
+        arg.gen(p);
+    }
+}
+
 impl<'a> Gen for LabeledStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         self.label.gen(p);
         p.print_colon();
         p.print_newline();
@@ -363,6 +492,7 @@ impl<'a> Gen for LabeledStatement<'a> {
 impl<'a> Gen for TryStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"try");
         p.print_space();
         p.print_block1(&self.block);
@@ -391,9 +521,10 @@ impl<'a> Gen for TryStatement<'a> {
 impl<'a> Gen for ThrowStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"throw");
         p.print_space();
-        self.argument.gen(p);
+        print_return_argument(&self.argument, p);
         p.print_semicolon_after_statement();
     }
 }
@@ -401,6 +532,7 @@ impl<'a> Gen for ThrowStatement<'a> {
 impl<'a> Gen for WithStatement<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"with");
         p.print_space();
         p.print(b'(');
@@ -414,6 +546,7 @@ impl<'a> Gen for WithStatement<'a> {
 impl Gen for DebuggerStatement {
     fn gen(&self, p: &mut Formatter) {
         p.print_indent();
+        p.add_source_mapping(self.span.start);
         p.print_str(b"debugger");
         p.print_semicolon_after_statement();
     }
@@ -426,7 +559,8 @@ impl<'a> Gen for ModuleDeclaration<'a> {
             Self::ExportAllDeclaration(decl) => decl.gen(p),
             Self::ExportDefaultDeclaration(decl) => decl.gen(p),
             Self::ExportNamedDeclaration(decl) => decl.gen(p),
-            Self::TSExportAssignment(_) | Self::TSNamespaceExportDeclaration(_) => {}
+            Self::TSExportAssignment(decl) => decl.gen(p),
+            Self::TSNamespaceExportDeclaration(decl) => decl.gen(p),
         }
     }
 }
@@ -435,12 +569,10 @@ impl<'a> Gen for Declaration<'a> {
     fn gen(&self, p: &mut Formatter) {
         match self {
             Self::VariableDeclaration(stmt) => {
-                p.print_indent();
-                stmt.gen(p);
+                print_variable_declaration(stmt, true, p);
                 p.print_semicolon_after_statement();
             }
             Self::FunctionDeclaration(stmt) => {
-                p.print_indent();
                 stmt.gen(p);
                 p.print_newline();
             }
@@ -448,41 +580,128 @@ impl<'a> Gen for Declaration<'a> {
                 declaration.gen(p);
                 p.print_newline();
             }
-            Self::TSTypeAliasDeclaration(_)
-            | Self::TSInterfaceDeclaration(_)
-            | Self::TSEnumDeclaration(_)
-            | Self::TSModuleDeclaration(_)
-            | Self::TSImportEqualsDeclaration(_) => {}
+            Self::TSTypeAliasDeclaration(decl) => decl.gen(p),
+            Self::TSInterfaceDeclaration(decl) => decl.gen(p),
+            Self::TSEnumDeclaration(decl) => decl.gen(p),
+            Self::TSModuleDeclaration(decl) => decl.gen(p),
+            Self::TSImportEqualsDeclaration(decl) => decl.gen(p),
         }
     }
 }
 
 impl<'a> Gen for VariableDeclaration<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print_str(match self.kind {
-            VariableDeclarationKind::Const => b"const",
-            VariableDeclarationKind::Let => b"let",
-            VariableDeclarationKind::Var => b"var",
-        });
-        p.print_space();
-        p.print_list(&self.declarations);
+        print_variable_declaration(self, false, p);
     }
 }
 
+/// Multiple declarators are printed on separate lines in statements when any of them is initialized.
+fn print_variable_declaration(
+    decl: &VariableDeclaration<'_>,
+    is_statement: bool,
+    p: &mut Formatter,
+) {
+    print_declare(&decl.modifiers, p);
+    p.print_str(match decl.kind {
+        VariableDeclarationKind::Const => b"const",
+        VariableDeclarationKind::Let => b"let",
+        VariableDeclarationKind::Var => b"var",
+    });
+    p.print_space();
+    let has_init = is_statement && decl.declarations.iter().any(|d| d.init.is_some());
+    p.print_group(false, |p| {
+        for (index, declarator) in decl.declarations.iter().enumerate() {
+            if index == 1 {
+                p.indent();
+            }
+            if index != 0 {
+                p.print_comma();
+                if has_init {
+                    p.print_newline();
+                    p.print_indent();
+                } else {
+                    p.print_line();
+                }
+            }
+            declarator.gen(p);
+        }
+        if decl.declarations.len() > 1 {
+            p.dedent();
+        }
+    });
+}
+
 impl<'a> Gen for VariableDeclarator<'a> {
     fn gen(&self, p: &mut Formatter) {
-        self.id.gen(p);
+        print_binding_pattern(&self.id, self.definite, p);
         if let Some(init) = &self.init {
-            p.print_space();
-            p.print_equal();
-            p.print_space();
-            init.gen(p);
+            print_assignment_right(b" =", init, p);
+        }
+    }
+}
+
+/// Print the operator and the right hand side of an assignment, variable declarator or object property.
+///
+/// Like Prettier, the line is broken after the operator for binary expressions, strings and member chains
+/// when they do not fit; for everything else only when the start of `right` does not fit.
+pub fn print_assignment_right(operator: &[u8], right: &Expression<'_>, p: &mut Formatter) {
+    p.print_str(operator);
+    if should_break_after_operator(right) {
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_line();
+            print_expression_without_indent(right, p);
+            p.dedent_if_break();
+        });
+    } else {
+        let group = p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_line();
+            p.dedent_if_break();
+        });
+        p.indent_if_group_breaks(group);
+        right.gen(p);
+        p.dedent_if_break();
+    }
+}
+
+fn should_break_after_operator(right: &Expression<'_>) -> bool {
+    match right {
+        Expression::LogicalExpression(expr) => !is_object_or_array_with_content(&expr.right),
+        Expression::BinaryExpression(_)
+        | Expression::SequenceExpression(_)
+        | Expression::StringLiteral(_) => true,
+        Expression::ConditionalExpression(expr) => is_binaryish(&expr.test),
+        Expression::MemberExpression(_) => is_member_chain(right),
+        _ => false,
+    }
+}
+
+fn is_object_or_array_with_content(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::ObjectExpression(object) => !object.properties.is_empty(),
+        Expression::ArrayExpression(array) => !array.elements.is_empty(),
+        _ => false,
+    }
+}
+
+/// `a.b.c`, without calls or computed members
+fn is_member_chain(expr: &Expression<'_>) -> bool {
+    let mut expr = expr;
+    let mut depth = 0;
+    while let Expression::MemberExpression(member) = expr {
+        if matches!(&**member, MemberExpression::ComputedMemberExpression(_)) {
+            return false;
         }
+        expr = member.object();
+        depth += 1;
     }
+    depth > 1 && matches!(expr, Expression::Identifier(_) | Expression::ThisExpression(_))
 }
 
 impl<'a> Gen for Function<'a> {
     fn gen(&self, p: &mut Formatter) {
+        print_declare(&self.modifiers, p);
         if self.r#async {
             p.print_str(b"async");
             p.print_space();
@@ -491,36 +710,49 @@ impl<'a> Gen for Function<'a> {
         if self.generator {
             p.print(b'*');
         }
+        p.print_space();
         if let Some(id) = &self.id {
-            if !self.generator {
-                p.print_space();
-            }
             id.gen(p);
-            p.print_space();
         }
+        print_type_parameters(self.type_parameters.as_deref(), p);
         p.print(b'(');
         self.params.gen(p);
         p.print(b')');
-        p.print_space();
+        print_return_type(self.return_type.as_deref(), p);
+        // Overloads and `declare function` have no body
         if let Some(body) = &self.body {
+            p.print_space();
             body.gen(p);
+        } else {
+            p.print_statement_semicolon();
         }
     }
 }
 
 impl<'a> Gen for FunctionBody<'a> {
     fn gen(&self, p: &mut Formatter) {
+        if self.directives.is_empty()
+            && self.statements.is_empty()
+            && !p.has_comments_before(self.span.end)
+        {
+            p.print_str(b"{}");
+            return;
+        }
         p.print(b'{');
         p.indent();
         p.print_newline();
         for directive in &self.directives {
             directive.gen(p);
         }
-        p.needs_semicolon = false;
-        for stmt in &self.statements {
-            p.print_semicolon_if_needed();
-            stmt.gen(p);
+        if let Some(directive) = self.directives.last()
+            && !self.statements.is_empty()
+            && p.is_next_line_empty(directive.span.end)
+        {
+            p.print_newline();
         }
+        p.needs_semicolon = false;
+        p.print_statements(&self.statements);
+        p.print_leading_comments(self.span.end);
         p.dedent();
         p.print_indent();
         p.print(b'}');
@@ -530,98 +762,96 @@ impl<'a> Gen for FunctionBody<'a> {
 
 impl<'a> Gen for FormalParameter<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.print_inline_comments(self.span.start);
+        print_decorators(&self.decorators, p);
+        print_accessibility(self.accessibility, p);
+        if self.readonly {
+            p.print_str(b"readonly ");
+        }
         self.pattern.gen(p);
     }
 }
 
 impl<'a> Gen for FormalParameters<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print_list(&self.items);
-        if let Some(rest) = &self.rest {
-            if !self.items.is_empty() {
-                p.print_comma();
-            }
-            rest.gen(p);
+        if self.items.is_empty() && self.rest.is_none() {
+            return;
+        }
+        // A single destructured parameter is hugged by the parentheses: `function foo({ a, b }) {}`
+        if self.rest.is_none()
+            && self.items.len() == 1
+            && matches!(
+                self.items[0].pattern.kind,
+                BindingPatternKind::ObjectPattern(_) | BindingPatternKind::ArrayPattern(_)
+            )
+        {
+            self.items[0].gen(p);
+            return;
         }
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.items);
+            if let Some(rest) = &self.rest {
+                if !self.items.is_empty() {
+                    p.print_comma();
+                    p.print_line();
+                }
+                rest.gen(p);
+            } else {
+                p.print_trailing_comma(TrailingComma::All);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+        });
     }
 }
 
 impl<'a> Gen for ImportDeclaration<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
         p.print_str(b"import ");
+        if self.import_kind.is_type() {
+            p.print_str(b"type ");
+        }
         if self.specifiers.is_empty() {
-            p.print(b'\'');
-            p.print_str(self.source.value.as_bytes());
-            p.print(b'\'');
+            self.source.gen(p);
             self.assertions.gen(p);
             p.print_semicolon_after_statement();
             return;
         }
 
-        let mut in_block = false;
-        for (index, specifier) in self.specifiers.iter().enumerate() {
+        let mut named = vec![];
+        let mut printed_any = false;
+        for specifier in &self.specifiers {
             match specifier {
                 ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
-                    if in_block {
-                        p.print_space();
-                        p.print_str(b"},");
-                        p.print_space();
-                        in_block = false;
-                    } else if index != 0 {
-                        p.print_comma();
-                        p.print_space();
-                    }
                     spec.local.gen(p);
+                    printed_any = true;
                 }
                 ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
-                    if in_block {
-                        p.print_space();
-                        p.print_str(b"},");
-                        p.print_space();
-                        in_block = false;
-                    } else if index != 0 {
+                    if printed_any {
                         p.print_comma();
                         p.print_space();
                     }
                     p.print_str(b"* as ");
                     spec.local.gen(p);
+                    printed_any = true;
                 }
-                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
-                    if in_block {
-                        p.print_comma();
-                    } else {
-                        if index != 0 {
-                            p.print_comma();
-                            p.print_space();
-                        }
-                        in_block = true;
-                        p.print(b'{');
-                    }
-                    p.print_space();
-
-                    let imported_name = match &spec.imported {
-                        ModuleExportName::Identifier(identifier) => {
-                            identifier.gen(p);
-                            identifier.name.as_bytes()
-                        }
-                        ModuleExportName::StringLiteral(literal) => {
-                            literal.gen(p);
-                            literal.value.as_bytes()
-                        }
-                    };
-
-                    let local_name = spec.local.name.as_bytes();
-
-                    if imported_name != local_name {
-                        p.print_str(b" as ");
-                        spec.local.gen(p);
-                    }
-                }
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => named.push(spec),
             }
         }
-        if in_block {
-            p.print_space();
-            p.print(b'}');
+        if !named.is_empty() {
+            if printed_any {
+                p.print_comma();
+                p.print_space();
+            }
+            p.print_group(false, |p| {
+                p.print_open_brace();
+                p.print_soft_list(&named);
+                p.print_trailing_comma(TrailingComma::Es5);
+                p.print_close_brace();
+            });
         }
         p.print_str(b" from ");
         self.source.gen(p);
@@ -630,13 +860,35 @@ impl<'a> Gen for ImportDeclaration<'a> {
     }
 }
 
+impl<'a, T: Gen> Gen for &'a T {
+    fn gen(&self, p: &mut Formatter) {
+        (*self).gen(p);
+    }
+}
+
+impl Gen for ImportSpecifier {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_inline_comments(self.span.start);
+        self.imported.gen(p);
+        if self.imported.name() != &self.local.name {
+            p.print_str(b" as ");
+            self.local.gen(p);
+        }
+        p.print_inline_trailing_comments(self.span.end);
+    }
+}
+
 impl<'a> Gen for Option<Vec<'a, ImportAttribute>> {
     fn gen(&self, p: &mut Formatter) {
         if let Some(assertions) = &self {
             p.print_space();
             p.print_str(b"assert");
             p.print_space();
-            p.print_block(assertions, Separator::Comma);
+            p.print_group(false, |p| {
+                p.print_open_brace();
+                p.print_soft_list(assertions);
+                p.print_close_brace();
+            });
         };
     }
 }
@@ -657,17 +909,33 @@ impl Gen for ImportAttribute {
 
 impl<'a> Gen for ExportNamedDeclaration<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
+        if let Some(Declaration::ClassDeclaration(class)) = &self.declaration
+            && has_decorators_before(class, self.span.start)
+        {
+            print_decorators(&class.decorators, p);
+            p.print_str(b"export ");
+            print_class(class, false, p);
+            p.print_newline();
+            return;
+        }
         p.print_str(b"export ");
         match &self.declaration {
             Some(decl) => decl.gen(p),
             None => {
-                p.print(b'{');
-                if !self.specifiers.is_empty() {
-                    p.print_space();
-                    p.print_list(&self.specifiers);
-                    p.print_space();
+                if self.export_kind.is_type() {
+                    p.print_str(b"type ");
+                }
+                if self.specifiers.is_empty() {
+                    p.print_str(b"{}");
+                } else {
+                    p.print_group(false, |p| {
+                        p.print_open_brace();
+                        p.print_soft_list(&self.specifiers);
+                        p.print_trailing_comma(TrailingComma::Es5);
+                        p.print_close_brace();
+                    });
                 }
-                p.print(b'}');
                 if let Some(source) = &self.source {
                     p.print_space();
                     p.print_str(b"from");
@@ -682,11 +950,13 @@ impl<'a> Gen for ExportNamedDeclaration<'a> {
 
 impl Gen for ExportSpecifier {
     fn gen(&self, p: &mut Formatter) {
+        p.print_inline_comments(self.span.start);
         self.local.gen(p);
         if self.local.name() != self.exported.name() {
             p.print_str(b" as ");
             self.exported.gen(p);
         }
+        p.print_inline_trailing_comments(self.span.end);
     }
 }
 
@@ -703,8 +973,12 @@ impl Gen for ModuleExportName {
 
 impl<'a> Gen for ExportAllDeclaration<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
         p.print_str(b"export");
         p.print_space();
+        if self.export_kind.is_type() {
+            p.print_str(b"type ");
+        }
         p.print(b'*');
 
         if let Some(exported) = &self.exported {
@@ -724,6 +998,15 @@ impl<'a> Gen for ExportAllDeclaration<'a> {
 
 impl<'a> Gen for ExportDefaultDeclaration<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
+        if let ExportDefaultDeclarationKind::ClassDeclaration(class) = &self.declaration
+            && has_decorators_before(class, self.span.start)
+        {
+            print_decorators(&class.decorators, p);
+            p.print_str(b"export default ");
+            print_class(class, false, p);
+            return;
+        }
         p.print_str(b"export default ");
         self.declaration.gen(p);
     }
@@ -737,13 +1020,17 @@ impl<'a> Gen for ExportDefaultDeclarationKind<'a> {
             }
             Self::FunctionDeclaration(fun) => fun.gen(p),
             Self::ClassDeclaration(value) => value.gen(p),
-            Self::TSInterfaceDeclaration(_) | Self::TSEnumDeclaration(_) => {}
+            Self::TSInterfaceDeclaration(decl) => decl.gen(p),
+            Self::TSEnumDeclaration(decl) => decl.gen(p),
         }
     }
 }
 
 impl<'a> Gen for Expression<'a> {
     fn gen(&self, p: &mut Formatter) {
+        let start = self.span().start;
+        p.print_inline_comments(start);
+        p.add_source_mapping(start);
         match self {
             Self::BooleanLiteral(lit) => lit.gen(p),
             Self::NullLiteral(lit) => lit.gen(p),
@@ -778,31 +1065,34 @@ impl<'a> Gen for Expression<'a> {
             Self::NewExpression(expr) => expr.gen(p),
             Self::MetaProperty(expr) => expr.gen(p),
             Self::ClassExpression(expr) => expr.gen(p),
-            Self::JSXElement(el) => el.gen(p),
-            Self::JSXFragment(fragment) => fragment.gen(p),
-            Self::TSAsExpression(expr) => expr.expression.gen(p),
-            Self::TSSatisfiesExpression(expr) => expr.expression.gen(p),
-            Self::TSTypeAssertion(expr) => expr.expression.gen(p),
-            Self::TSNonNullExpression(expr) => expr.expression.gen(p),
-            Self::TSInstantiationExpression(expr) => expr.expression.gen(p),
+            Self::JSXElement(el) => print_jsx_in_parens(p, |p| el.gen(p)),
+            Self::JSXFragment(fragment) => print_jsx_in_parens(p, |p| fragment.gen(p)),
+            Self::TSAsExpression(expr) => expr.gen(p),
+            Self::TSSatisfiesExpression(expr) => expr.gen(p),
+            Self::TSTypeAssertion(expr) => expr.gen(p),
+            Self::TSNonNullExpression(expr) => expr.gen(p),
+            Self::TSInstantiationExpression(expr) => expr.gen(p),
         }
     }
 }
 
 impl Gen for IdentifierReference {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for IdentifierName {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for BindingIdentifier {
     fn gen(&self, p: &mut Formatter) {
+        p.add_source_mapping(self.span.start);
         p.print_str(self.name.as_bytes());
     }
 }
@@ -849,11 +1139,78 @@ impl Gen for RegExpLiteral {
 
 impl Gen for StringLiteral {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'\'');
-        for c in self.value.chars() {
-            p.print_str(c.escape_default().to_string().as_bytes());
+        // Prefer the raw text to keep escapes as written
+        let raw = p
+            .source_text_of(self.span)
+            .filter(|raw| raw.len() >= 2 && raw.starts_with(['"', '\'']))
+            .map(|raw| raw[1..raw.len() - 1].to_string());
+        let content = raw.as_deref().unwrap_or(&self.value);
+        let preferred = p.options().quote_style.as_byte();
+        let alternate = if preferred == b'"' { b'\'' } else { b'"' };
+        let count = |quote: u8| content.bytes().filter(|b| *b == quote).count();
+        // Use the other quote if it results in fewer escapes
+        let quote = if count(preferred) > count(alternate) { alternate } else { preferred };
+        p.print(quote);
+        if raw.is_some() {
+            print_raw_string_content(content, quote, p);
+        } else {
+            print_string_content(content, quote, p);
+        }
+        p.print(quote);
+    }
+}
+
+/// Print the raw content of a string literal for the given quote:
+/// quotes are escaped or unescaped as needed, other escapes are kept unless unnecessary.
+fn print_raw_string_content(raw: &str, quote: u8, p: &mut Formatter) {
+    let mut chars = raw.chars();
+    let mut buffer = [0; 4];
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(escaped) = chars.next() else {
+                    p.print(b'\\');
+                    break;
+                };
+                let is_quote = escaped == '"' || escaped == '\'';
+                // Escaped quotes which are not the enclosing quote do not need escaping
+                let needs_backslash = if is_quote {
+                    escaped as u32 == u32::from(quote)
+                } else {
+                    matches!(escaped, '\n' | '\r' | '0'..='7' | '\\' | 'b' | 'f' | 'n' | 'r' | 't'..='v' | 'x' | '\u{2028}' | '\u{2029}')
+                };
+                if needs_backslash {
+                    p.print(b'\\');
+                }
+                p.print_str(escaped.encode_utf8(&mut buffer).as_bytes());
+            }
+            c if c as u32 == u32::from(quote) => {
+                p.print(b'\\');
+                p.print(quote);
+            }
+            c => p.print_str(c.encode_utf8(&mut buffer).as_bytes()),
+        }
+    }
+}
+
+/// Print the cooked value of a string literal, escaping what cannot appear in the literal.
+fn print_string_content(value: &str, quote: u8, p: &mut Formatter) {
+    let mut buffer = [0; 4];
+    for c in value.chars() {
+        match c {
+            '\\' => p.print_str(b"\\\\"),
+            '\n' => p.print_str(b"\\n"),
+            '\r' => p.print_str(b"\\r"),
+            '\t' => p.print_str(b"\\t"),
+            '\u{2028}' => p.print_str(b"\\u2028"),
+            '\u{2029}' => p.print_str(b"\\u2029"),
+            '\0' => p.print_str(b"\\x00"),
+            c if c as u32 == u32::from(quote) => {
+                p.print(b'\\');
+                p.print(quote);
+            }
+            c => p.print_str(c.encode_utf8(&mut buffer).as_bytes()),
         }
-        p.print(b'\'');
     }
 }
 
@@ -909,14 +1266,181 @@ impl<'a> Gen for PrivateFieldExpression<'a> {
 
 impl<'a> Gen for CallExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
+        if print_member_chain(self, p) {
+            return;
+        }
         self.callee.gen(p);
-
         if self.optional {
             p.print_str(b"?.");
         }
-        p.print(b'(');
-        p.print_list(&self.arguments);
-        p.print(b')');
+        print_type_arguments(self.type_parameters.as_deref(), p);
+        print_arguments(&self.arguments, p);
+    }
+}
+
+/// Print call arguments, one per line if they do not fit.
+///
+/// A function or object as the last argument is hugged:
+/// only its first line needs to fit, the rest is printed after the other arguments.
+fn print_arguments(arguments: &[Argument<'_>], p: &mut Formatter) {
+    p.print(b'(');
+    if !arguments.is_empty() {
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_soft_line();
+            for (index, argument) in arguments.iter().enumerate() {
+                if index != 0 {
+                    p.print_comma();
+                    p.print_line();
+                }
+                if index == arguments.len() - 1 && is_huggable_argument(argument) {
+                    p.hug();
+                }
+                argument.gen(p);
+            }
+            p.print_trailing_comma(TrailingComma::All);
+            p.dedent_if_break();
+            p.print_soft_line();
+        });
+    }
+    p.print(b')');
+}
+
+fn is_huggable_argument(argument: &Argument<'_>) -> bool {
+    match argument {
+        Argument::Expression(
+            Expression::FunctionExpression(_)
+            | Expression::ObjectExpression(_)
+            | Expression::ArrayExpression(_)
+            | Expression::TemplateLiteral(_),
+        ) => true,
+        Argument::Expression(Expression::ArrowExpression(arrow)) => !arrow.expression,
+        _ => false,
+    }
+}
+
+/// A link of a member chain: `.foo(bar)`
+struct ChainLink<'a, 'b> {
+    member: &'b MemberExpression<'a>,
+    calls: std::vec::Vec<&'b CallExpression<'a>>,
+}
+
+/// Print `a.b().c().d()` with one call per line when it does not fit, returns false for other calls.
+///
+/// Like Prettier, the links are grouped into member accesses followed by calls.
+/// Chains with up to two groups including the head are printed normally,
+/// the first group stays on the line of the head when the head is short, e.g. `this` or `Object`.
+fn print_member_chain(call: &CallExpression<'_>, p: &mut Formatter) -> bool {
+    // Collect the links from the end of the chain
+    let mut links: std::vec::Vec<ChainLink> = vec![];
+    let mut calls = vec![call];
+    let mut expr = &call.callee;
+    let head = loop {
+        match expr {
+            Expression::CallExpression(call) => {
+                calls.push(call);
+                expr = &call.callee;
+            }
+            Expression::MemberExpression(member)
+                if !matches!(&**member, MemberExpression::ComputedMemberExpression(_)) =>
+            {
+                calls.reverse();
+                links.push(ChainLink { member, calls: std::mem::take(&mut calls) });
+                expr = member.object();
+            }
+            _ => break expr,
+        }
+    };
+    if !calls.is_empty() {
+        // The head is a call itself, e.g. `a()`, which is not part of the chain
+        return false;
+    }
+    links.reverse();
+
+    // Member accesses directly after the head belong to the head: `this.items.toArray()`
+    let absorbed = links.iter().take_while(|link| link.calls.is_empty()).count();
+    let mut groups: std::vec::Vec<&[ChainLink]> = vec![];
+    let mut group_start = absorbed;
+    for (index, link) in links.iter().enumerate().skip(absorbed) {
+        if !link.calls.is_empty() || index == links.len() - 1 {
+            groups.push(&links[group_start..=index]);
+            group_start = index + 1;
+        }
+    }
+
+    let is_factory = |name: &str| {
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            || name.chars().all(|c| c == '_' || c == '$')
+    };
+    let should_merge = groups.len() > 1
+        && if absorbed == 0 {
+            match head {
+                Expression::ThisExpression(_) => true,
+                Expression::Identifier(ident) => is_factory(&ident.name),
+                _ => false,
+            }
+        } else {
+            links[absorbed - 1].member.static_property_name().is_some_and(is_factory)
+        };
+    // The cutoff counts the head as a group too
+    let cutoff = if should_merge { 3 } else { 2 };
+    if groups.len() < cutoff {
+        return false;
+    }
+
+    let calls = links.iter().flat_map(|link| &link.calls).collect::<std::vec::Vec<_>>();
+    let has_function_arguments = calls.iter().any(|call| {
+        call.arguments.iter().any(|argument| {
+            matches!(
+                argument,
+                Argument::Expression(
+                    Expression::FunctionExpression(_) | Expression::ArrowExpression(_)
+                )
+            )
+        })
+    });
+    let merged = usize::from(should_merge);
+    p.print_group(calls.len() > 2 && has_function_arguments, |p| {
+        head.gen(p);
+        for link in &links[..absorbed] {
+            print_chain_link(link, p);
+        }
+        for link in groups[..merged].iter().copied().flatten() {
+            print_chain_link(link, p);
+        }
+        p.indent_if_break();
+        for (index, group) in groups.iter().enumerate().skip(merged) {
+            p.print_soft_line();
+            if index == groups.len() - 1 {
+                p.hug();
+            }
+            for link in *group {
+                print_chain_link(link, p);
+            }
+        }
+        p.dedent_if_break();
+    });
+    true
+}
+
+fn print_chain_link(link: &ChainLink, p: &mut Formatter) {
+    match link.member {
+        MemberExpression::StaticMemberExpression(member) => {
+            p.print_str(if member.optional { b"?." } else { b"." });
+            member.property.gen(p);
+        }
+        MemberExpression::PrivateFieldExpression(member) => {
+            p.print_str(if member.optional { b"?." } else { b"." });
+            member.field.gen(p);
+        }
+        MemberExpression::ComputedMemberExpression(_) => {}
+    }
+    for call in &link.calls {
+        if call.optional {
+            p.print_str(b"?.");
+        }
+        print_type_arguments(call.type_parameters.as_deref(), p);
+        print_arguments(&call.arguments, p);
     }
 }
 
@@ -924,7 +1448,7 @@ impl<'a> Gen for Argument<'a> {
     fn gen(&self, p: &mut Formatter) {
         match self {
             Self::SpreadElement(elem) => elem.gen(p),
-            Self::Expression(elem) => elem.gen(p),
+            Self::Expression(elem) => print_expression_without_jsx_parens(elem, p),
         }
     }
 }
@@ -932,7 +1456,7 @@ impl<'a> Gen for Argument<'a> {
 impl<'a> Gen for ArrayExpressionElement<'a> {
     fn gen(&self, p: &mut Formatter) {
         match self {
-            Self::Expression(expr) => expr.gen(p),
+            Self::Expression(expr) => print_expression_without_jsx_parens(expr, p),
             Self::SpreadElement(elem) => elem.gen(p),
             Self::Elision(_span) => {}
         }
@@ -948,31 +1472,74 @@ impl<'a> Gen for SpreadElement<'a> {
 
 impl<'a> Gen for ArrayExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'[');
-        p.print_list(&self.elements);
-        if self.trailing_comma.is_some() {
-            p.print_comma();
+        if self.elements.is_empty() {
+            p.print_str(b"[]");
+            return;
         }
-        p.print(b']');
+        // A matrix of objects or arrays is always expanded
+        let is_matrix = self.elements.len() > 1
+            && (self.elements.iter().all(|element| {
+                matches!(element, ArrayExpressionElement::Expression(Expression::ObjectExpression(object)) if object.properties.len() > 1)
+            }) || self.elements.iter().all(|element| {
+                matches!(element, ArrayExpressionElement::Expression(Expression::ArrayExpression(array)) if array.elements.len() > 1)
+            }));
+        p.print_group(is_matrix, |p| {
+            p.print(b'[');
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.elements);
+            if matches!(self.elements.last(), Some(ArrayExpressionElement::Elision(_))) {
+                // A trailing hole needs its comma
+                p.print_comma();
+            } else {
+                p.print_trailing_comma(TrailingComma::Es5);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+            p.print(b']');
+        });
     }
 }
 
 impl<'a> Gen for ObjectExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'{');
-        p.indent();
-        for (i, item) in self.properties.iter().enumerate() {
-            if i != 0 {
-                p.print_comma();
+        let Some(first) = self.properties.first() else {
+            let has_comments = p.has_comments_before(self.span.end);
+            p.print_group(has_comments, |p| {
+                p.print(b'{');
+                p.indent_if_break();
+                p.print_dangling_comments(self.span.end);
+                p.dedent_if_break();
+                p.print_soft_line();
+                p.print(b'}');
+            });
+            return;
+        };
+        // Like Prettier, objects with a line break after `{` in the original source stay expanded
+        let should_break = p.has_newline_in_range(self.span.start, first.span().start);
+        p.print_group(should_break, |p| {
+            p.print_open_brace();
+            for (index, property) in self.properties.iter().enumerate() {
+                let span = property.span();
+                let is_last = index + 1 == self.properties.len();
+                p.print_leading_comments_in_group(span.start);
+                property.gen(p);
+                if is_last {
+                    p.print_trailing_comma(TrailingComma::Es5);
+                } else {
+                    p.print_comma();
+                }
+                p.print_trailing_comments(span.end);
+                if !is_last {
+                    p.print_line();
+                    if p.is_next_line_empty(span.end) {
+                        p.print_soft_line();
+                    }
+                }
             }
-            p.print_newline();
-            p.print_indent();
-            item.gen(p);
-        }
-        p.print_newline();
-        p.dedent();
-        p.print_indent();
-        p.print(b'}');
+            p.print_dangling_comments(self.span.end);
+            p.print_close_brace();
+        });
     }
 }
 
@@ -1013,9 +1580,11 @@ impl<'a> Gen for ObjectProperty<'a> {
                 if self.computed {
                     p.print(b']');
                 }
+                print_type_parameters(func.type_parameters.as_deref(), p);
                 p.print(b'(');
                 func.params.gen(p);
                 p.print(b')');
+                print_return_type(func.return_type.as_deref(), p);
                 p.print_space();
                 if let Some(body) = &func.body {
                     body.gen(p);
@@ -1023,6 +1592,10 @@ impl<'a> Gen for ObjectProperty<'a> {
                 return;
             }
         }
+        if self.shorthand {
+            self.value.gen(p);
+            return;
+        }
         if self.computed {
             p.print(b'[');
         }
@@ -1030,9 +1603,7 @@ impl<'a> Gen for ObjectProperty<'a> {
         if self.computed {
             p.print(b']');
         }
-        p.print_colon();
-        p.print_space();
-        self.value.gen(p);
+        print_assignment_right(b":", &self.value, p);
     }
 }
 
@@ -1052,22 +1623,53 @@ impl<'a> Gen for ArrowExpression<'a> {
             p.print_str(b"async");
             p.print_space();
         }
+        print_type_parameters(self.type_parameters.as_deref(), p);
         p.print(b'(');
         self.params.gen(p);
         p.print(b')');
+        print_return_type(self.return_type.as_deref(), p);
         p.print_space();
         p.print_str(b"=>");
-        p.print_space();
         if self.expression {
-            if let Statement::ExpressionStatement(stmt) = &self.body.statements[0] {
-                stmt.expression.gen(p);
+            if let Some(Statement::ExpressionStatement(stmt)) = self.body.statements.first() {
+                print_arrow_body(&stmt.expression, p);
             }
         } else {
+            p.print_space();
             self.body.gen(p);
         }
     }
 }
 
+/// An expression body is moved to the next line when it does not fit,
+/// unless it can be broken by itself like an object or an array.
+fn print_arrow_body(body: &Expression<'_>, p: &mut Formatter) {
+    let inner = match body {
+        Expression::ParenthesizedExpression(expr) => &expr.expression,
+        _ => body,
+    };
+    if matches!(
+        inner,
+        Expression::ObjectExpression(_)
+            | Expression::ArrayExpression(_)
+            | Expression::TemplateLiteral(_)
+            | Expression::TaggedTemplateExpression(_)
+            | Expression::ArrowExpression(_)
+            | Expression::JSXElement(_)
+            | Expression::JSXFragment(_)
+    ) {
+        p.print_space();
+        body.gen(p);
+        return;
+    }
+    p.print_group(false, |p| {
+        p.indent_if_break();
+        p.print_line();
+        print_expression_without_indent(body, p);
+        p.dedent_if_break();
+    });
+}
+
 impl<'a> Gen for YieldExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_str(b"yield");
@@ -1087,7 +1689,6 @@ impl<'a> Gen for UpdateExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         let operator = self.operator.as_str().as_bytes();
         if self.prefix {
-            p.print_space();
             p.print_str(operator);
             self.argument.gen(p);
         } else {
@@ -1099,13 +1700,20 @@ impl<'a> Gen for UpdateExpression<'a> {
 
 impl<'a> Gen for UnaryExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        let operator = self.operator.as_str().as_bytes();
-        if self.operator.is_keyword() {
-            p.print_str(operator);
-            p.print_space();
-        } else {
+        let operator = self.operator.as_str();
+        p.print_str(operator.as_bytes());
+        // `- -a` and `+ ++a` must not be merged into a single token
+        let needs_space = self.operator.is_keyword()
+            || matches!(self.operator, UnaryOperator::UnaryNegation | UnaryOperator::UnaryPlus)
+                && match &self.argument {
+                    Expression::UnaryExpression(arg) => arg.operator.as_str().starts_with(operator),
+                    Expression::UpdateExpression(arg) => {
+                        arg.prefix && arg.operator.as_str().starts_with(operator)
+                    }
+                    _ => false,
+                };
+        if needs_space {
             p.print_space();
-            p.print_str(operator);
         }
         self.argument.gen(p);
     }
@@ -1113,9 +1721,9 @@ impl<'a> Gen for UnaryExpression<'a> {
 
 impl<'a> Gen for BinaryExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        self.left.gen(p);
-        self.operator.gen(p);
-        self.right.gen(p);
+        let operands =
+            (&self.left, self.operator.as_str(), binary_precedence(self.operator), &self.right);
+        print_binaryish(operands, true, p);
     }
 }
 
@@ -1142,25 +1750,181 @@ impl<'a> Gen for PrivateInExpression<'a> {
 
 impl<'a> Gen for LogicalExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        self.left.gen(p);
-        p.print_space();
-        p.print_str(self.operator.as_str().as_bytes());
-        p.print_space();
-        self.right.gen(p);
+        let operands =
+            (&self.left, self.operator.as_str(), logical_precedence(self.operator), &self.right);
+        print_binaryish(operands, true, p);
+    }
+}
+
+/// The left operand, operator, precedence and right operand of a binary or logical expression
+type BinaryishOperands<'a, 'b> = (&'b Expression<'a>, &'static str, Precedence, &'b Expression<'a>);
+
+fn is_binaryish(expr: &Expression<'_>) -> bool {
+    matches!(expr, Expression::BinaryExpression(_) | Expression::LogicalExpression(_))
+}
+
+fn binaryish_operands<'a, 'b>(expr: &'b Expression<'a>) -> Option<BinaryishOperands<'a, 'b>> {
+    match expr {
+        Expression::BinaryExpression(expr) => Some((
+            &expr.left,
+            expr.operator.as_str(),
+            binary_precedence(expr.operator),
+            &expr.right,
+        )),
+        Expression::LogicalExpression(expr) => Some((
+            &expr.left,
+            expr.operator.as_str(),
+            logical_precedence(expr.operator),
+            &expr.right,
+        )),
+        _ => None,
+    }
+}
+
+fn binary_precedence(operator: BinaryOperator) -> Precedence {
+    match operator {
+        BinaryOperator::BitwiseOR => Precedence::BitwiseOr,
+        BinaryOperator::BitwiseXOR => Precedence::BitwiseXor,
+        BinaryOperator::BitwiseAnd => Precedence::BitwiseAnd,
+        BinaryOperator::Equality
+        | BinaryOperator::Inequality
+        | BinaryOperator::StrictEquality
+        | BinaryOperator::StrictInequality => Precedence::Equality,
+        BinaryOperator::LessThan
+        | BinaryOperator::LessEqualThan
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterEqualThan
+        | BinaryOperator::Instanceof
+        | BinaryOperator::In => Precedence::Relational,
+        BinaryOperator::ShiftLeft
+        | BinaryOperator::ShiftRight
+        | BinaryOperator::ShiftRightZeroFill => Precedence::Shift,
+        BinaryOperator::Subtraction | BinaryOperator::Addition => Precedence::Add,
+        BinaryOperator::Multiplication | BinaryOperator::Remainder | BinaryOperator::Division => {
+            Precedence::Multiply
+        }
+        BinaryOperator::Exponential => Precedence::Exponential,
+    }
+}
+
+fn logical_precedence(operator: LogicalOperator) -> Precedence {
+    match operator {
+        LogicalOperator::Or => Precedence::LogicalOr,
+        LogicalOperator::And => Precedence::LogicalAnd,
+        LogicalOperator::Coalesce => Precedence::Coalesce,
+    }
+}
+
+/// Whether `a op1 b op2 c` is printed as a flat list of operands, the rules are from Prettier.
+fn should_flatten(
+    operator: &str,
+    precedence: Precedence,
+    left_operator: &str,
+    left_precedence: Precedence,
+) -> bool {
+    let is_multiplicative = |operator: &str| matches!(operator, "*" | "/" | "%");
+    let is_equality = |operator: &str| matches!(operator, "==" | "!=" | "===" | "!==");
+    let is_shift = |operator: &str| matches!(operator, "<<" | ">>" | ">>>");
+    precedence == left_precedence
+        && operator != "**"
+        && !(is_equality(operator) && is_equality(left_operator))
+        // `a * b % c` and `a % b * c`
+        && !((operator == "%" && is_multiplicative(left_operator)) || (left_operator == "%" && is_multiplicative(operator)))
+        // `a * b / c`
+        && !(operator != left_operator && is_multiplicative(operator) && is_multiplicative(left_operator))
+        && !(is_shift(operator) && is_shift(left_operator))
+}
+
+/// Print a binary or logical expression with one operand per line when it does not fit,
+/// operands after the first are indented when `indent` is set.
+fn print_binaryish(operands: BinaryishOperands, indent: bool, p: &mut Formatter) {
+    p.print_group(false, |p| {
+        print_binaryish_operands(operands, indent, p);
+        if indent {
+            p.dedent_if_break();
+        }
+    });
+}
+
+/// Print `a && b && c` as `a &&`, line, `b &&`, line, `c`,
+/// with `IndentIfBreak` after the first operand when `indent` is set.
+fn print_binaryish_operands(operands: BinaryishOperands, indent: bool, p: &mut Formatter) {
+    let (left, operator, precedence, right) = operands;
+    if let Some(left_operands) = binaryish_operands(left)
+        && should_flatten(operator, precedence, left_operands.1, left_operands.2)
+    {
+        let start = left.span().start;
+        p.print_inline_comments(start);
+        p.add_source_mapping(start);
+        print_binaryish_operands(left_operands, indent, p);
+    } else {
+        p.print_group(false, |p| print_operand(left, operator, precedence, p));
+        if indent {
+            p.indent_if_break();
+        }
+    }
+    p.print_space();
+    p.print_str(operator.as_bytes());
+    p.print_line();
+    print_operand(right, operator, precedence, p);
+}
+
+/// Operands are wrapped in parentheses when mixing operators is confusing, e.g. `(a && b) || c`.
+fn print_operand(
+    operand: &Expression<'_>,
+    operator: &str,
+    precedence: Precedence,
+    p: &mut Formatter,
+) {
+    let is_logical = |operator: &str| matches!(operator, "&&" | "||" | "??");
+    let is_bitwise = |operator: &str| matches!(operator, "&" | "|" | "^" | "<<" | ">>" | ">>>");
+    let needs_parens =
+        binaryish_operands(operand).is_some_and(|(_, operand_operator, operand_precedence, _)| {
+            (is_logical(operator) && is_logical(operand_operator) && operator != operand_operator)
+                || (operand_precedence == precedence
+                    && !should_flatten(operator, precedence, operand_operator, operand_precedence))
+                || (operand_operator == "%" && matches!(operator, "+" | "-"))
+                || is_bitwise(operator)
+        });
+    if needs_parens {
+        p.print(b'(');
+        operand.gen(p);
+        p.print(b')');
+    } else {
+        operand.gen(p);
+    }
+}
+
+/// Print an expression that is already indented by the caller, e.g. inside of parentheses.
+fn print_expression_without_indent(expr: &Expression<'_>, p: &mut Formatter) {
+    match binaryish_operands(expr) {
+        Some(operands) => {
+            let start = expr.span().start;
+            p.print_inline_comments(start);
+            p.add_source_mapping(start);
+            print_binaryish(operands, false, p);
+        }
+        None => expr.gen(p),
     }
 }
 
 impl<'a> Gen for ConditionalExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
-        self.test.gen(p);
-        p.print_space();
-        p.print(b'?');
-        p.print_space();
-        self.consequent.gen(p);
-        p.print_space();
-        p.print(b':');
-        p.print_space();
-        self.alternate.gen(p);
+        if is_jsx(&self.consequent) || is_jsx(&self.alternate) {
+            print_jsx_conditional(self, p);
+            return;
+        }
+        p.print_group(false, |p| {
+            self.test.gen(p);
+            p.indent_if_break();
+            p.print_line();
+            p.print_str(b"? ");
+            self.consequent.gen(p);
+            p.print_line();
+            p.print_str(b": ");
+            self.alternate.gen(p);
+            p.dedent_if_break();
+        });
     }
 }
 
@@ -1168,9 +1932,7 @@ impl<'a> Gen for AssignmentExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         self.left.gen(p);
         p.print_space();
-        p.print_str(self.operator.as_str().as_bytes());
-        p.print_space();
-        self.right.gen(p);
+        print_assignment_right(self.operator.as_str().as_bytes(), &self.right, p);
     }
 }
 
@@ -1188,10 +1950,10 @@ impl<'a> Gen for SimpleAssignmentTarget<'a> {
         match self {
             Self::AssignmentTargetIdentifier(ident) => ident.gen(p),
             Self::MemberAssignmentTarget(member_expr) => member_expr.gen(p),
-            Self::TSAsExpression(expr) => expr.expression.gen(p),
-            Self::TSSatisfiesExpression(expr) => expr.expression.gen(p),
-            Self::TSNonNullExpression(expr) => expr.expression.gen(p),
-            Self::TSTypeAssertion(expr) => expr.expression.gen(p),
+            Self::TSAsExpression(expr) => expr.gen(p),
+            Self::TSSatisfiesExpression(expr) => expr.gen(p),
+            Self::TSNonNullExpression(expr) => expr.gen(p),
+            Self::TSTypeAssertion(expr) => expr.gen(p),
         }
     }
 }
@@ -1207,17 +1969,32 @@ impl<'a> Gen for AssignmentTargetPattern<'a> {
 
 impl<'a> Gen for ArrayAssignmentTarget<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'[');
-        p.print_list(&self.elements);
-        if let Some(target) = &self.rest {
-            p.print_comma();
-            p.print_ellipsis();
-            target.gen(p);
-        }
-        if self.trailing_comma.is_some() {
-            p.print_comma();
+        if self.elements.is_empty() && self.rest.is_none() {
+            p.print_str(b"[]");
+            return;
         }
-        p.print(b']');
+        p.print_group(false, |p| {
+            p.print(b'[');
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.elements);
+            if let Some(target) = &self.rest {
+                if !self.elements.is_empty() {
+                    p.print_comma();
+                    p.print_line();
+                }
+                p.print_ellipsis();
+                target.gen(p);
+            } else if matches!(self.elements.last(), Some(None)) {
+                // A trailing hole needs its comma
+                p.print_comma();
+            } else {
+                p.print_trailing_comma(TrailingComma::Es5);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+            p.print(b']');
+        });
     }
 }
 
@@ -1231,16 +2008,25 @@ impl<'a> Gen for Option<AssignmentTargetMaybeDefault<'a>> {
 
 impl<'a> Gen for ObjectAssignmentTarget<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'{');
-        p.print_list(&self.properties);
-        if let Some(target) = &self.rest {
-            if !self.properties.is_empty() {
-                p.print_comma();
-            }
-            p.print_ellipsis();
-            target.gen(p);
+        if self.properties.is_empty() && self.rest.is_none() {
+            p.print_str(b"{}");
+            return;
         }
-        p.print(b'}');
+        p.print_group(false, |p| {
+            p.print_open_brace();
+            p.print_soft_list(&self.properties);
+            if let Some(target) = &self.rest {
+                if !self.properties.is_empty() {
+                    p.print_comma();
+                    p.print_line();
+                }
+                p.print_ellipsis();
+                target.gen(p);
+            } else {
+                p.print_trailing_comma(TrailingComma::Es5);
+            }
+            p.print_close_brace();
+        });
     }
 }
 
@@ -1256,7 +2042,7 @@ impl<'a> Gen for AssignmentTargetMaybeDefault<'a> {
 impl<'a> Gen for AssignmentTargetWithDefault<'a> {
     fn gen(&self, p: &mut Formatter) {
         self.binding.gen(p);
-        p.print_equal();
+        p.print_str(b" = ");
         self.init.gen(p);
     }
 }
@@ -1311,6 +2097,11 @@ impl<'a> Gen for SequenceExpression<'a> {
 
 impl<'a> Gen for ParenthesizedExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
+        // Multi-line JSX gets its own parentheses
+        if matches!(self.expression, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+            self.expression.gen(p);
+            return;
+        }
         p.print(b'(');
         self.expression.gen(p);
         p.print(b')');
@@ -1320,12 +2111,18 @@ impl<'a> Gen for ParenthesizedExpression<'a> {
 impl<'a> Gen for ImportExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_str(b"import(");
-        self.source.gen(p);
-        if !self.arguments.is_empty() {
-            p.print_comma();
-            p.print_space();
-            p.print_list(&self.arguments);
-        }
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_soft_line();
+            self.source.gen(p);
+            if !self.arguments.is_empty() {
+                p.print_comma();
+                p.print_line();
+                p.print_soft_list(&self.arguments);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+        });
         p.print(b')');
     }
 }
@@ -1352,6 +2149,7 @@ impl<'a> Gen for TemplateLiteral<'a> {
 impl<'a> Gen for TaggedTemplateExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         self.tag.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
         self.quasi.gen(p);
     }
 }
@@ -1382,9 +2180,8 @@ impl<'a> Gen for NewExpression<'a> {
     fn gen(&self, p: &mut Formatter) {
         p.print_str(b"new ");
         self.callee.gen(p);
-        p.print(b'(');
-        p.print_list(&self.arguments);
-        p.print(b')');
+        print_type_arguments(self.type_parameters.as_deref(), p);
+        print_arguments(&self.arguments, p);
     }
 }
 
@@ -1396,318 +2193,238 @@ impl Gen for MetaProperty {
     }
 }
 
-impl<'a> Gen for Class<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"class");
-        if let Some(id) = &self.id {
-            p.print_space();
-            id.gen(p);
-        }
-        if let Some(super_class) = self.super_class.as_ref() {
-            p.print_str(b" extends ");
-            super_class.gen(p);
-        }
-        p.print_space();
-        p.print(b'{');
-        p.print_newline();
-        p.indent();
-        for item in &self.body.body {
-            p.print_semicolon_if_needed();
-            p.print_indent();
-            item.gen(p);
-            if matches!(
-                item,
-                ClassElement::PropertyDefinition(_) | ClassElement::AccessorProperty(_)
-            ) {
-                p.print_semicolon_after_statement();
-            } else {
-                p.print_newline();
-            }
-        }
-        p.needs_semicolon = false;
-        p.dedent();
-        p.print_indent();
-        p.print(b'}');
-    }
+/// Decorators written before `export`, e.g. `@dec export class A {}`
+fn has_decorators_before(class: &Class, export_start: u32) -> bool {
+    class.decorators.first().is_some_and(|decorator| decorator.span.start < export_start)
 }
 
-impl<'a> Gen for ClassElement<'a> {
+impl<'a> Gen for Class<'a> {
     fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::StaticBlock(elem) => elem.gen(p),
-            Self::MethodDefinition(elem) => elem.gen(p),
-            Self::PropertyDefinition(elem) => elem.gen(p),
-            Self::AccessorProperty(elem) => elem.gen(p),
-            Self::TSAbstractMethodDefinition(_)
-            | Self::TSAbstractPropertyDefinition(_)
-            | Self::TSIndexSignature(_) => {}
-        }
+        print_class(self, true, p);
     }
 }
 
-impl Gen for JSXIdentifier {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(self.name.as_bytes());
+fn print_class(class: &Class, with_decorators: bool, p: &mut Formatter) {
+    p.add_source_mapping(class.span.start);
+    if with_decorators {
+        print_decorators(&class.decorators, p);
     }
-}
-
-impl<'a> Gen for JSXMemberExpressionObject<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Identifier(ident) => ident.gen(p),
-            Self::MemberExpression(member_expr) => member_expr.gen(p),
-        }
+    print_declare(&class.modifiers, p);
+    if class.modifiers.contains(ModifierKind::Abstract) {
+        p.print_str(b"abstract ");
     }
-}
-
-impl<'a> Gen for JSXMemberExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        self.object.gen(p);
-        p.print(b'.');
-        self.property.gen(p);
+    p.print_str(b"class");
+    if let Some(id) = &class.id {
+        p.print_space();
+        id.gen(p);
     }
-}
-
-impl<'a> Gen for JSXElementName<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Identifier(identifier) => identifier.gen(p),
-            Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
-            Self::MemberExpression(member_expr) => member_expr.gen(p),
-        }
+    print_type_parameters(class.type_parameters.as_deref(), p);
+    if let Some(super_class) = class.super_class.as_ref() {
+        p.print_str(b" extends ");
+        super_class.gen(p);
+        print_type_arguments(class.super_type_parameters.as_deref(), p);
     }
-}
-
-impl Gen for JSXNamespacedName {
-    fn gen(&self, p: &mut Formatter) {
-        self.namespace.gen(p);
-        p.print(b'.');
-        self.property.gen(p);
+    if let Some(implements) = &class.implements && !implements.is_empty() {
+        p.print_str(b" implements ");
+        p.print_list(implements);
     }
-}
-
-impl<'a> Gen for JSXAttributeName<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Identifier(ident) => ident.gen(p),
-            Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
+    p.print_space();
+    if class.body.body.is_empty() && !p.has_comments_before(class.body.span.end) {
+        p.print_str(b"{}");
+        return;
+    }
+    p.print(b'{');
+    p.print_newline();
+    p.indent();
+    let elements = &class.body.body;
+    for (index, item) in elements.iter().enumerate() {
+        p.print_semicolon_if_needed();
+        let span = item.span();
+        p.print_leading_comments(span.start);
+        p.print_indent();
+        item.gen(p);
+        if needs_semicolon(item) {
+            // Without semicolons, `;` is still needed when the next member would continue the property
+            if p.options.semi || elements.get(index + 1).is_some_and(starts_with_bracket_or_star) {
+                p.print_semicolon();
+            }
+        }
+        p.print_newline();
+        p.print_trailing_comments(span.end);
+        if index + 1 != elements.len() && p.is_next_line_empty(span.end) {
+            p.print_newline();
         }
     }
+    p.print_leading_comments(class.body.span.end);
+    p.needs_semicolon = false;
+    p.dedent();
+    p.print_indent();
+    p.print(b'}');
 }
 
-impl<'a> Gen for JSXAttribute<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        self.name.gen(p);
-        p.print(b'=');
-        if let Some(value) = &self.value {
-            value.gen(p);
+/// Properties, index signatures and methods without a body, e.g. overloads
+fn needs_semicolon(element: &ClassElement<'_>) -> bool {
+    match element {
+        ClassElement::PropertyDefinition(_)
+        | ClassElement::AccessorProperty(_)
+        | ClassElement::TSAbstractPropertyDefinition(_)
+        | ClassElement::TSIndexSignature(_) => true,
+        ClassElement::MethodDefinition(method) => method.value.body.is_none(),
+        ClassElement::TSAbstractMethodDefinition(method) => {
+            method.method_definition.value.body.is_none()
         }
+        ClassElement::StaticBlock(_) => false,
     }
 }
 
-impl Gen for JSXEmptyExpression {
-    fn gen(&self, _: &mut Formatter) {}
+fn starts_with_bracket_or_star(element: &ClassElement<'_>) -> bool {
+    match element {
+        ClassElement::MethodDefinition(method) => {
+            method.computed || (method.value.generator && !method.r#static)
+        }
+        ClassElement::PropertyDefinition(prop) => prop.computed && !prop.r#static,
+        _ => false,
+    }
 }
 
-impl<'a> Gen for JSXExpression<'a> {
+impl<'a> Gen for ClassElement<'a> {
     fn gen(&self, p: &mut Formatter) {
         match self {
-            Self::Expression(expr) => expr.gen(p),
-            Self::EmptyExpression(expr) => expr.gen(p),
+            Self::StaticBlock(elem) => elem.gen(p),
+            Self::MethodDefinition(elem) => elem.gen(p),
+            Self::PropertyDefinition(elem) => elem.gen(p),
+            Self::AccessorProperty(elem) => elem.gen(p),
+            Self::TSAbstractMethodDefinition(elem) => {
+                print_method_definition(&elem.method_definition, true, p);
+            }
+            Self::TSAbstractPropertyDefinition(elem) => {
+                print_property_definition(&elem.property_definition, true, p);
+            }
+            Self::TSIndexSignature(elem) => elem.gen(p),
         }
     }
 }
 
-impl<'a> Gen for JSXExpressionContainer<'a> {
+impl<'a> Gen for StaticBlock<'a> {
     fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"static");
+        p.print_space();
+        if self.body.is_empty() && !p.has_comments_before(self.span.end) {
+            p.print_str(b"{}");
+            return;
+        }
         p.print(b'{');
-        self.expression.gen(p);
+        p.print_newline();
+        p.indent();
+        p.print_statements(&self.body);
+        p.print_leading_comments(self.span.end);
+        p.dedent();
+        p.needs_semicolon = false;
+        p.print_indent();
         p.print(b'}');
     }
 }
 
-impl<'a> Gen for JSXAttributeValue<'a> {
+impl<'a> Gen for MethodDefinition<'a> {
     fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Fragment(fragment) => fragment.gen(p),
-            Self::Element(el) => el.gen(p),
-            Self::StringLiteral(lit) => lit.gen(p),
-            Self::ExpressionContainer(expr_container) => expr_container.gen(p),
-        }
+        print_method_definition(self, false, p);
     }
 }
 
-impl<'a> Gen for JSXSpreadAttribute<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        self.argument.gen(p);
+fn print_method_definition(method: &MethodDefinition<'_>, is_abstract: bool, p: &mut Formatter) {
+    print_decorators(&method.decorators, p);
+    print_accessibility(method.accessibility, p);
+    if method.r#static {
+        p.print_str(b"static ");
     }
-}
-
-impl<'a> Gen for JSXAttributeItem<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Attribute(attr) => attr.gen(p),
-            Self::SpreadAttribute(spread_attr) => spread_attr.gen(p),
-        }
+    if is_abstract {
+        p.print_str(b"abstract ");
     }
-}
-
-impl<'a> Gen for JSXOpeningElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"<");
-        self.name.gen(p);
-        for attr in &self.attributes {
-            attr.gen(p);
-        }
-        if self.self_closing {
-            p.print_space();
-            p.print_str(b"/>");
-        } else {
-            p.print(b'>');
-        }
+    if method.r#override {
+        p.print_str(b"override ");
     }
-}
 
-impl<'a> Gen for JSXClosingElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"</");
-        self.name.gen(p);
-        p.print(b'>');
+    match &method.kind {
+        MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
+        MethodDefinitionKind::Get => p.print_str(b"get "),
+        MethodDefinitionKind::Set => p.print_str(b"set "),
     }
-}
 
-impl<'a> Gen for JSXElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        self.opening_element.gen(p);
-        for child in &self.children {
-            child.gen(p);
-        }
-        if let Some(closing_element) = &self.closing_element {
-            closing_element.gen(p);
-        }
+    if method.value.r#async {
+        p.print_str(b"async ");
     }
-}
 
-impl Gen for JSXOpeningFragment {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"<>");
+    if method.value.generator {
+        p.print_str(b"*");
     }
-}
 
-impl Gen for JSXClosingFragment {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"</>");
+    if method.computed {
+        p.print(b'[');
     }
-}
-
-impl Gen for JSXText {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(self.value.as_bytes());
+    method.key.gen(p);
+    if method.computed {
+        p.print(b']');
     }
-}
-
-impl<'a> Gen for JSXSpreadChild<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"...");
-        self.expression.gen(p);
+    if method.optional {
+        p.print(b'?');
     }
-}
-
-impl<'a> Gen for JSXChild<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match self {
-            Self::Fragment(fragment) => fragment.gen(p),
-            Self::Element(el) => el.gen(p),
-            Self::Spread(spread) => spread.expression.gen(p),
-            Self::ExpressionContainer(expr_container) => expr_container.gen(p),
-            Self::Text(text) => text.gen(p),
-        }
+    print_type_parameters(method.value.type_parameters.as_deref(), p);
+    p.print(b'(');
+    method.value.params.gen(p);
+    p.print(b')');
+    print_return_type(method.value.return_type.as_deref(), p);
+    if let Some(body) = &method.value.body {
+        p.print_space();
+        body.gen(p);
     }
 }
 
-impl<'a> Gen for JSXFragment<'a> {
+impl<'a> Gen for PropertyDefinition<'a> {
     fn gen(&self, p: &mut Formatter) {
-        self.opening_fragment.gen(p);
-        for child in &self.children {
-            child.gen(p);
-        }
-        self.closing_fragment.gen(p);
+        print_property_definition(self, false, p);
     }
 }
 
-impl<'a> Gen for StaticBlock<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(b"static");
-        p.print_space();
-        p.print(b'{');
-        p.print_newline();
-        p.indent();
-        for stmt in &self.body {
-            p.print_semicolon_if_needed();
-            stmt.gen(p);
-        }
-        p.dedent();
-        p.needs_semicolon = false;
-        p.print_indent();
-        p.print(b'}');
+fn print_property_definition(
+    property: &PropertyDefinition<'_>,
+    is_abstract: bool,
+    p: &mut Formatter,
+) {
+    print_decorators(&property.decorators, p);
+    if property.declare {
+        p.print_str(b"declare ");
     }
-}
-
-impl<'a> Gen for MethodDefinition<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        if self.r#static {
-            p.print_str(b"static ");
-        }
-
-        match &self.kind {
-            MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
-            MethodDefinitionKind::Get => p.print_str(b"get "),
-            MethodDefinitionKind::Set => p.print_str(b"set "),
-        }
-
-        if self.value.r#async {
-            p.print_str(b"async ");
-        }
-
-        if self.value.generator {
-            p.print_str(b"*");
-        }
-
-        if self.computed {
-            p.print(b'[');
-        }
-        self.key.gen(p);
-        if self.computed {
-            p.print(b']');
-        }
-        p.print(b'(');
-        self.value.params.gen(p);
-        p.print(b')');
-        p.print_space();
-        if let Some(body) = &self.value.body {
-            body.gen(p);
-        }
+    print_accessibility(property.accessibility, p);
+    if property.r#static {
+        p.print_str(b"static ");
     }
-}
-
-impl<'a> Gen for PropertyDefinition<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        if self.r#static {
-            p.print_str(b"static ");
-        }
-        if self.computed {
-            p.print(b'[');
-        }
-        self.key.gen(p);
-        if self.computed {
-            p.print(b']');
-        }
-        if let Some(value) = &self.value {
-            p.print_space();
-            p.print_equal();
-            p.print_space();
-            value.gen(p);
-        }
+    if is_abstract {
+        p.print_str(b"abstract ");
+    }
+    if property.r#override {
+        p.print_str(b"override ");
+    }
+    if property.readonly {
+        p.print_str(b"readonly ");
+    }
+    if property.computed {
+        p.print(b'[');
+    }
+    property.key.gen(p);
+    if property.computed {
+        p.print(b']');
+    }
+    if property.optional {
+        p.print(b'?');
+    }
+    if property.definite {
+        p.print(b'!');
+    }
+    if let Some(annotation) = &property.type_annotation {
+        print_type_annotation(annotation, p);
+    }
+    if let Some(value) = &property.value {
+        print_assignment_right(b" =", value, p);
     }
 }
 
@@ -1725,10 +2442,7 @@ impl<'a> Gen for AccessorProperty<'a> {
             p.print(b']');
         }
         if let Some(value) = &self.value {
-            p.print_space();
-            p.print_equal();
-            p.print_space();
-            value.gen(p);
+            print_assignment_right(b" =", value, p);
         }
     }
 }
@@ -1742,33 +2456,39 @@ impl Gen for PrivateIdentifier {
 
 impl<'a> Gen for BindingPattern<'a> {
     fn gen(&self, p: &mut Formatter) {
-        match &self.kind {
-            BindingPatternKind::BindingIdentifier(ident) => ident.gen(p),
-            BindingPatternKind::ObjectPattern(pattern) => pattern.gen(p),
-            BindingPatternKind::ArrayPattern(pattern) => pattern.gen(p),
-            BindingPatternKind::AssignmentPattern(pattern) => pattern.gen(p),
-        }
+        print_binding_pattern(self, false, p);
     }
 }
 
 impl<'a> Gen for ObjectPattern<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'{');
-        p.print_space();
-        p.print_list(&self.properties);
-        if let Some(rest) = &self.rest {
-            if !self.properties.is_empty() {
-                p.print_comma();
-            }
-            rest.gen(p);
+        if self.properties.is_empty() && self.rest.is_none() {
+            p.print_str(b"{}");
+            return;
         }
-        p.print_space();
-        p.print(b'}');
+        p.print_group(false, |p| {
+            p.print_open_brace();
+            p.print_soft_list(&self.properties);
+            if let Some(rest) = &self.rest {
+                if !self.properties.is_empty() {
+                    p.print_comma();
+                    p.print_line();
+                }
+                rest.gen(p);
+            } else {
+                p.print_trailing_comma(TrailingComma::Es5);
+            }
+            p.print_close_brace();
+        });
     }
 }
 
 impl<'a> Gen for BindingProperty<'a> {
     fn gen(&self, p: &mut Formatter) {
+        if self.shorthand {
+            self.value.gen(p);
+            return;
+        }
         if self.computed {
             p.print(b'[');
         }
@@ -1791,22 +2511,39 @@ impl<'a> Gen for RestElement<'a> {
 
 impl<'a> Gen for ArrayPattern<'a> {
     fn gen(&self, p: &mut Formatter) {
-        p.print(b'[');
-        for (index, item) in self.elements.iter().enumerate() {
-            if index != 0 {
-                p.print_comma();
-            }
-            if let Some(item) = item {
-                item.gen(p);
+        if self.elements.is_empty() && self.rest.is_none() {
+            p.print_str(b"[]");
+            return;
+        }
+        p.print_group(false, |p| {
+            p.print(b'[');
+            p.indent_if_break();
+            p.print_soft_line();
+            for (index, item) in self.elements.iter().enumerate() {
+                if index != 0 {
+                    p.print_comma();
+                    p.print_line();
+                }
+                if let Some(item) = item {
+                    item.gen(p);
+                }
             }
-            if index == self.elements.len() - 1 && (item.is_none() || self.rest.is_some()) {
+            if let Some(rest) = &self.rest {
+                if !self.elements.is_empty() {
+                    p.print_comma();
+                    p.print_line();
+                }
+                rest.gen(p);
+            } else if matches!(self.elements.last(), Some(None)) {
+                // A trailing hole needs its comma
                 p.print_comma();
+            } else {
+                p.print_trailing_comma(TrailingComma::Es5);
             }
-        }
-        if let Some(rest) = &self.rest {
-            rest.gen(p);
-        }
-        p.print(b']');
+            p.dedent_if_break();
+            p.print_soft_line();
+            p.print(b']');
+        });
     }
 }
 
@@ -0,0 +1,521 @@
+//! JSX
+//!
+//! An element is printed on one line when it fits, otherwise its attributes and children go on their own lines.
+//! Like Prettier, an element with child elements, several attributes or several expression children
+//! is always broken, and a multi-line element is wrapped in parentheses unless it is a statement,
+//! an argument, an array element or inside of another element.
+//!
+//! Whitespace in JSX text is only significant when it does not contain a line break,
+//! significant whitespace at the start or the end of a line is written as `{" "}`.
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+
+use crate::{gen_ts::print_type_arguments, Formatter, Gen};
+
+/// A JSX element or fragment in parentheses when it is printed over multiple lines
+pub fn print_jsx_in_parens<F: FnOnce(&mut Formatter)>(p: &mut Formatter, f: F) {
+    // `x = (` or `cond && (` stays on the current line
+    p.hug();
+    p.print_group(false, |p| {
+        p.print_if_break("(");
+        p.indent_if_break();
+        p.print_soft_line();
+        f(p);
+        p.dedent_if_break();
+        p.print_soft_line();
+        p.print_if_break(")");
+    });
+}
+
+/// Print `expr` without adding parentheses around a multi-line JSX element,
+/// used where the element starts on its own line anyway.
+pub fn print_expression_without_jsx_parens(expr: &Expression<'_>, p: &mut Formatter) {
+    match expr {
+        Expression::JSXElement(el) => {
+            p.print_inline_comments(el.span.start);
+            p.add_source_mapping(el.span.start);
+            el.gen(p);
+        }
+        Expression::JSXFragment(fragment) => {
+            p.print_inline_comments(fragment.span.start);
+            p.add_source_mapping(fragment.span.start);
+            fragment.gen(p);
+        }
+        Expression::ParenthesizedExpression(paren) if is_jsx(&paren.expression) => {
+            print_expression_without_jsx_parens(&paren.expression, p);
+        }
+        _ => expr.gen(p),
+    }
+}
+
+pub fn is_jsx(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
+        Expression::ParenthesizedExpression(paren) => is_jsx(&paren.expression),
+        _ => false,
+    }
+}
+
+/// A conditional with a JSX branch, every branch except `null` is in parentheses when it is broken:
+///
+/// ```jsx
+/// cond ? (
+///   <div />
+/// ) : (
+///   <span />
+/// )
+/// ```
+pub fn print_jsx_conditional(expr: &ConditionalExpression<'_>, p: &mut Formatter) {
+    p.print_group(false, |p| {
+        expr.test.gen(p);
+        p.print_str(b" ? ");
+        print_jsx_conditional_branch(&expr.consequent, p);
+        p.print_str(b" : ");
+        print_jsx_conditional_branch(&expr.alternate, p);
+    });
+}
+
+fn print_jsx_conditional_branch(expr: &Expression<'_>, p: &mut Formatter) {
+    if matches!(expr, Expression::NullLiteral(_)) || expr.is_undefined() {
+        expr.gen(p);
+        return;
+    }
+    p.print_if_break("(");
+    p.indent();
+    p.print_soft_line();
+    print_expression_without_jsx_parens(expr, p);
+    p.dedent();
+    p.print_soft_line();
+    p.print_if_break(")");
+}
+
+impl Gen for JSXIdentifier {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(self.name.as_bytes());
+    }
+}
+
+impl<'a> Gen for JSXMemberExpressionObject<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Identifier(ident) => ident.gen(p),
+            Self::MemberExpression(member_expr) => member_expr.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for JSXMemberExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.object.gen(p);
+        p.print(b'.');
+        self.property.gen(p);
+    }
+}
+
+impl<'a> Gen for JSXElementName<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Identifier(identifier) => identifier.gen(p),
+            Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
+            Self::MemberExpression(member_expr) => member_expr.gen(p),
+        }
+    }
+}
+
+impl Gen for JSXNamespacedName {
+    fn gen(&self, p: &mut Formatter) {
+        self.namespace.gen(p);
+        p.print_colon();
+        self.property.gen(p);
+    }
+}
+
+impl<'a> Gen for JSXAttributeName<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Identifier(ident) => ident.gen(p),
+            Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for JSXAttribute<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.name.gen(p);
+        if let Some(value) = &self.value {
+            p.print(b'=');
+            value.gen(p);
+        }
+    }
+}
+
+impl Gen for JSXEmptyExpression {
+    fn gen(&self, _: &mut Formatter) {}
+}
+
+impl<'a> Gen for JSXExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Expression(expr) => print_expression_without_jsx_parens(expr, p),
+            Self::EmptyExpression(expr) => expr.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for JSXExpressionContainer<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'{');
+        self.expression.gen(p);
+        // `{/* comment */}`
+        p.print_comments_before_close(self.span.end);
+        p.print(b'}');
+    }
+}
+
+impl<'a> Gen for JSXAttributeValue<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Fragment(fragment) => fragment.gen(p),
+            Self::Element(el) => el.gen(p),
+            Self::StringLiteral(lit) => print_jsx_string(lit, p),
+            Self::ExpressionContainer(expr_container) => expr_container.gen(p),
+        }
+    }
+}
+
+/// JSX strings have no escapes, double quotes are used unless the value contains one
+fn print_jsx_string(lit: &StringLiteral, p: &mut Formatter) {
+    let quote = if lit.value.contains('"') { b'\'' } else { b'"' };
+    p.print(quote);
+    p.print_str(lit.value.as_bytes());
+    p.print(quote);
+}
+
+impl<'a> Gen for JSXSpreadAttribute<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'{');
+        p.print_ellipsis();
+        self.argument.gen(p);
+        p.print(b'}');
+    }
+}
+
+impl<'a> Gen for JSXAttributeItem<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Attribute(attr) => attr.gen(p),
+            Self::SpreadAttribute(spread_attr) => spread_attr.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for JSXOpeningElement<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'<');
+        self.name.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
+        let single_line_string = |attr: &JSXAttributeItem| {
+            matches!(attr, JSXAttributeItem::Attribute(attr)
+                if matches!(&attr.value, Some(JSXAttributeValue::StringLiteral(lit)) if !lit.value.contains('\n')))
+        };
+        // `<div className="a very long class name">` is not broken
+        if self.attributes.is_empty()
+            || (self.attributes.len() == 1 && single_line_string(&self.attributes[0]))
+        {
+            for attr in &self.attributes {
+                p.print_space();
+                attr.gen(p);
+            }
+            p.print_str(if self.self_closing { b" />" } else { b">" });
+            return;
+        }
+        let has_multi_line_string = self.attributes.iter().any(|attr| {
+            matches!(attr, JSXAttributeItem::Attribute(attr)
+                if matches!(&attr.value, Some(JSXAttributeValue::StringLiteral(lit)) if lit.value.contains('\n')))
+        });
+        p.print_group(has_multi_line_string, |p| {
+            p.indent();
+            for attr in &self.attributes {
+                p.print_line();
+                attr.gen(p);
+            }
+            p.dedent();
+            if self.self_closing {
+                p.print_line();
+                p.print_str(b"/>");
+            } else {
+                p.print_soft_line();
+                p.print(b'>');
+            }
+        });
+    }
+}
+
+impl<'a> Gen for JSXClosingElement<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"</");
+        self.name.gen(p);
+        p.print(b'>');
+    }
+}
+
+impl<'a> Gen for JSXElement<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        let Some(closing_element) = &self.closing_element else {
+            self.opening_element.gen(p);
+            return;
+        };
+        let should_break = self.opening_element.attributes.len() > 1;
+        print_element(
+            p,
+            |p| self.opening_element.gen(p),
+            &self.children,
+            should_break,
+            |p| closing_element.gen(p),
+        );
+    }
+}
+
+impl Gen for JSXOpeningFragment {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"<>");
+    }
+}
+
+impl Gen for JSXClosingFragment {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"</>");
+    }
+}
+
+impl<'a> Gen for JSXFragment<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_element(
+            p,
+            |p| self.opening_fragment.gen(p),
+            &self.children,
+            false,
+            |p| self.closing_fragment.gen(p),
+        );
+    }
+}
+
+impl Gen for JSXText {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(self.value.as_bytes());
+    }
+}
+
+impl<'a> Gen for JSXSpreadChild<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'{');
+        p.print_ellipsis();
+        self.expression.gen(p);
+        p.print(b'}');
+    }
+}
+
+impl<'a> Gen for JSXChild<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::Fragment(fragment) => fragment.gen(p),
+            Self::Element(el) => el.gen(p),
+            Self::Spread(spread) => spread.gen(p),
+            Self::ExpressionContainer(expr_container) => expr_container.gen(p),
+            Self::Text(text) => text.gen(p),
+        }
+    }
+}
+
+/// Whitespace between two children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Separator {
+    None,
+    /// Whitespace containing a line break, which is removed by JSX
+    Newline,
+    /// Whitespace containing an empty line between two children, kept as one empty line
+    BlankLine,
+    /// Whitespace on a single line, or between two words, printed as a space
+    Space,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Part<'b, 'a> {
+    Word(&'b str),
+    Child(&'b JSXChild<'a>),
+}
+
+fn is_jsx_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+fn whitespace_separator(whitespace: &str) -> Separator {
+    match whitespace.matches('\n').count() {
+        _ if whitespace.is_empty() => Separator::None,
+        0 => Separator::Space,
+        1 => Separator::Newline,
+        _ => Separator::BlankLine,
+    }
+}
+
+/// Split the children into words and non-text children, each with the whitespace before it.
+/// Returns the whitespace after the last part.
+fn split_children<'b, 'a>(
+    children: &'b [JSXChild<'a>],
+    parts: &mut std::vec::Vec<(Separator, Part<'b, 'a>)>,
+) -> Separator {
+    let mut pending = Separator::None;
+    for child in children {
+        match child {
+            JSXChild::Text(text) => {
+                let value = text.value.as_str();
+                let trimmed = value.trim_matches(is_jsx_whitespace);
+                // After `{" "}`, whitespace containing a line break is removed
+                if trimmed.is_empty() {
+                    if pending != Separator::Space {
+                        pending = whitespace_separator(value);
+                    }
+                    continue;
+                }
+                let leading =
+                    &value[..value.len() - value.trim_start_matches(is_jsx_whitespace).len()];
+                // Text is never separated from the previous child by an empty line
+                pending = match whitespace_separator(leading) {
+                    _ if pending == Separator::Space => Separator::Space,
+                    Separator::BlankLine => Separator::Newline,
+                    separator => separator,
+                };
+                for (index, word) in
+                    trimmed.split(is_jsx_whitespace).filter(|w| !w.is_empty()).enumerate()
+                {
+                    let separator = if index == 0 { pending } else { Separator::Space };
+                    parts.push((separator, Part::Word(word)));
+                }
+                let trailing = &value[value.trim_end_matches(is_jsx_whitespace).len()..];
+                pending = match whitespace_separator(trailing) {
+                    Separator::BlankLine => Separator::Newline,
+                    separator => separator,
+                };
+            }
+            // `{" "}` is written back where it is needed
+            JSXChild::ExpressionContainer(container)
+                if matches!(&container.expression,
+                    JSXExpression::Expression(Expression::StringLiteral(lit)) if lit.value == " ") =>
+            {
+                pending = Separator::Space;
+            }
+            _ => {
+                parts.push((pending, Part::Child(child)));
+                pending = Separator::None;
+            }
+        }
+    }
+    pending
+}
+
+/// Print the children of a JSX element or fragment.
+///
+/// When the element is broken, children next to each other or on different lines in the source
+/// are printed on separate lines, while text and the children directly around it fill the lines like a paragraph:
+/// every separator within text is a group of its own together with the part after it,
+/// so it only breaks when that part does not fit on the current line.
+fn print_element<O, C>(
+    p: &mut Formatter,
+    opening: O,
+    children: &[JSXChild<'_>],
+    multiple_attributes: bool,
+    closing: C,
+) where
+    O: FnOnce(&mut Formatter),
+    C: FnOnce(&mut Formatter),
+{
+    let mut parts = vec![];
+    let trailing = split_children(children, &mut parts);
+    if parts.is_empty() {
+        opening(p);
+        if trailing == Separator::Space {
+            p.print_space();
+        }
+        closing(p);
+        return;
+    }
+    let has_tag = parts
+        .iter()
+        .any(|(_, part)| matches!(part, Part::Child(JSXChild::Element(_) | JSXChild::Fragment(_))));
+    let expressions = parts
+        .iter()
+        .filter(|(_, part)| matches!(part, Part::Child(JSXChild::ExpressionContainer(_))))
+        .count();
+    let has_newline = parts
+        .iter()
+        .skip(1)
+        .any(|(separator, _)| matches!(separator, Separator::Newline | Separator::BlankLine));
+    let should_break = has_tag || multiple_attributes || expressions > 1 || has_newline;
+    p.print_group(should_break, |p| {
+        opening(p);
+        p.indent();
+        let mut previous: Option<Part> = None;
+        for (separator, part) in parts {
+            let position = p.doc_position();
+            match (separator, previous, part) {
+                (Separator::Space, None, _) => {
+                    p.print_if_break("{\" \"}");
+                    p.print_line();
+                    print_part(part, p);
+                }
+                (_, None, _)
+                | (Separator::None | Separator::Newline, Some(Part::Child(_)), Part::Child(_))
+                | (Separator::Newline, ..) => {
+                    p.print_soft_line();
+                    print_part(part, p);
+                }
+                (Separator::BlankLine, ..) => {
+                    p.print_soft_line();
+                    p.print_soft_line();
+                    print_part(part, p);
+                }
+                (Separator::Space, Some(Part::Word(_)), Part::Word(_)) => {
+                    p.print_group(false, |p| {
+                        p.print_line();
+                        print_part(part, p);
+                    });
+                }
+                (Separator::Space, ..) => {
+                    p.print_group(false, |p| {
+                        p.print_if_break("{\" \"}");
+                        p.print_line();
+                        print_part(part, p);
+                    });
+                }
+                (Separator::None, ..) => {
+                    p.print_group(false, |p| {
+                        p.print_soft_line();
+                        print_part(part, p);
+                    });
+                }
+            }
+            // A multi-line child, e.g. `{items.map((item) => (` breaks the element
+            if p.has_hard_break_since(position) {
+                p.mark_hard_break();
+            }
+            previous = Some(part);
+        }
+        if trailing == Separator::Space {
+            p.print_if_break("{\" \"}");
+            p.dedent();
+            p.print_line();
+        } else {
+            p.dedent();
+            p.print_soft_line();
+        }
+        closing(p);
+    });
+}
+
+fn print_part(part: Part, p: &mut Formatter) {
+    match part {
+        Part::Word(word) => p.print_str(word.as_bytes()),
+        Part::Child(child) => child.gen(p),
+    }
+}
@@ -0,0 +1,998 @@
+//! TypeScript syntax
+//!
+//! Types, TypeScript declarations and the TypeScript parts of JavaScript nodes,
+//! e.g. type annotations, type parameters and class member modifiers.
+//! Parenthesized types are not kept in the AST, so parentheses are added where they are required.
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use crate::{gen::print_assignment_right, Formatter, Gen, TrailingComma};
+
+/// `: T`
+pub fn print_type_annotation(annotation: &TSTypeAnnotation<'_>, p: &mut Formatter) {
+    p.print_colon();
+    print_type_after_operator(&annotation.type_annotation, p);
+}
+
+/// A union after `=` or `:` is broken with a leading `|` on every line
+fn print_type_after_operator(ty: &TSType<'_>, p: &mut Formatter) {
+    if let TSType::TSUnionType(union) = ty {
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_line();
+            print_union_types(&union.types, p);
+            p.dedent_if_break();
+        });
+    } else {
+        p.print_space();
+        p.print_inline_comments(ty.span().start);
+        ty.gen(p);
+    }
+}
+
+fn print_union_types(types: &[TSType<'_>], p: &mut Formatter) {
+    p.print_if_break("| ");
+    for (index, ty) in types.iter().enumerate() {
+        if index != 0 {
+            p.print_line();
+            p.print_str(b"| ");
+        }
+        print_type_with_parens(ty, needs_parens_in_union(ty), p);
+    }
+}
+
+pub fn print_type_parameters(params: Option<&TSTypeParameterDeclaration<'_>>, p: &mut Formatter) {
+    if let Some(params) = params {
+        params.gen(p);
+    }
+}
+
+pub fn print_type_arguments(args: Option<&TSTypeParameterInstantiation<'_>>, p: &mut Formatter) {
+    if let Some(args) = args {
+        args.gen(p);
+    }
+}
+
+pub fn print_return_type(return_type: Option<&TSTypeAnnotation<'_>>, p: &mut Formatter) {
+    if let Some(return_type) = return_type {
+        print_type_annotation(return_type, p);
+    }
+}
+
+/// Decorators stay on the line of the decorated node, unless they were on their own line
+pub fn print_decorators(decorators: &[Decorator<'_>], p: &mut Formatter) {
+    for decorator in decorators {
+        decorator.gen(p);
+        if p.is_followed_by_newline(decorator.span.end) {
+            p.print_newline();
+            p.print_indent();
+        } else {
+            p.print_space();
+        }
+    }
+}
+
+pub fn print_accessibility(accessibility: Option<TSAccessibility>, p: &mut Formatter) {
+    if let Some(accessibility) = accessibility {
+        p.print_str(match accessibility {
+            TSAccessibility::Private => b"private ",
+            TSAccessibility::Protected => b"protected ",
+            TSAccessibility::Public => b"public ",
+        });
+    }
+}
+
+pub fn print_declare(modifiers: &Modifiers<'_>, p: &mut Formatter) {
+    if modifiers.contains(ModifierKind::Declare) {
+        p.print_str(b"declare ");
+    }
+}
+
+/// `?` or `!` after a binding, followed by its type annotation
+pub fn print_binding_pattern(pattern: &BindingPattern<'_>, definite: bool, p: &mut Formatter) {
+    p.print_inline_comments(pattern.span().start);
+    match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => ident.gen(p),
+        BindingPatternKind::ObjectPattern(pattern) => pattern.gen(p),
+        BindingPatternKind::ArrayPattern(pattern) => pattern.gen(p),
+        BindingPatternKind::AssignmentPattern(pattern) => pattern.gen(p),
+    }
+    if pattern.optional {
+        p.print(b'?');
+    }
+    if definite {
+        p.print(b'!');
+    }
+    if let Some(annotation) = &pattern.type_annotation {
+        print_type_annotation(annotation, p);
+    }
+    let end = pattern
+        .type_annotation
+        .as_ref()
+        .map_or(pattern.span().end, |annotation| annotation.span.end);
+    p.print_inline_trailing_comments(end);
+}
+
+impl<'a> Gen for Decorator<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'@');
+        self.expression.gen(p);
+    }
+}
+
+impl<'a> Gen for TSTypeParameterDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'<');
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.params);
+            // `<T,>() => {}` is not a JSX element in `.tsx` files
+            if self.params.len() == 1
+                && self.params[0].constraint.is_none()
+                && p.source_text_of(self.span)
+                    .is_some_and(|text| text.trim_end_matches('>').trim_end().ends_with(','))
+            {
+                p.print_comma();
+            } else {
+                p.print_trailing_comma(TrailingComma::All);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+        });
+        p.print(b'>');
+    }
+}
+
+impl<'a> Gen for TSTypeParameter<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.r#const {
+            p.print_str(b"const ");
+        }
+        if self.r#in {
+            p.print_str(b"in ");
+        }
+        if self.out {
+            p.print_str(b"out ");
+        }
+        self.name.gen(p);
+        if let Some(constraint) = &self.constraint {
+            p.print_str(b" extends ");
+            constraint.gen(p);
+        }
+        if let Some(default) = &self.default {
+            p.print_str(b" = ");
+            default.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for TSTypeParameterInstantiation<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'<');
+        p.print_group(false, |p| {
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.params);
+            p.print_trailing_comma(TrailingComma::All);
+            p.dedent_if_break();
+            p.print_soft_line();
+        });
+        p.print(b'>');
+    }
+}
+
+impl<'a> Gen for TSTypeAnnotation<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.type_annotation.gen(p);
+    }
+}
+
+impl<'a> Gen for TSType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::TSAnyKeyword(_) => p.print_str(b"any"),
+            Self::TSBigIntKeyword(_) => p.print_str(b"bigint"),
+            Self::TSBooleanKeyword(_) => p.print_str(b"boolean"),
+            Self::TSNeverKeyword(_) => p.print_str(b"never"),
+            Self::TSNullKeyword(_) => p.print_str(b"null"),
+            Self::TSNumberKeyword(_) => p.print_str(b"number"),
+            Self::TSObjectKeyword(_) => p.print_str(b"object"),
+            Self::TSStringKeyword(_) => p.print_str(b"string"),
+            Self::TSSymbolKeyword(_) => p.print_str(b"symbol"),
+            Self::TSThisKeyword(_) => p.print_str(b"this"),
+            Self::TSUndefinedKeyword(_) => p.print_str(b"undefined"),
+            Self::TSUnknownKeyword(_) => p.print_str(b"unknown"),
+            Self::TSVoidKeyword(_) => p.print_str(b"void"),
+            Self::TSArrayType(ty) => ty.gen(p),
+            Self::TSConditionalType(ty) => ty.gen(p),
+            Self::TSConstructorType(ty) => ty.gen(p),
+            Self::TSFunctionType(ty) => ty.gen(p),
+            Self::TSImportType(ty) => ty.gen(p),
+            Self::TSIndexedAccessType(ty) => ty.gen(p),
+            Self::TSInferType(ty) => ty.gen(p),
+            Self::TSIntersectionType(ty) => ty.gen(p),
+            Self::TSLiteralType(ty) => ty.gen(p),
+            Self::TSMappedType(ty) => ty.gen(p),
+            Self::TSQualifiedName(ty) => ty.gen(p),
+            Self::TSTemplateLiteralType(ty) => ty.gen(p),
+            Self::TSTupleType(ty) => ty.gen(p),
+            Self::TSTypeLiteral(ty) => ty.gen(p),
+            Self::TSTypeOperatorType(ty) => ty.gen(p),
+            Self::TSTypePredicate(ty) => ty.gen(p),
+            Self::TSTypeQuery(ty) => ty.gen(p),
+            Self::TSTypeReference(ty) => ty.gen(p),
+            Self::TSUnionType(ty) => ty.gen(p),
+            Self::JSDocNullableType(ty) => {
+                if ty.postfix {
+                    print_type_with_parens(
+                        &ty.type_annotation,
+                        needs_parens_in_postfix(&ty.type_annotation),
+                        p,
+                    );
+                    p.print(b'?');
+                } else {
+                    p.print(b'?');
+                    ty.type_annotation.gen(p);
+                }
+            }
+            Self::JSDocUnknownType(_) => p.print(b'?'),
+        }
+    }
+}
+
+fn print_type_with_parens(ty: &TSType<'_>, parens: bool, p: &mut Formatter) {
+    if parens {
+        p.print(b'(');
+    }
+    ty.gen(p);
+    if parens {
+        p.print(b')');
+    }
+}
+
+/// Types ending with another type, e.g. `() => T` or `A extends B ? C : D`
+fn is_function_like(ty: &TSType<'_>) -> bool {
+    match ty {
+        TSType::TSFunctionType(_) | TSType::TSConstructorType(_) | TSType::TSConditionalType(_) => {
+            true
+        }
+        TSType::TSInferType(ty) => ty.type_parameter.constraint.is_some(),
+        _ => false,
+    }
+}
+
+/// `(A | B)[]`, `(keyof T)[]`
+fn needs_parens_in_postfix(ty: &TSType<'_>) -> bool {
+    is_function_like(ty)
+        || matches!(
+            ty,
+            TSType::TSUnionType(_)
+                | TSType::TSIntersectionType(_)
+                | TSType::TSTypeOperatorType(_)
+                | TSType::TSInferType(_)
+        )
+}
+
+/// `keyof (A | B)`
+fn needs_parens_in_type_operator(ty: &TSType<'_>) -> bool {
+    is_function_like(ty) || matches!(ty, TSType::TSUnionType(_) | TSType::TSIntersectionType(_))
+}
+
+/// `(() => void) | A`, nested unions keep their parentheses
+fn needs_parens_in_union(ty: &TSType<'_>) -> bool {
+    is_function_like(ty) || matches!(ty, TSType::TSUnionType(_))
+}
+
+/// `(A | B) & C`
+fn needs_parens_in_intersection(ty: &TSType<'_>) -> bool {
+    is_function_like(ty) || matches!(ty, TSType::TSUnionType(_) | TSType::TSIntersectionType(_))
+}
+
+impl<'a> Gen for TSArrayType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_type_with_parens(&self.element_type, needs_parens_in_postfix(&self.element_type), p);
+        p.print_str(b"[]");
+    }
+}
+
+impl<'a> Gen for TSIndexedAccessType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_type_with_parens(&self.object_type, needs_parens_in_postfix(&self.object_type), p);
+        p.print(b'[');
+        self.index_type.gen(p);
+        p.print(b']');
+    }
+}
+
+impl<'a> Gen for TSConditionalType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_group(false, |p| {
+            print_conditional_type(self, p);
+        });
+    }
+}
+
+/// Like Prettier, a chain of conditional types in the false branch is printed at the same indentation:
+///
+/// ```text
+/// A extends B
+///   ? C
+///   : D extends E
+///   ? F
+///   : G
+/// ```
+fn print_conditional_type(ty: &TSConditionalType<'_>, p: &mut Formatter) {
+    print_type_with_parens(&ty.check_type, is_function_like(&ty.check_type), p);
+    p.print_str(b" extends ");
+    let extends_parens = matches!(
+        ty.extends_type,
+        TSType::TSFunctionType(_) | TSType::TSConstructorType(_) | TSType::TSConditionalType(_)
+    );
+    print_type_with_parens(&ty.extends_type, extends_parens, p);
+    p.indent_if_break();
+    p.print_line();
+    p.print_str(b"? ");
+    ty.true_type.gen(p);
+    p.print_line();
+    p.print_str(b": ");
+    p.dedent_if_break();
+    if let TSType::TSConditionalType(false_type) = &ty.false_type {
+        print_conditional_type(false_type, p);
+    } else {
+        p.indent_if_break();
+        ty.false_type.gen(p);
+        p.dedent_if_break();
+    }
+}
+
+fn print_signature(
+    type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+    params: &FormalParameters<'_>,
+    p: &mut Formatter,
+) {
+    print_type_parameters(type_parameters, p);
+    p.print(b'(');
+    params.gen(p);
+    p.print(b')');
+}
+
+impl<'a> Gen for TSFunctionType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_signature(self.type_parameters.as_deref(), &self.params, p);
+        p.print_str(b" => ");
+        self.return_type.gen(p);
+    }
+}
+
+impl<'a> Gen for TSConstructorType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.r#abstract {
+            p.print_str(b"abstract ");
+        }
+        p.print_str(b"new ");
+        print_signature(self.type_parameters.as_deref(), &self.params, p);
+        p.print_str(b" => ");
+        self.return_type.gen(p);
+    }
+}
+
+impl<'a> Gen for TSImportType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.is_type_of {
+            p.print_str(b"typeof ");
+        }
+        p.print_str(b"import(");
+        self.parameter.gen(p);
+        p.print(b')');
+        if let Some(qualifier) = &self.qualifier {
+            p.print(b'.');
+            qualifier.gen(p);
+        }
+        print_type_arguments(self.type_parameters.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for TSInferType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"infer ");
+        self.type_parameter.gen(p);
+    }
+}
+
+impl<'a> Gen for TSUnionType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_group(false, |p| {
+            print_union_types(&self.types, p);
+        });
+    }
+}
+
+impl<'a> Gen for TSIntersectionType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_group(false, |p| {
+            for (index, ty) in self.types.iter().enumerate() {
+                if index == 1 {
+                    p.indent_if_break();
+                }
+                if index != 0 {
+                    p.print_str(b" &");
+                    p.print_line();
+                }
+                print_type_with_parens(ty, needs_parens_in_intersection(ty), p);
+            }
+            if self.types.len() > 1 {
+                p.dedent_if_break();
+            }
+        });
+    }
+}
+
+impl<'a> Gen for TSLiteralType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match &self.literal {
+            TSLiteral::BooleanLiteral(lit) => lit.gen(p),
+            TSLiteral::NullLiteral(lit) => lit.gen(p),
+            TSLiteral::NumberLiteral(lit) => lit.gen(p),
+            TSLiteral::BigintLiteral(lit) => lit.gen(p),
+            TSLiteral::RegExpLiteral(lit) => lit.gen(p),
+            TSLiteral::StringLiteral(lit) => lit.gen(p),
+            TSLiteral::TemplateLiteral(lit) => lit.gen(p),
+            TSLiteral::UnaryExpression(expr) => expr.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for TSMappedType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        // Like objects, mapped types with a line break after `{` stay expanded
+        let should_break = p.has_newline_in_range(self.span.start, self.type_parameter.span.start);
+        p.print_group(should_break, |p| {
+            p.print_open_brace();
+            p.print_str(match self.readonly {
+                TSMappedTypeModifierOperator::True => b"readonly ",
+                TSMappedTypeModifierOperator::Plus => b"+readonly ",
+                TSMappedTypeModifierOperator::Minus => b"-readonly ",
+                TSMappedTypeModifierOperator::None => b"",
+            });
+            p.print(b'[');
+            self.type_parameter.name.gen(p);
+            if let Some(constraint) = &self.type_parameter.constraint {
+                p.print_str(b" in ");
+                constraint.gen(p);
+            }
+            if let Some(name_type) = &self.name_type {
+                p.print_str(b" as ");
+                name_type.gen(p);
+            }
+            p.print(b']');
+            p.print_str(match self.optional {
+                TSMappedTypeModifierOperator::True => b"?",
+                TSMappedTypeModifierOperator::Plus => b"+?",
+                TSMappedTypeModifierOperator::Minus => b"-?",
+                TSMappedTypeModifierOperator::None => b"",
+            });
+            p.print_colon();
+            p.print_space();
+            self.type_annotation.gen(p);
+            if p.options.semi {
+                p.print_if_break(";");
+            }
+            p.print_close_brace();
+        });
+    }
+}
+
+impl<'a> Gen for TSQualifiedName<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.left.gen(p);
+        p.print(b'.');
+        self.right.gen(p);
+    }
+}
+
+impl<'a> Gen for TSTypeName<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::IdentifierName(ident) => ident.gen(p),
+            Self::QualifiedName(name) => name.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for TSTemplateLiteralType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'`');
+        let mut types = self.types.iter();
+        for quasi in &self.quasis {
+            p.print_str(quasi.value.raw.as_bytes());
+            if let Some(ty) = types.next() {
+                p.print_str(b"${");
+                ty.gen(p);
+                p.print(b'}');
+            }
+        }
+        p.print(b'`');
+    }
+}
+
+impl<'a> Gen for TSTupleType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.element_types.is_empty() {
+            p.print_str(b"[]");
+            return;
+        }
+        p.print_group(false, |p| {
+            p.print(b'[');
+            p.indent_if_break();
+            p.print_soft_line();
+            p.print_soft_list(&self.element_types);
+            if !matches!(self.element_types.last(), Some(TSTupleElement::TSRestType(_))) {
+                p.print_trailing_comma(TrailingComma::Es5);
+            }
+            p.dedent_if_break();
+            p.print_soft_line();
+            p.print(b']');
+        });
+    }
+}
+
+impl<'a> Gen for TSTupleElement<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::TSType(ty) => ty.gen(p),
+            Self::TSOptionalType(ty) => {
+                let parens = needs_parens_in_postfix(&ty.type_annotation);
+                print_type_with_parens(&ty.type_annotation, parens, p);
+                p.print(b'?');
+            }
+            Self::TSRestType(ty) => {
+                p.print_ellipsis();
+                ty.type_annotation.gen(p);
+            }
+            Self::TSNamedTupleMember(member) => {
+                // The AST does not record rest members, `[...rest: T[]]`
+                if p.source_text_of(member.span).is_some_and(|text| text.starts_with("...")) {
+                    p.print_ellipsis();
+                }
+                member.label.gen(p);
+                if member.optional {
+                    p.print(b'?');
+                }
+                p.print_colon();
+                p.print_space();
+                member.element_type.gen(p);
+            }
+        }
+    }
+}
+
+impl<'a> Gen for TSTypeLiteral<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        let Some(first) = self.members.first() else {
+            p.print_str(b"{}");
+            return;
+        };
+        // Like objects, type literals with a line break after `{` stay expanded
+        let should_break = p.has_newline_in_range(self.span.start, first.span().start);
+        p.print_group(should_break, |p| {
+            p.print_open_brace();
+            for (index, member) in self.members.iter().enumerate() {
+                let span = member.span();
+                let is_last = index + 1 == self.members.len();
+                p.print_leading_comments_in_group(span.start);
+                member.gen(p);
+                if p.options.semi {
+                    if is_last {
+                        p.print_if_break(";");
+                    } else {
+                        p.print_semicolon();
+                    }
+                }
+                p.print_trailing_comments(span.end);
+                if !is_last {
+                    // Without semicolons, members are only separated by `;` when printed on one line
+                    if p.options.semi {
+                        p.print_line();
+                    } else {
+                        p.print_line_with("; ");
+                    }
+                    if p.is_next_line_empty(span.end) {
+                        p.print_soft_line();
+                    }
+                }
+            }
+            p.print_dangling_comments(self.span.end);
+            p.print_close_brace();
+        });
+    }
+}
+
+impl<'a> Gen for TSTypeOperatorType<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(match self.operator {
+            TSTypeOperator::Keyof => b"keyof ",
+            TSTypeOperator::Unique => b"unique ",
+            TSTypeOperator::Readonly => b"readonly ",
+        });
+        let parens = needs_parens_in_type_operator(&self.type_annotation);
+        print_type_with_parens(&self.type_annotation, parens, p);
+    }
+}
+
+impl<'a> Gen for TSTypePredicate<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.asserts {
+            p.print_str(b"asserts ");
+        }
+        match &self.parameter_name {
+            TSTypePredicateName::Identifier(ident) => ident.gen(p),
+            TSTypePredicateName::This(_) => p.print_str(b"this"),
+        }
+        if let Some(annotation) = &self.type_annotation {
+            p.print_str(b" is ");
+            annotation.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for TSTypeQuery<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"typeof ");
+        self.expr_name.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for TSTypeReference<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.type_name.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for TSSignature<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self {
+            Self::TSIndexSignature(signature) => signature.gen(p),
+            Self::TSPropertySignature(signature) => signature.gen(p),
+            Self::TSCallSignatureDeclaration(signature) => {
+                print_signature(signature.type_parameters.as_deref(), &signature.params, p);
+                print_return_type(signature.return_type.as_deref(), p);
+            }
+            Self::TSConstructSignatureDeclaration(signature) => {
+                p.print_str(b"new ");
+                print_signature(signature.type_parameters.as_deref(), &signature.params, p);
+                print_return_type(signature.return_type.as_deref(), p);
+            }
+            Self::TSMethodSignature(signature) => signature.gen(p),
+        }
+    }
+}
+
+impl<'a> Gen for TSIndexSignature<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        // The AST does not record `readonly [key: string]: T`
+        if p.source_text_of(self.span).is_some_and(|text| text.starts_with("readonly")) {
+            p.print_str(b"readonly ");
+        }
+        p.print(b'[');
+        p.print_list(&self.parameters);
+        p.print(b']');
+        print_type_annotation(&self.type_annotation, p);
+    }
+}
+
+impl<'a> Gen for TSIndexSignatureName<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(self.name.as_bytes());
+        print_type_annotation(&self.type_annotation, p);
+    }
+}
+
+fn print_property_key(key: &PropertyKey<'_>, computed: bool, p: &mut Formatter) {
+    if computed {
+        p.print(b'[');
+    }
+    key.gen(p);
+    if computed {
+        p.print(b']');
+    }
+}
+
+impl<'a> Gen for TSPropertySignature<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        if self.readonly {
+            p.print_str(b"readonly ");
+        }
+        print_property_key(&self.key, self.computed, p);
+        if self.optional {
+            p.print(b'?');
+        }
+        if let Some(annotation) = &self.type_annotation {
+            print_type_annotation(annotation, p);
+        }
+    }
+}
+
+impl<'a> Gen for TSMethodSignature<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match self.kind {
+            TSMethodSignatureKind::Method => {}
+            TSMethodSignatureKind::Get => p.print_str(b"get "),
+            TSMethodSignatureKind::Set => p.print_str(b"set "),
+        }
+        print_property_key(&self.key, self.computed, p);
+        if self.optional {
+            p.print(b'?');
+        }
+        print_signature(self.type_parameters.as_deref(), &self.params, p);
+        print_return_type(self.return_type.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for TSTypeAliasDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_declare(&self.modifiers, p);
+        p.print_str(b"type ");
+        self.id.gen(p);
+        print_type_parameters(self.type_parameters.as_deref(), p);
+        p.print_str(b" =");
+        print_type_after_operator(&self.type_annotation, p);
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl<'a> Gen for TSInterfaceDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_declare(&self.modifiers, p);
+        p.print_str(b"interface ");
+        self.id.gen(p);
+        print_type_parameters(self.type_parameters.as_deref(), p);
+        if let Some(extends) = &self.extends {
+            if !extends.is_empty() {
+                p.print_str(b" extends ");
+                p.print_list(extends);
+            }
+        }
+        p.print_space();
+        print_signatures_block(&self.body.body, self.body.span, p);
+        p.print_newline();
+    }
+}
+
+impl<'a> Gen for TSInterfaceHeritage<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
+    }
+}
+
+/// The body of an interface, one member per line
+fn print_signatures_block(members: &Vec<'_, TSSignature<'_>>, span: Span, p: &mut Formatter) {
+    if members.is_empty() && !p.has_comments_before(span.end) {
+        p.print_str(b"{}");
+        return;
+    }
+    p.print(b'{');
+    p.print_newline();
+    p.indent();
+    for (index, member) in members.iter().enumerate() {
+        let span = member.span();
+        p.print_leading_comments(span.start);
+        p.print_indent();
+        member.gen(p);
+        p.print_statement_semicolon();
+        p.print_newline();
+        p.print_trailing_comments(span.end);
+        if index + 1 != members.len() && p.is_next_line_empty(span.end) {
+            p.print_newline();
+        }
+    }
+    p.print_leading_comments(span.end);
+    p.dedent();
+    p.print_indent();
+    p.print(b'}');
+}
+
+impl<'a> Gen for TSEnumDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_declare(&self.modifiers, p);
+        if self.modifiers.contains(ModifierKind::Const) {
+            p.print_str(b"const ");
+        }
+        p.print_str(b"enum ");
+        self.id.gen(p);
+        p.print_space();
+        if self.members.is_empty() && !p.has_comments_before(self.span.end) {
+            p.print_str(b"{}");
+            p.print_newline();
+            return;
+        }
+        p.print(b'{');
+        p.print_newline();
+        p.indent();
+        for (index, member) in self.members.iter().enumerate() {
+            p.print_leading_comments(member.span.start);
+            p.print_indent();
+            member.gen(p);
+            if index + 1 != self.members.len() || p.options.trailing_comma >= TrailingComma::Es5 {
+                p.print_comma();
+            }
+            p.print_newline();
+            p.print_trailing_comments(member.span.end);
+            if index + 1 != self.members.len() && p.is_next_line_empty(member.span.end) {
+                p.print_newline();
+            }
+        }
+        p.print_leading_comments(self.span.end);
+        p.dedent();
+        p.print_indent();
+        p.print(b'}');
+        p.print_newline();
+    }
+}
+
+impl<'a> Gen for TSEnumMember<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        match &self.id {
+            TSEnumMemberName::Identifier(ident) => ident.gen(p),
+            TSEnumMemberName::StringLiteral(lit) => lit.gen(p),
+            TSEnumMemberName::ComputedPropertyName(expr) => {
+                p.print(b'[');
+                expr.gen(p);
+                p.print(b']');
+            }
+            TSEnumMemberName::NumberLiteral(lit) => lit.gen(p),
+        }
+        if let Some(init) = &self.initializer {
+            print_assignment_right(b" =", init, p);
+        }
+    }
+}
+
+impl<'a> Gen for TSModuleDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        print_declare(&self.modifiers, p);
+        // The keyword is not recorded in the AST, `global` has none
+        let keyword_span = Span::new(self.span.start, module_name_span(&self.id).start);
+        match p.source_text_of(keyword_span) {
+            Some(text) if text.contains("namespace") => p.print_str(b"namespace "),
+            Some(text) if text.contains("module") => p.print_str(b"module "),
+            Some(_) => {}
+            None => match &self.id {
+                TSModuleDeclarationName::Identifier(ident) if ident.name == "global" => {}
+                TSModuleDeclarationName::Identifier(_) => p.print_str(b"namespace "),
+                TSModuleDeclarationName::StringLiteral(_) => p.print_str(b"module "),
+            },
+        }
+        let mut decl = self;
+        loop {
+            match &decl.id {
+                TSModuleDeclarationName::Identifier(ident) => ident.gen(p),
+                TSModuleDeclarationName::StringLiteral(lit) => lit.gen(p),
+            }
+            match &decl.body {
+                TSModuleDeclarationBody::TSModuleDeclaration(body) => {
+                    p.print(b'.');
+                    decl = body;
+                }
+                TSModuleDeclarationBody::TSModuleBlock(block) => {
+                    // `declare module "foo";`
+                    if block.span.start == block.span.end {
+                        p.print_semicolon_after_statement();
+                        return;
+                    }
+                    p.print_space();
+                    if block.body.is_empty() && !p.has_comments_before(block.span.end) {
+                        p.print_str(b"{}");
+                    } else {
+                        p.print(b'{');
+                        p.print_newline();
+                        p.indent();
+                        p.print_statements(&block.body);
+                        p.print_leading_comments(block.span.end);
+                        p.dedent();
+                        p.needs_semicolon = false;
+                        p.print_indent();
+                        p.print(b'}');
+                    }
+                    p.print_newline();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Gen for TSImportEqualsDeclaration<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"import ");
+        if self.import_kind.is_type() {
+            p.print_str(b"type ");
+        }
+        self.id.gen(p);
+        p.print_str(b" = ");
+        match &*self.module_reference {
+            TSModuleReference::TypeName(name) => name.gen(p),
+            TSModuleReference::ExternalModuleReference(reference) => {
+                p.print_str(b"require(");
+                reference.expression.gen(p);
+                p.print(b')');
+            }
+        }
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl<'a> Gen for TSExportAssignment<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"export = ");
+        self.expression.gen(p);
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl Gen for TSNamespaceExportDeclaration {
+    fn gen(&self, p: &mut Formatter) {
+        p.print_str(b"export as namespace ");
+        self.id.gen(p);
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl<'a> Gen for TSClassImplements<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for TSAsExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        p.print_str(b" as ");
+        self.type_annotation.gen(p);
+    }
+}
+
+impl<'a> Gen for TSSatisfiesExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        p.print_str(b" satisfies ");
+        self.type_annotation.gen(p);
+    }
+}
+
+impl<'a> Gen for TSTypeAssertion<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        p.print(b'<');
+        self.type_annotation.gen(p);
+        p.print(b'>');
+        self.expression.gen(p);
+    }
+}
+
+impl<'a> Gen for TSNonNullExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        p.print(b'!');
+    }
+}
+
+impl<'a> Gen for TSInstantiationExpression<'a> {
+    fn gen(&self, p: &mut Formatter) {
+        self.expression.gen(p);
+        self.type_parameters.gen(p);
+    }
+}
+
+fn module_name_span(name: &TSModuleDeclarationName) -> Span {
+    match name {
+        TSModuleDeclarationName::Identifier(ident) => ident.span,
+        TSModuleDeclarationName::StringLiteral(lit) => lit.span,
+    }
+}
@@ -3,37 +3,120 @@
 
 #![feature(let_chains)]
 
+mod check;
+mod comment;
+mod doc;
 mod gen;
+mod gen_jsx;
+mod gen_ts;
+mod range;
+
+use std::collections::VecDeque;
 
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::ast::*;
-
-pub use crate::gen::Gen;
+use oxc_sourcemap::{SourceMap, SourceMapBuilder};
+use oxc_span::{GetSpan, Span};
+
+pub use crate::{
+    check::{check, CheckError},
+    doc::GroupId,
+    gen::Gen,
+    range::TextEdit,
+};
+use crate::{
+    comment::PendingComment,
+    doc::{Doc, Group},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct FormatterOptions {
     pub indentation: u8,
+    /// The line width the formatter tries to stay within
+    pub print_width: usize,
+    pub quote_style: QuoteStyle,
+    pub trailing_comma: TrailingComma,
+    /// Print semicolons at the end of statements, or only where they are needed to prevent ASI hazards
+    pub semi: bool,
+    /// Print spaces between brackets in object literals, e.g. `{ foo: bar }`
+    pub bracket_spacing: bool,
 }
 
 impl Default for FormatterOptions {
     fn default() -> Self {
-        Self { indentation: 4 }
+        Self {
+            indentation: 4,
+            print_width: 80,
+            quote_style: QuoteStyle::Double,
+            trailing_comma: TrailingComma::All,
+            semi: true,
+            bracket_spacing: true,
+        }
     }
 }
 
+/// The preferred quote for string literals, the other one is used if it results in fewer escapes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::Double => b'"',
+            Self::Single => b'\'',
+        }
+    }
+}
+
+/// Where trailing commas are printed in multi-line comma separated lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TrailingComma {
+    None,
+    /// Where valid in ES5 (objects, arrays, etc.)
+    Es5,
+    /// Wherever possible, including function parameters and call arguments
+    All,
+}
+
 pub struct Formatter {
     options: FormatterOptions,
 
     /// Output Code
     code: Vec<u8>,
 
-    /// Current indentation tracking
-    indentation: u8,
+    /// Commands not laid out yet, see [doc]
+    docs: Vec<Doc>,
+    /// Text referenced by `docs`
+    doc_text: Vec<u8>,
+    /// Open groups
+    groups: Vec<Group>,
+    /// Whether each open `Doc::IndentIfBreak` added indentation
+    if_break_indents: Vec<bool>,
+
+    /// Original source text, used for keeping some of the original formatting
+    source_text: String,
+
+    source_map: Option<SourceMapBuilder>,
+
+    /// Comments not printed yet, sorted by position
+    comments: VecDeque<PendingComment>,
+
+    /// Current indentation tracking, in spaces
+    indentation: usize,
 
     // states
     needs_semicolon: bool,
 }
 
+pub struct FormatterReturn {
+    pub code: String,
+    /// `Some` when enabled by [Formatter::with_source_map]
+    pub source_map: Option<SourceMap>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Separator {
     Comma,
@@ -47,21 +130,62 @@ impl Formatter {
         Self {
             options,
             code: Vec::with_capacity(source_len),
+            docs: vec![],
+            doc_text: vec![],
+            groups: vec![],
+            if_break_indents: vec![],
+            source_text: String::new(),
+            source_map: None,
+            comments: VecDeque::new(),
             indentation: 0,
             needs_semicolon: false,
         }
     }
 
-    pub fn build(mut self, program: &Program<'_>) -> String {
+    /// Record a source map for `source_text`, the text `program` was parsed from.
+    pub fn with_source_map(&mut self, source_name: &str, source_text: &str) {
+        self.source_map = Some(SourceMapBuilder::new(source_name, source_text));
+    }
+
+    /// Keep formatting choices from `source_text`, the text `program` was parsed from, like Prettier does:
+    ///
+    /// * object literals with a line break after `{` stay expanded
+    /// * a single blank line between statements, class members and object properties is kept
+    /// * string literals are printed from their raw text
+    pub fn with_source_text(&mut self, source_text: &str) {
+        self.source_text = source_text.to_string();
+    }
+
+    pub fn build(mut self, program: &Program<'_>) -> FormatterReturn {
         program.gen(&mut self);
-        self.into_code()
+        self.into_code_and_source_map()
     }
 
     #[inline]
-    pub fn into_code(self) -> String {
+    pub fn into_code(mut self) -> String {
+        self.flush();
         unsafe { String::from_utf8_unchecked(self.code) }
     }
 
+    pub fn into_code_and_source_map(mut self) -> FormatterReturn {
+        self.flush();
+        let source_map = self.source_map.take().map(SourceMapBuilder::into_source_map);
+        FormatterReturn { code: self.into_code(), source_map }
+    }
+
+    pub fn options(&self) -> &FormatterOptions {
+        &self.options
+    }
+
+    /// Map the current output position to `position` in the original source.
+    #[inline]
+    pub fn add_source_mapping(&mut self, position: u32) {
+        if self.source_map.is_some() {
+            self.docs.push(Doc::Mapping(position));
+        }
+    }
+
+    /// Output laid out so far
     pub fn code(&self) -> &Vec<u8> {
         &self.code
     }
@@ -69,33 +193,36 @@ impl Formatter {
     /// Push a single character into the buffer
     #[inline]
     pub fn print(&mut self, ch: u8) {
-        self.code.push(ch);
+        self.push_text(&[ch]);
     }
 
     /// Push a string into the buffer
     #[inline]
     pub fn print_str(&mut self, s: &[u8]) {
-        self.code.extend_from_slice(s);
+        self.push_text(s);
     }
 
     #[inline]
     pub fn print_space(&mut self) {
-        self.code.push(b' ');
+        self.print(b' ');
     }
 
     #[inline]
     pub fn print_newline(&mut self) {
-        self.code.push(b'\n');
+        self.print(b'\n');
+        if self.groups.is_empty() {
+            self.flush();
+        }
     }
 
     #[inline]
     pub fn indent(&mut self) {
-        self.indentation += self.options.indentation;
+        self.docs.push(Doc::Indent);
     }
 
     #[inline]
     pub fn dedent(&mut self) {
-        self.indentation -= self.options.indentation;
+        self.docs.push(Doc::Dedent);
     }
 
     #[inline]
@@ -103,14 +230,22 @@ impl Formatter {
         self.print(b';');
     }
 
+    /// Print `;` at the end of a statement, unless disabled by [FormatterOptions::semi]
+    #[inline]
+    pub fn print_statement_semicolon(&mut self) {
+        if self.options.semi {
+            self.print_semicolon();
+        }
+    }
+
     #[inline]
     pub fn print_comma(&mut self) {
         self.print(b',');
     }
 
     fn print_semicolon_after_statement(&mut self) {
-        self.print_semicolon();
-        self.print(b'\n');
+        self.print_statement_semicolon();
+        self.print_newline();
     }
 
     fn print_semicolon_if_needed(&mut self) {
@@ -135,12 +270,42 @@ impl Formatter {
         self.print(b'=');
     }
 
+    #[inline]
     pub fn print_indent(&mut self) {
-        for _ in 0..self.indentation {
-            self.print(b' ');
+        self.docs.push(Doc::Indentation);
+    }
+
+    /// Print a `,` if the enclosing group is broken and trailing commas are enabled for `kind`.
+    #[inline]
+    pub fn print_trailing_comma(&mut self, kind: TrailingComma) {
+        if self.options.trailing_comma >= kind && kind != TrailingComma::None {
+            self.print_if_break(",");
         }
     }
 
+    /// Print the opening bracket of an object literal, pattern or import/export specifiers,
+    /// followed by a line break when the enclosing group is broken.
+    pub fn print_open_brace(&mut self) {
+        self.print(b'{');
+        self.indent_if_break();
+        if self.options.bracket_spacing {
+            self.print_line();
+        } else {
+            self.print_soft_line();
+        }
+    }
+
+    /// The counterpart of [Formatter::print_open_brace].
+    pub fn print_close_brace(&mut self) {
+        self.dedent_if_break();
+        if self.options.bracket_spacing {
+            self.print_line();
+        } else {
+            self.print_soft_line();
+        }
+        self.print(b'}');
+    }
+
     #[inline]
     pub fn print_sequence<T: Gen>(&mut self, items: &[T], separator: Separator) {
         let len = items.len();
@@ -157,6 +322,72 @@ impl Formatter {
         }
     }
 
+    /// Print a comma separated list, which is broken into one item per line
+    /// when the enclosing group is broken.
+    pub fn print_soft_list<T: Gen>(&mut self, items: &[T]) {
+        for (index, item) in items.iter().enumerate() {
+            if index != 0 {
+                self.print_comma();
+                self.print_line();
+            }
+            item.gen(self);
+        }
+    }
+
+    /// Print statements, keeping a single blank line between them from the original source.
+    pub fn print_statements(&mut self, statements: &[Statement<'_>]) {
+        for (index, stmt) in statements.iter().enumerate() {
+            self.print_semicolon_if_needed();
+            stmt.gen(self);
+            if index + 1 != statements.len() && self.is_next_line_empty(stmt.span().end) {
+                self.print_newline();
+            }
+        }
+    }
+
+    /// Whether the line after the one `position` is on is empty in the original source,
+    /// ignoring `,`, `;` and comments at the end of the line.
+    pub fn is_next_line_empty(&self, position: u32) -> bool {
+        let Some(text) = self.source_text.get(position as usize..) else { return false };
+        let mut rest = text;
+        loop {
+            let trimmed = rest.trim_start_matches([' ', '\t', ',', ';']);
+            if let Some(comment) = trimmed.strip_prefix("/*")
+                && let Some(end) = comment.find("*/")
+                && !comment[..end].contains('\n') {
+                rest = &comment[end + 2..];
+            } else {
+                rest = trimmed;
+                break;
+            }
+        }
+        if rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+        }
+        let Some(rest) = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')) else {
+            return false;
+        };
+        let rest = rest.trim_start_matches([' ', '\t']);
+        rest.starts_with(['\n', '\r'])
+    }
+
+    /// Whether there is a line break between `start` and `end` in the original source.
+    pub fn has_newline_in_range(&self, start: u32, end: u32) -> bool {
+        self.source_text.get(start as usize..end as usize).is_some_and(|text| text.contains('\n'))
+    }
+
+    /// Whether the rest of the line after `position` is empty in the original source.
+    pub fn is_followed_by_newline(&self, position: u32) -> bool {
+        self.source_text
+            .get(position as usize..)
+            .is_some_and(|text| text.trim_start_matches([' ', '\t']).starts_with(['\n', '\r']))
+    }
+
+    /// The original source text of `span`, if it was provided by [Formatter::with_source_text].
+    pub fn source_text_of(&self, span: Span) -> Option<&str> {
+        self.source_text.get(span.start as usize..span.end as usize)
+    }
+
     #[inline]
     pub fn print_body(&mut self, stmt: &Statement<'_>) {
         if let Statement::BlockStatement(block) = stmt {
@@ -173,13 +404,15 @@ impl Formatter {
 
     #[inline]
     pub fn print_block1(&mut self, stmt: &BlockStatement<'_>) {
+        if stmt.body.is_empty() && !self.has_comments_before(stmt.span.end) {
+            self.print_str(b"{}");
+            return;
+        }
         self.print(b'{');
         self.print_newline();
         self.indent();
-        for item in &stmt.body {
-            self.print_semicolon_if_needed();
-            item.gen(self);
-        }
+        self.print_statements(&stmt.body);
+        self.print_leading_comments(stmt.span.end);
         self.dedent();
         self.needs_semicolon = false;
         self.print_indent();
@@ -212,7 +445,7 @@ impl Formatter {
         }
     }
 
-    pub fn last_char(&self) -> Option<&u8> {
-        self.code.last()
+    pub fn last_char(&self) -> Option<u8> {
+        self.last_printed_char()
     }
 }
@@ -0,0 +1,124 @@
+//! Range formatting
+//!
+//! Editors format a selection or the statement that was just typed instead of the whole file.
+//! The statements overlapping the range in the innermost statement list
+//! (program, block, function body, switch case or static block) containing the range are formatted,
+//! and the result is returned as an edit to the original source text.
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, Visit};
+use oxc_span::{GetSpan, Span};
+
+use crate::Formatter;
+
+/// Replace `span` of the original source text with `new_text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+impl Formatter {
+    /// Format the statements overlapping `range`, a byte range of the original source text.
+    ///
+    /// Requires the source text from [Formatter::with_source_text].
+    /// Returns no edits when the statements are already formatted.
+    pub fn build_range<'a>(mut self, program: &'a Program<'a>, range: Span) -> Vec<TextEdit> {
+        let mut finder = StatementsFinder { range, statements: &program.body };
+        finder.visit_statements(&program.body);
+        let statements = finder.statements;
+        let Some(start) = statements.iter().position(|stmt| overlaps(stmt.span(), range)) else {
+            return vec![];
+        };
+        let len =
+            statements[start..].iter().take_while(|stmt| overlaps(stmt.span(), range)).count();
+        let overlapping = &statements[start..start + len];
+        let (Some(first), Some(last)) = (overlapping.first(), overlapping.last()) else {
+            return vec![];
+        };
+        let span = Span::new(first.span().start, last.span().end);
+        let Some(original) = self.source_text_of(span).map(ToString::to_string) else {
+            return vec![];
+        };
+
+        // Comments outside of the replaced text stay where they are
+        self.retain_comments_within(span);
+        self.indentation = self.line_indentation(span.start);
+        self.print_statements(overlapping);
+        self.print_leading_comments(span.end);
+
+        let code = self.into_code();
+        // The original source keeps the indentation of the first line and the line break after the last one
+        let new_text = code.trim_start_matches([' ', '\t']).trim_end_matches('\n');
+        if new_text == original {
+            return vec![];
+        }
+        vec![TextEdit { span, new_text: new_text.to_string() }]
+    }
+
+    /// Format the statement ending at `position`, e.g. after typing `;` or `}`.
+    pub fn build_on_type<'a>(self, program: &'a Program<'a>, position: u32) -> Vec<TextEdit> {
+        self.build_range(program, Span::new(position, position))
+    }
+
+    /// Width of the whitespace at the start of the line containing `position`,
+    /// a tab advances to the next multiple of [crate::FormatterOptions::indentation]
+    fn line_indentation(&self, position: u32) -> usize {
+        let before = self.source_text.get(..position as usize).unwrap_or_default();
+        let line_start = before.rfind(['\n', '\r']).map_or(0, |i| i + 1);
+        let tab_width = usize::from(self.options.indentation).max(1);
+        let mut width = 0;
+        for c in before[line_start..].chars() {
+            match c {
+                ' ' => width += 1,
+                '\t' => width += tab_width - width % tab_width,
+                _ => break,
+            }
+        }
+        width
+    }
+}
+
+fn overlaps(span: Span, range: Span) -> bool {
+    span.start <= range.end && range.start <= span.end
+}
+
+/// Find the innermost statement list whose container strictly contains the range
+/// and which has statements overlapping the range
+struct StatementsFinder<'a> {
+    range: Span,
+    statements: &'a [Statement<'a>],
+}
+
+impl<'a> StatementsFinder<'a> {
+    fn enter(&mut self, container: Span, statements: &'a [Statement<'a>]) {
+        if container.start < self.range.start
+            && self.range.end < container.end
+            && statements.iter().any(|stmt| overlaps(stmt.span(), self.range))
+        {
+            self.statements = statements;
+        }
+    }
+}
+
+impl<'a> Visit<'a> for StatementsFinder<'a> {
+    fn visit_block_statement(&mut self, stmt: &'a BlockStatement<'a>) {
+        self.enter(stmt.span, &stmt.body);
+        self.visit_statements(&stmt.body);
+    }
+
+    fn visit_function_body(&mut self, body: &'a FunctionBody<'a>) {
+        self.enter(body.span, &body.statements);
+        self.visit_statements(&body.statements);
+    }
+
+    fn visit_switch_case(&mut self, case: &'a SwitchCase<'a>) {
+        self.enter(case.span, &case.consequent);
+        self.visit_statements(&case.consequent);
+    }
+
+    fn visit_static_block(&mut self, block: &'a StaticBlock<'a>) {
+        self.enter(block.span, &block.body);
+        self.visit_statements(&block.body);
+    }
+}
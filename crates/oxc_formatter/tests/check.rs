@@ -0,0 +1,39 @@
+use oxc_formatter::{check, FormatterOptions};
+use oxc_span::SourceType;
+
+fn test(source_text: &str, source_type: SourceType, expected: &str) {
+    let options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    assert_eq!(check(source_text, source_type, options).as_deref(), Ok(expected));
+}
+
+#[test]
+fn passes() {
+    let source_type = SourceType::default();
+    test("a  =  (1 + 2) * 3", source_type, "a = (1 + 2) * 3;\n");
+    test("x = [a, b,]", source_type, "x = [a, b];\n");
+    test("if (a) b(); else c()", source_type, "if (a) {\n  b();\n} else\n  c();\n");
+    test("// comment\nfoo( ) // trailing", source_type, "// comment\nfoo(); // trailing\n");
+}
+
+#[test]
+fn typescript() {
+    let source_type = SourceType::default().with_typescript(true).with_module(true);
+    test("type A=(string|number)[]", source_type, "type A = (string | number)[];\n");
+    test("let x=<string>(y as any)", source_type, "let x = <string>(y as any);\n");
+}
+
+#[test]
+fn jsx_whitespace() {
+    let source_type = SourceType::default().with_jsx(true);
+    test("x = <p>  a   b{\" \"}\n  c</p>", source_type, "x = <p> a b c</p>;\n");
+    test(
+        "x = <p>Some text that is long enough to need breaking with <a href='https://example.com'>a link</a></p>",
+        source_type,
+        "x = (\n  <p>\n    Some text that is long enough to need breaking with{\" \"}\n    <a href=\"https://example.com\">a link</a>\n  </p>\n);\n",
+    );
+}
+
+#[test]
+fn non_finite_numbers() {
+    test("x = 1e999", SourceType::default(), "x = 1e999;\n");
+}
@@ -0,0 +1,72 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test(source_text: &str, expected: &str) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let mut formatter = Formatter::new(source_text.len(), FormatterOptions::default());
+    formatter.with_comments(source_text, &ret.trivias);
+    let code = formatter.build(&ret.program).code;
+    assert_eq!(code, expected, "for source {source_text}");
+}
+
+#[test]
+fn leading() {
+    test(
+        "// eslint-disable no-console\n/**\n   * JSDoc\n   * @param {number} a\n   */\nfunction foo(a) {}",
+        "// eslint-disable no-console\n/**\n * JSDoc\n * @param {number} a\n */\nfunction foo(a) {}\n",
+    );
+    test(
+        "function foo() {\n  // leading\n  a;\n}",
+        "function foo() {\n    // leading\n    a;\n}\n",
+    );
+}
+
+#[test]
+fn trailing() {
+    test("a; // trailing\nb;", "a; // trailing\nb;\n");
+    test("a; /* one */ // two\nb;", "a; /* one */ // two\nb;\n");
+    test("if (a) {} // after block", "if (a) {} // after block\n");
+    test("class A {\n  m() {} // after\n}", "class A {\n    m() {} // after\n}\n");
+}
+
+#[test]
+fn dangling() {
+    test("if (a) {\n  // empty\n}", "if (a) {\n    // empty\n}\n");
+    test("function foo() {\n  a;\n  /* end */\n}", "function foo() {\n    a;\n    /* end */\n}\n");
+    test("a;\n// end of file", "a;\n// end of file\n");
+}
+
+#[test]
+fn object_properties() {
+    test(
+        "x = { a: 1, // one\n  // two\n  b: 2 }",
+        "x = {\n    a: 1, // one\n    // two\n    b: 2,\n};\n",
+    );
+}
+
+#[test]
+fn inline() {
+    test("foo(/* inline */ 1);", "foo(/* inline */ 1);\n");
+}
+
+#[test]
+fn parameters() {
+    test("function f(/* a */ x /* b */, y) {}", "function f(/* a */ x /* b */, y) {}\n");
+    test("(/* p */ a) => a;", "(/* p */ a) => a;\n");
+}
+
+#[test]
+fn binding_patterns() {
+    test("const [a /* x */, b] = arr;", "const [a /* x */, b] = arr;\n");
+    test("const { a /* x */, b: /* y */ c } = obj;", "const { a /* x */, b: /* y */ c } = obj;\n");
+}
+
+#[test]
+fn specifiers() {
+    test("import { a /* ia */, b } from \"x\";", "import { a /* ia */, b } from \"x\";\n");
+    test("export { /* ea */ a, b as c /* eb */ };", "export { /* ea */ a, b as c /* eb */ };\n");
+}
@@ -0,0 +1,80 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test(source_text: &str, expected: &str) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_jsx(true).with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(ret.errors.is_empty(), "parse errors for source {source_text}");
+    let options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    let mut formatter = Formatter::new(source_text.len(), options);
+    formatter.with_source_text(source_text);
+    formatter.with_comments(source_text, &ret.trivias);
+    let code = formatter.build(&ret.program).code;
+    assert_eq!(code, expected, "for source {source_text}");
+}
+
+#[test]
+fn elements() {
+    test("<div/>", "<div />;\n");
+    test("<div></div>", "<div></div>;\n");
+    test("<a:b c:d='e'/>", "<a:b c:d=\"e\" />;\n");
+    test("<Foo.Bar {...props}/>", "<Foo.Bar {...props} />;\n");
+    test("<div title='say \"hi\"'/>", "<div title='say \"hi\"' />;\n");
+    test("<div>{/* comment */}</div>", "<div>{/* comment */}</div>;\n");
+    test("x = <><A/><B/></>", "x = (\n  <>\n    <A />\n    <B />\n  </>\n);\n");
+}
+
+#[test]
+fn attributes() {
+    test("x = <div className='a' id='b'/>", "x = <div className=\"a\" id=\"b\" />;\n");
+    test(
+        "x = <input value={value} onChange={(e) => setValue(e.target.value)} disabled required />",
+        "x = (\n  <input\n    value={value}\n    onChange={(e) => setValue(e.target.value)}\n    disabled\n    required\n  />\n);\n",
+    );
+    // A single string attribute is never broken
+    test(
+        "x = <div className='a very long class name that does not fit on the line at all'/>",
+        "x = (\n  <div className=\"a very long class name that does not fit on the line at all\" />\n);\n",
+    );
+}
+
+#[test]
+fn children() {
+    test("x = <p>  hello   world  </p>", "x = <p> hello world </p>;\n");
+    test("x = <p>\n  hello\n  world\n</p>", "x = <p>hello world</p>;\n");
+    test("x = <p><b>a</b></p>", "x = (\n  <p>\n    <b>a</b>\n  </p>\n);\n");
+    test(
+        "x = <p>Hello, <b>{name}</b>!</p>",
+        "x = (\n  <p>\n    Hello, <b>{name}</b>!\n  </p>\n);\n",
+    );
+    test(
+        "x = <div>\n  <A />\n\n\n  <B />\n</div>",
+        "x = (\n  <div>\n    <A />\n\n    <B />\n  </div>\n);\n",
+    );
+    // Significant whitespace at the end of a line
+    test(
+        "x = <p>Some text that is long enough to need breaking with <a href='https://example.com'>a link</a></p>",
+        "x = (\n  <p>\n    Some text that is long enough to need breaking with{\" \"}\n    <a href=\"https://example.com\">a link</a>\n  </p>\n);\n",
+    );
+    test("x = <p>a{\" \"}\n  b</p>", "x = <p>a b</p>;\n");
+}
+
+#[test]
+fn parentheses() {
+    test(
+        "function App() { return <ul>{items.map(item => <li key={item.id} title={item.title}>{item.name}</li>)}</ul> }",
+        "function App() {\n  return (\n    <ul>\n      {items.map((item) => (\n        <li key={item.id} title={item.title}>\n          {item.name}\n        </li>\n      ))}\n    </ul>\n  );\n}\n",
+    );
+    test("x = (<div>a</div>)", "x = <div>a</div>;\n");
+    test("x = cond && <div><b/></div>", "x = cond && (\n  <div>\n    <b />\n  </div>\n);\n");
+    test("render(<App/>, root)", "render(<App />, root);\n");
+}
+
+#[test]
+fn conditional() {
+    test("x = a ? <b/> : <c/>", "x = a ? <b /> : <c />;\n");
+    test("x = a ? <div><b/></div> : null", "x = a ? (\n  <div>\n    <b />\n  </div>\n) : null;\n");
+}
@@ -0,0 +1,141 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions, QuoteStyle, TrailingComma};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test_with_options(source_text: &str, expected: &str, options: FormatterOptions) {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let mut formatter = Formatter::new(source_text.len(), options);
+    formatter.with_source_text(source_text);
+    formatter.with_comments(source_text, &ret.trivias);
+    let code = formatter.build(&ret.program).code;
+    assert_eq!(code, expected, "for source {source_text}");
+}
+
+fn test(source_text: &str, expected: &str) {
+    let options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn print_width() {
+    test("foo(a, b);", "foo(a, b);\n");
+    test(
+        "foo(argumentNumberOne, argumentNumberTwo, argumentNumberThree, argumentNumberFour);",
+        "foo(\n  argumentNumberOne,\n  argumentNumberTwo,\n  argumentNumberThree,\n  argumentNumberFour,\n);\n",
+    );
+    let options =
+        FormatterOptions { indentation: 2, print_width: 10, ..FormatterOptions::default() };
+    test_with_options("x = [1, 2, 3];", "x = [\n  1,\n  2,\n  3,\n];\n", options);
+}
+
+#[test]
+fn hug_last_argument() {
+    test(
+        "describe('thing', function () { it('works', () => { expect(a).toBe(b) }) })",
+        "describe(\"thing\", function () {\n  it(\"works\", () => {\n    expect(a).toBe(b);\n  });\n});\n",
+    );
+}
+
+#[test]
+fn member_chain() {
+    test("a.b().c();", "a.b().c();\n");
+    test(
+        "promise.then(result => console.log(result)).catch(error => handle(error)).finally(() => done());",
+        "promise\n  .then((result) => console.log(result))\n  .catch((error) => handle(error))\n  .finally(() => done());\n",
+    );
+}
+
+#[test]
+fn binary_expressions() {
+    test(
+        "const longCondition = someCondition && anotherCondition || yetAnotherCondition && theFinalCondition;",
+        "const longCondition =\n  (someCondition && anotherCondition) ||\n  (yetAnotherCondition && theFinalCondition);\n",
+    );
+    test(
+        "if (someCondition && anotherCondition && yetAnotherCondition && theFinalConditionHere) {}",
+        "if (\n  someCondition &&\n  anotherCondition &&\n  yetAnotherCondition &&\n  theFinalConditionHere\n) {}\n",
+    );
+}
+
+#[test]
+fn objects() {
+    test("x = {a: 1, b: 2}", "x = { a: 1, b: 2 };\n");
+    // A line break after `{` keeps the object expanded
+    test("x = {\n  a: 1, b: 2}", "x = {\n  a: 1,\n  b: 2,\n};\n");
+    test("x = {a, b: c}", "x = { a, b: c };\n");
+}
+
+#[test]
+fn blank_lines() {
+    test("a;\n\n\n\nb;\nc;", "a;\n\nb;\nc;\n");
+}
+
+#[test]
+fn quote_style() {
+    test("x = 'a'", "x = \"a\";\n");
+    test("x = 'a\"b'", "x = 'a\"b';\n");
+    let options = FormatterOptions {
+        indentation: 2,
+        quote_style: QuoteStyle::Single,
+        ..FormatterOptions::default()
+    };
+    test_with_options("x = \"a\"", "x = 'a';\n", options);
+}
+
+#[test]
+fn trailing_comma() {
+    let source = "function foo(argumentNumberOne, argumentNumberTwo) { return [argumentNumberOne, argumentNumberTwo] }";
+    let options = FormatterOptions {
+        indentation: 2,
+        print_width: 40,
+        trailing_comma: TrailingComma::Es5,
+        ..FormatterOptions::default()
+    };
+    test_with_options(
+        source,
+        "function foo(\n  argumentNumberOne,\n  argumentNumberTwo\n) {\n  return [\n    argumentNumberOne,\n    argumentNumberTwo,\n  ];\n}\n",
+        options,
+    );
+    let options = FormatterOptions { trailing_comma: TrailingComma::None, ..options };
+    test_with_options(
+        source,
+        "function foo(\n  argumentNumberOne,\n  argumentNumberTwo\n) {\n  return [\n    argumentNumberOne,\n    argumentNumberTwo\n  ];\n}\n",
+        options,
+    );
+}
+
+#[test]
+fn semi() {
+    let options = FormatterOptions { indentation: 2, semi: false, ..FormatterOptions::default() };
+    test_with_options("a;\n[b] = c;\n(d);", "a\n;[b] = c\n;(d)\n", options);
+    test_with_options("class A { a = 1; [b] = 2 }", "class A {\n  a = 1;\n  [b] = 2\n}\n", options);
+}
+
+#[test]
+fn bracket_spacing() {
+    let options =
+        FormatterOptions { indentation: 2, bracket_spacing: false, ..FormatterOptions::default() };
+    test_with_options(
+        "import {a} from 'a'; x = {a: 1}",
+        "import {a} from \"a\";\nx = {a: 1};\n",
+        options,
+    );
+}
+
+#[test]
+fn deep_indentation() {
+    // More than `u8::MAX` spaces of indentation
+    let depth = 70;
+    let source_text = format!("{}a;{}", "{".repeat(depth), "}".repeat(depth));
+    let mut expected = String::new();
+    for level in 0..depth {
+        expected.push_str(&format!("{}{{\n", " ".repeat(level * 4)));
+    }
+    expected.push_str(&format!("{}a;\n", " ".repeat(depth * 4)));
+    for level in (0..depth).rev() {
+        expected.push_str(&format!("{}}}\n", " ".repeat(level * 4)));
+    }
+    test_with_options(&source_text, &expected, FormatterOptions::default());
+}
@@ -0,0 +1,83 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions, TextEdit};
+use oxc_parser::Parser;
+use oxc_span::{SourceType, Span};
+
+/// Format the text between the two `|` markers of `source_with_range`
+fn test(source_with_range: &str, expected: &str) {
+    let start = source_with_range.find('|').unwrap();
+    let end = source_with_range.rfind('|').unwrap() - 1;
+    let source_text = source_with_range.replacen('|', "", 2);
+    #[allow(clippy::cast_possible_truncation)]
+    let range = Span::new(start as u32, end as u32);
+    let edits = format_range(&source_text, range);
+    let mut code = source_text;
+    for edit in edits.iter().rev() {
+        code.replace_range(edit.span.start as usize..edit.span.end as usize, &edit.new_text);
+    }
+    assert_eq!(code, expected, "for source {source_with_range}");
+}
+
+fn format_range(source_text: &str, range: Span) -> Vec<TextEdit> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    let mut formatter = Formatter::new(source_text.len(), options);
+    formatter.with_source_text(source_text);
+    formatter.with_comments(source_text, &ret.trivias);
+    formatter.build_range(program, range)
+}
+
+#[test]
+fn statements() {
+    test("a  =  1;\n|b  =  2;|\nc  =  3;\n", "a  =  1;\nb = 2;\nc  =  3;\n");
+    test("|a  =  1;\nb  =  2;|\nc  =  3;\n", "a = 1;\nb = 2;\nc  =  3;\n");
+    // A range inside of an expression formats the whole statement
+    test("x  =  f(|a|,b);\n", "x = f(a, b);\n");
+}
+
+#[test]
+fn nested() {
+    test(
+        "function foo() {\n  a  =  1;\n  if (x) {\n    |b  =  2|;\n  }\n}\n",
+        "function foo() {\n  a  =  1;\n  if (x) {\n    b = 2;\n  }\n}\n",
+    );
+    test(
+        "describe(() => {\n    |it( 'works' , () => {})|;\n});\n",
+        "describe(() => {\n    it(\"works\", () => {});\n});\n",
+    );
+    // A tab is as wide as the indentation
+    test(
+        "function foo() {\n\t|if (x) {  b() }|\n}\n",
+        "function foo() {\n\tif (x) {\n    b();\n  }\n}\n",
+    );
+}
+
+#[test]
+fn comments() {
+    test(
+        "// keep  this\n|a  =  1; // trailing  comment|\n",
+        "// keep  this\na = 1; // trailing  comment\n",
+    );
+    test("|a  =  1;\n// between\nb  =  2;|\n", "a = 1;\n// between\nb = 2;\n");
+}
+
+#[test]
+fn no_edits() {
+    assert!(format_range("a = 1;\nb  =  2;\n", Span::new(0, 6)).is_empty());
+    assert!(format_range("a = 1;\n\n\nb  =  2;\n", Span::new(8, 8)).is_empty());
+}
+
+#[test]
+fn on_type() {
+    let source_text = "if (a) {\n  b  =  1;\n}\n";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let mut formatter = Formatter::new(source_text.len(), FormatterOptions::default());
+    formatter.with_source_text(source_text);
+    // After typing `;`
+    let edits = formatter.build_on_type(program, 19);
+    assert_eq!(edits, vec![TextEdit { span: Span::new(11, 19), new_text: "b = 1;".to_string() }]);
+}
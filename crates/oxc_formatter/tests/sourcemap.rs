@@ -0,0 +1,19 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+#[test]
+fn mappings() {
+    let source_text = "if(a){b(c)}";
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    let mut formatter = Formatter::new(source_text.len(), FormatterOptions::default());
+    formatter.with_source_map("test.js", source_text);
+    let ret = formatter.build(&program);
+    assert_eq!(ret.code, "if (a) {\n    b(c);\n}\n");
+    let source_map = ret.source_map.unwrap();
+    // `if` 0:0 -> 0:0, `a` 0:4 -> 0:3, `b(c)` 1:4 -> 0:6, `c` 1:6 -> 0:8
+    assert_eq!(source_map.mappings, "AAAA,IAAG;IAAG,EAAE");
+    assert_eq!(source_map.sources, vec!["test.js"]);
+}
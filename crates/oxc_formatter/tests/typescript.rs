@@ -0,0 +1,104 @@
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test_with_options(source_text: &str, expected: &str, options: FormatterOptions) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_typescript(true).with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(ret.errors.is_empty(), "parse errors for source {source_text}");
+    let mut formatter = Formatter::new(source_text.len(), options);
+    formatter.with_source_text(source_text);
+    formatter.with_comments(source_text, &ret.trivias);
+    let code = formatter.build(&ret.program).code;
+    assert_eq!(code, expected, "for source {source_text}");
+}
+
+fn test(source_text: &str, expected: &str) {
+    let options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn type_annotations() {
+    test("let x:number=1", "let x: number = 1;\n");
+    test(
+        "function f(x: /* t */ number /* n */, y?: string /* s */) {}",
+        "function f(x: /* t */ number /* n */, y?: string /* s */) {}\n",
+    );
+    test("let x!:number", "let x!: number;\n");
+    test(
+        "function foo<T,U extends T=T>(a?:T,...b:U[]):void{}",
+        "function foo<T, U extends T = T>(a?: T, ...b: U[]): void {}\n",
+    );
+    test("const f=async<T,>(x:T):Promise<T>=>x", "const f = async <T,>(x: T): Promise<T> => x;\n");
+    test("a as unknown as B", "a as unknown as B;\n");
+    test("<string>a", "<string>a;\n");
+    test("a!.b!", "a!.b!;\n");
+    test("foo<string>(a)", "foo<string>(a);\n");
+}
+
+#[test]
+fn types() {
+    test("type A=(string|number)[]", "type A = (string | number)[];\n");
+    test("type A=keyof typeof a", "type A = keyof typeof a;\n");
+    test("type A=(()=>void)|string", "type A = (() => void) | string;\n");
+    test("type A=[a:string,b?:number]", "type A = [a: string, b?: number];\n");
+    test("type A={a:string,b():void}", "type A = { a: string; b(): void };\n");
+    test(
+        "type A<T>={readonly[K in keyof T]?:T[K]}",
+        "type A<T> = { readonly [K in keyof T]?: T[K] };\n",
+    );
+    test(
+        "type A<T>=T extends string?'a':T extends number?'b':never",
+        "type A<T> = T extends string ? \"a\" : T extends number ? \"b\" : never;\n",
+    );
+    test(
+        "type A='aaaaaaaaaaaaaaaa'|'bbbbbbbbbbbbbbbbbbbb'|'cccccccccccccccccccc'|'dddddddddd'",
+        "type A =\n  | \"aaaaaaaaaaaaaaaa\"\n  | \"bbbbbbbbbbbbbbbbbbbb\"\n  | \"cccccccccccccccccccc\"\n  | \"dddddddddd\";\n",
+    );
+}
+
+#[test]
+fn declarations() {
+    test(
+        "interface A<T> extends B{a:string;b?():void\nreadonly [k:string]:any}",
+        "interface A<T> extends B {\n  a: string;\n  b?(): void;\n  readonly [k: string]: any;\n}\n",
+    );
+    test("enum A{B,C='c'}", "enum A {\n  B,\n  C = \"c\",\n}\n");
+    test("declare const enum A{}", "declare const enum A {}\n");
+    test("namespace A.B{export const x=1}", "namespace A.B {\n  export const x = 1;\n}\n");
+    test("declare module 'a';", "declare module \"a\";\n");
+    test(
+        "declare global{interface Window{a:string}}",
+        "declare global {\n  interface Window {\n    a: string;\n  }\n}\n",
+    );
+    test("import type {A} from 'a'", "import type { A } from \"a\";\n");
+    test("import fs=require('fs')", "import fs = require(\"fs\");\n");
+    test("export type * from 'a'", "export type * from \"a\";\n");
+    test(
+        "function f(a:string):void\nfunction f(a:any){}",
+        "function f(a: string): void;\nfunction f(a: any) {}\n",
+    );
+}
+
+#[test]
+fn classes() {
+    test(
+        "abstract class A<T> extends B<T> implements C{private readonly a:number=1;abstract b():void;declare c?:string;constructor(public d:string){super()}}",
+        "abstract class A<T> extends B<T> implements C {\n  private readonly a: number = 1;\n  abstract b(): void;\n  declare c?: string;\n  constructor(public d: string) {\n    super();\n  }\n}\n",
+    );
+    test(
+        "@dec() export class A{@input() a:string}",
+        "@dec() export class A {\n  @input() a: string;\n}\n",
+    );
+    test("@dec()\nexport class A{}", "@dec()\nexport class A {}\n");
+}
+
+#[test]
+fn no_semi() {
+    let options = FormatterOptions { indentation: 2, semi: false, ..FormatterOptions::default() };
+    test_with_options("type A={a:string,b:number}", "type A = { a: string; b: number }\n", options);
+    test_with_options("interface A{a:string}", "interface A {\n  a: string\n}\n", options);
+}
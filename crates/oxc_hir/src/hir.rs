@@ -687,6 +687,9 @@ pub struct CallExpression<'a> {
     pub callee: Expression<'a>,
     pub arguments: Vec<'a, Argument<'a>>,
     pub optional: bool, // for optional chaining
+    /// Annotated with `/*#__PURE__*/`, calls a function annotated with `/*@__NO_SIDE_EFFECTS__*/`
+    /// or a global builtin without side effects
+    pub pure: bool,
 }
 
 impl<'a> CallExpression<'a> {
@@ -739,6 +742,8 @@ pub struct NewExpression<'a> {
     pub span: Span,
     pub callee: Expression<'a>,
     pub arguments: Vec<'a, Argument<'a>>,
+    /// Annotated with `/*#__PURE__*/` or a global builtin constructor without side effects
+    pub pure: bool,
 }
 
 /// Meta Property `new.target` | `import.meta`
@@ -1412,6 +1417,8 @@ pub struct Function<'a> {
     pub r#async: bool,
     pub params: Box<'a, FormalParameters<'a>>,
     pub body: Option<Box<'a, FunctionBody<'a>>>,
+    /// Annotated with `/*@__NO_SIDE_EFFECTS__*/`, calls to this function are pure
+    pub pure: bool,
 }
 
 impl<'a> Function<'a> {
@@ -1496,6 +1503,7 @@ impl<'a> FunctionBody<'a> {
 /// Arrow Function Definitions
 #[derive(Debug, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type", rename_all = "camelCase"))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ArrowExpression<'a> {
     pub span: Span,
     /// Is the function body an arrow expression? i.e. `() => expr` instead of `() => {}`
@@ -1505,6 +1513,8 @@ pub struct ArrowExpression<'a> {
     pub params: Box<'a, FormalParameters<'a>>, // UniqueFormalParameters in spec
     /// See `expression` for whether this arrow expression returns an expression.
     pub body: Box<'a, FunctionBody<'a>>,
+    /// Annotated with `/*@__NO_SIDE_EFFECTS__*/`, calls to this function are pure
+    pub pure: bool,
 }
 
 /// Generator Function Definitions
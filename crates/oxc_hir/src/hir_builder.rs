@@ -413,6 +413,7 @@ impl<'a> HirBuilder<'a> {
         r#async: bool,
         params: Box<'a, FormalParameters<'a>>,
         body: Box<'a, FunctionBody<'a>>,
+        pure: bool,
     ) -> Expression<'a> {
         Expression::ArrowExpression(self.alloc(ArrowExpression {
             span,
@@ -421,6 +422,7 @@ impl<'a> HirBuilder<'a> {
             r#async,
             params,
             body,
+            pure,
         }))
     }
 
@@ -459,8 +461,15 @@ impl<'a> HirBuilder<'a> {
         callee: Expression<'a>,
         arguments: Vec<'a, Argument<'a>>,
         optional: bool, // for optional chaining
+        pure: bool,
     ) -> Expression<'a> {
-        Expression::CallExpression(self.alloc(CallExpression { span, callee, arguments, optional }))
+        Expression::CallExpression(self.alloc(CallExpression {
+            span,
+            callee,
+            arguments,
+            optional,
+            pure,
+        }))
     }
 
     pub fn chain_expression(&mut self, span: Span, expression: ChainElement<'a>) -> Expression<'a> {
@@ -563,8 +572,9 @@ impl<'a> HirBuilder<'a> {
         span: Span,
         callee: Expression<'a>,
         arguments: Vec<'a, Argument<'a>>,
+        pure: bool,
     ) -> Expression<'a> {
-        Expression::NewExpression(self.alloc(NewExpression { span, callee, arguments }))
+        Expression::NewExpression(self.alloc(NewExpression { span, callee, arguments, pure }))
     }
 
     pub fn object_expression(
@@ -762,8 +772,19 @@ impl<'a> HirBuilder<'a> {
         r#async: bool,
         params: Box<'a, FormalParameters<'a>>,
         body: Option<Box<'a, FunctionBody<'a>>>,
+        pure: bool,
     ) -> Box<'a, Function<'a>> {
-        self.alloc(Function { r#type, span, id, expression, generator, r#async, params, body })
+        self.alloc(Function {
+            r#type,
+            span,
+            id,
+            expression,
+            generator,
+            r#async,
+            params,
+            body,
+            pure,
+        })
     }
 
     pub fn function_body(
@@ -2,8 +2,8 @@ use oxc_semantic::ReferenceFlag;
 use oxc_syntax::operator::{AssignmentOperator, LogicalOperator, UnaryOperator};
 
 use crate::hir::{
-    ArrayExpressionElement, Expression, NumberLiteral, ObjectProperty, ObjectPropertyKind,
-    PropertyKey, SpreadElement, UnaryExpression,
+    Argument, ArrayExpressionElement, CallExpression, Expression, NewExpression, NumberLiteral,
+    ObjectProperty, ObjectPropertyKind, PropertyKey, SpreadElement, UnaryExpression,
 };
 
 /// Code ported from [closure-compiler](https://github.com/google/closure-compiler/blob/f3ce5ed8b630428e311fe9aa2e20d36560d975e2/src/com/google/javascript/jscomp/NodeUtil.java#LL836C6-L836C6)
@@ -109,11 +109,29 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for Expression<'a> {
             | Self::BigintLiteral(_)
             | Self::NullLiteral(_)
             | Self::RegExpLiteral(_)
-            | Self::FunctionExpression(_) => false,
+            | Self::FunctionExpression(_)
+            | Self::ArrowExpression(_) => false,
             Self::Identifier(ident) => ident.reference_flag == ReferenceFlag::Write,
             Self::UnaryExpression(unary_expr) => {
                 unary_expr.check_for_state_change(check_for_new_objects)
             }
+            Self::LogicalExpression(logical_expr) => {
+                logical_expr.left.check_for_state_change(check_for_new_objects)
+                    || logical_expr.right.check_for_state_change(check_for_new_objects)
+            }
+            Self::ConditionalExpression(conditional_expr) => {
+                conditional_expr.test.check_for_state_change(check_for_new_objects)
+                    || conditional_expr.consequent.check_for_state_change(check_for_new_objects)
+                    || conditional_expr.alternate.check_for_state_change(check_for_new_objects)
+            }
+            Self::SequenceExpression(sequence_expr) => sequence_expr
+                .expressions
+                .iter()
+                .any(|expr| expr.check_for_state_change(check_for_new_objects)),
+            Self::CallExpression(call_expr) => {
+                call_expr.check_for_state_change(check_for_new_objects)
+            }
+            Self::NewExpression(new_expr) => new_expr.check_for_state_change(check_for_new_objects),
             Self::ObjectExpression(object_expr) => {
                 if check_for_new_objects {
                     return true;
@@ -193,8 +211,51 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for PropertyKey<'a> {
     }
 }
 
+impl<'a, 'b> CheckForStateChange<'a, 'b> for Argument<'a> {
+    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+        match self {
+            Self::SpreadElement(element) => element.check_for_state_change(check_for_new_objects),
+            Self::Expression(expr) => expr.check_for_state_change(check_for_new_objects),
+        }
+    }
+}
+
+/// port from [closure-compiler](https://github.com/google/closure-compiler/blob/f3ce5ed8b630428e311fe9aa2e20d36560d975e2/src/com/google/javascript/jscomp/AstAnalyzer.java#L454)
+/// Pure calls, see [CallExpression::pure], and calls of function expressions annotated with
+/// `/*@__NO_SIDE_EFFECTS__*/` only have the side effects of their arguments.
+impl<'a, 'b> CheckForStateChange<'a, 'b> for CallExpression<'a> {
+    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+        if !self.pure && !is_pure_callee(&self.callee) {
+            return true;
+        }
+        self.arguments.iter().any(|argument| argument.check_for_state_change(check_for_new_objects))
+    }
+}
+
+/// port from [closure-compiler](https://github.com/google/closure-compiler/blob/f3ce5ed8b630428e311fe9aa2e20d36560d975e2/src/com/google/javascript/jscomp/AstAnalyzer.java#L399)
+/// Pure `new` expressions, see [NewExpression::pure], only have the side effects of their arguments.
+impl<'a, 'b> CheckForStateChange<'a, 'b> for NewExpression<'a> {
+    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+        if check_for_new_objects || !self.pure {
+            return true;
+        }
+        self.arguments.iter().any(|argument| argument.check_for_state_change(check_for_new_objects))
+    }
+}
+
 impl<'a, 'b> MayHaveSideEffects<'a, 'b> for Expression<'a> {}
 impl<'a, 'b> MayHaveSideEffects<'a, 'b> for UnaryExpression<'a> {}
+impl<'a, 'b> MayHaveSideEffects<'a, 'b> for CallExpression<'a> {}
+impl<'a, 'b> MayHaveSideEffects<'a, 'b> for NewExpression<'a> {}
+
+/// The callee is a function expression annotated with `/*@__NO_SIDE_EFFECTS__*/`
+fn is_pure_callee(callee: &Expression) -> bool {
+    match callee {
+        Expression::FunctionExpression(func) => func.pure,
+        Expression::ArrowExpression(arrow) => arrow.pure,
+        _ => false,
+    }
+}
 
 /// A "simple" operator is one whose children are expressions, has no direct side-effects.
 fn is_simple_unary_operator(operator: UnaryOperator) -> bool {
@@ -330,18 +391,25 @@ pub fn get_boolean_value(expr: &Expression) -> Option<bool> {
             }
         }
         Expression::LogicalExpression(logical_expr) => {
-            let predict = |expr: &&Expression| get_boolean_value(expr) == Some(true);
+            let left = get_boolean_value(&logical_expr.left);
+            let right = get_boolean_value(&logical_expr.right);
             match logical_expr.operator {
                 // true && true -> true
-                // true && false -> false
-                LogicalOperator::And => {
-                    Some([&logical_expr.left, &logical_expr.right].iter().all(predict))
-                }
-                // true || false -> true
+                // false && x -> false
+                // x && false -> false
+                LogicalOperator::And => match (left, right) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                },
+                // true || x -> true
+                // x || true -> true
                 // false || false -> false
-                LogicalOperator::Or => {
-                    Some([&logical_expr.left, &logical_expr.right].iter().any(predict))
-                }
+                LogicalOperator::Or => match (left, right) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                },
                 LogicalOperator::Coalesce => None,
             }
         }
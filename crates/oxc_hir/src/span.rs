@@ -1,6 +1,6 @@
 use oxc_span::{GetSpan, Span};
 
-use crate::hir::{Expression, MemberExpression};
+use crate::hir::{Declaration, Expression, MemberExpression, ModuleDeclaration, Statement};
 
 impl<'a> GetSpan for Expression<'a> {
     fn span(&self) -> Span {
@@ -52,3 +52,51 @@ impl<'a> GetSpan for MemberExpression<'a> {
         }
     }
 }
+
+impl<'a> GetSpan for Statement<'a> {
+    fn span(&self) -> Span {
+        match self {
+            Self::BlockStatement(stmt) => stmt.span,
+            Self::BreakStatement(stmt) => stmt.span,
+            Self::ContinueStatement(stmt) => stmt.span,
+            Self::DebuggerStatement(stmt) => stmt.span,
+            Self::DoWhileStatement(stmt) => stmt.span,
+            Self::ExpressionStatement(stmt) => stmt.span,
+            Self::ForInStatement(stmt) => stmt.span,
+            Self::ForOfStatement(stmt) => stmt.span,
+            Self::ForStatement(stmt) => stmt.span,
+            Self::IfStatement(stmt) => stmt.span,
+            Self::LabeledStatement(stmt) => stmt.span,
+            Self::ReturnStatement(stmt) => stmt.span,
+            Self::SwitchStatement(stmt) => stmt.span,
+            Self::ThrowStatement(stmt) => stmt.span,
+            Self::TryStatement(stmt) => stmt.span,
+            Self::WhileStatement(stmt) => stmt.span,
+            Self::WithStatement(stmt) => stmt.span,
+            Self::ModuleDeclaration(decl) => decl.span(),
+            Self::Declaration(decl) => decl.span(),
+        }
+    }
+}
+
+impl<'a> GetSpan for ModuleDeclaration<'a> {
+    fn span(&self) -> Span {
+        match self {
+            Self::ImportDeclaration(decl) => decl.span,
+            Self::ExportAllDeclaration(decl) => decl.span,
+            Self::ExportDefaultDeclaration(decl) => decl.span,
+            Self::ExportNamedDeclaration(decl) => decl.span,
+        }
+    }
+}
+
+impl<'a> GetSpan for Declaration<'a> {
+    fn span(&self) -> Span {
+        match self {
+            Self::VariableDeclaration(decl) => decl.span,
+            Self::FunctionDeclaration(decl) => decl.span,
+            Self::ClassDeclaration(decl) => decl.span,
+            Self::TSEnumDeclaration(decl) => decl.span,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Failed to load config {0:?}")]
+#[diagnostic(help("{1}"))]
+pub struct FailedToLoadConfigError(pub PathBuf, pub String);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Unknown rule {0:?} in config {1:?}")]
+#[diagnostic(severity(warning), help("Run `oxc lint --rules` to list the supported rules"))]
+pub struct UnknownRuleError(pub String, pub PathBuf);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid severity of rule {0:?} in config {1:?}")]
+#[diagnostic(
+    severity(warning),
+    help("The severity is one of \"off\", \"warn\", \"error\" or 0, 1, 2")
+)]
+pub struct InvalidRuleSeverityError(pub String, pub PathBuf);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Unsupported extends {0:?} in config {1:?}")]
+#[diagnostic(
+    severity(warning),
+    help(
+        "Only \"eslint:recommended\", \"eslint:all\" and relative paths to config files are supported"
+    )
+)]
+pub struct UnsupportedExtendsError(pub String, pub PathBuf);
@@ -0,0 +1,723 @@
+//! ESLint style config files, e.g. `.eslintrc.json`
+//!
+//! * `extends`: `eslint:recommended`, `eslint:all` and relative paths to other config files
+//! * `rules`: `"off"` / `"warn"` / `"error"` or `0` / `1` / `2`, followed by the rule options
+//! * `overrides`: `rules`, `env` and `globals` for the files matching the `files` globs
+//! * `env`, `globals`, `ignorePatterns` and `root`
+//!
+//! A [LintConfig] is a single config file, finding the config files of a directory
+//! is left to the caller, see [LintConfig::is_root].
+
+mod errors;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use oxc_diagnostics::Error;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub use self::errors::{
+    FailedToLoadConfigError, InvalidRuleSeverityError, UnknownRuleError, UnsupportedExtendsError,
+};
+use crate::{RuleCategory, RuleEnum, RULES};
+
+/// Severity of a rule
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AllowWarnDeny {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl AllowWarnDeny {
+    pub fn is_enabled(self) -> bool {
+        self != Self::Allow
+    }
+
+    /// `"off"` / `"warn"` / `"error"` or `0` / `1` / `2`
+    fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => match s.as_str() {
+                "off" => Some(Self::Allow),
+                "warn" => Some(Self::Warn),
+                "error" => Some(Self::Deny),
+                _ => None,
+            },
+            Value::Number(n) => match n.as_u64()? {
+                0 => Some(Self::Allow),
+                1 => Some(Self::Warn),
+                2 => Some(Self::Deny),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for AllowWarnDeny {
+    fn from(s: &str) -> Self {
+        match s {
+            "allow" => Self::Allow,
+            "warn" => Self::Warn,
+            "deny" => Self::Deny,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// `"a"` or `["a", "b"]`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for OneOrMany {
+    fn default() -> Self {
+        Self::Many(vec![])
+    }
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(s) => vec![s],
+            Self::Many(v) => v,
+        }
+    }
+}
+
+/// The config file as written
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ConfigFile {
+    root: bool,
+    extends: OneOrMany,
+    rules: serde_json::Map<String, Value>,
+    overrides: Vec<OverrideFile>,
+    env: FxHashMap<String, bool>,
+    globals: FxHashMap<String, Value>,
+    ignore_patterns: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct OverrideFile {
+    files: OneOrMany,
+    excluded_files: OneOrMany,
+    rules: serde_json::Map<String, Value>,
+    env: FxHashMap<String, bool>,
+    globals: FxHashMap<String, Value>,
+}
+
+#[derive(Debug, Clone)]
+struct RuleSetting {
+    severity: AllowWarnDeny,
+    rule: RuleEnum,
+    /// The options of a previous setting are kept when only the severity is given
+    has_options: bool,
+}
+
+#[derive(Debug, Default)]
+struct Settings {
+    /// Applied in order, a later setting of a rule replaces the previous ones
+    rules: Vec<RuleSetting>,
+    env: FxHashMap<String, bool>,
+    /// Global variable name -> writable, `None` turns the global off
+    globals: FxHashMap<String, Option<bool>>,
+}
+
+impl Settings {
+    fn from_category(category: Option<RuleCategory>) -> Self {
+        let rules = RULES
+            .iter()
+            .filter(|rule| category.map_or(true, |category| rule.category() == category))
+            .map(|rule| RuleSetting {
+                severity: AllowWarnDeny::Deny,
+                rule: rule.clone(),
+                has_options: false,
+            })
+            .collect();
+        Self { rules, ..Self::default() }
+    }
+
+    fn parse(
+        rules: serde_json::Map<String, Value>,
+        env: FxHashMap<String, bool>,
+        globals: FxHashMap<String, Value>,
+        path: &Path,
+        errors: &mut Vec<Error>,
+    ) -> Self {
+        let mut settings = Self { env, ..Self::default() };
+
+        for (name, value) in rules {
+            let Some(rule) = find_rule(&name) else {
+                errors.push(UnknownRuleError(name, path.to_path_buf()).into());
+                continue;
+            };
+            // `"error"` or `["error", { ...options }]`
+            let (severity, options) = match &value {
+                Value::Array(array) => (array.first(), array.get(1..).unwrap_or_default()),
+                value => (Some(value), [].as_slice()),
+            };
+            let Some(severity) = severity.and_then(AllowWarnDeny::from_json) else {
+                errors.push(InvalidRuleSeverityError(name, path.to_path_buf()).into());
+                continue;
+            };
+            let options = (!options.is_empty()).then(|| Value::Array(options.to_vec()));
+            settings.rules.push(RuleSetting {
+                severity,
+                has_options: options.is_some(),
+                rule: rule.read_json(options),
+            });
+        }
+
+        settings.globals = globals
+            .into_iter()
+            .map(|(name, value)| {
+                let writable = match value {
+                    Value::String(s) if s == "off" => None,
+                    Value::String(s) => Some(s == "writable" || s == "writeable"),
+                    value => Some(value == Value::Bool(true)),
+                };
+                (name, writable)
+            })
+            .collect();
+
+        settings
+    }
+
+    fn extend(&mut self, other: Self) {
+        self.rules.extend(other.rules);
+        self.env.extend(other.env);
+        self.globals.extend(other.globals);
+    }
+}
+
+/// `rule-name`, `plugin/rule-name` or `@typescript-eslint/rule-name`
+fn find_rule(name: &str) -> Option<&'static RuleEnum> {
+    let (plugin_name, name) = match name.split_once('/') {
+        Some(("@typescript-eslint", name)) => (Some("typescript"), name),
+        Some((plugin_name, name)) => (Some(plugin_name), name),
+        None => (None, name),
+    };
+    RULES.iter().find(|rule| {
+        rule.name() == name
+            && plugin_name.map_or(true, |plugin_name| plugin_name == rule.plugin_name())
+    })
+}
+
+/// Glob patterns relative to the directory of the config file declaring them.
+/// Patterns without a slash match the file name, e.g. `*.test.js` matches `src/a.test.js`.
+#[derive(Debug)]
+struct Patterns {
+    dir: PathBuf,
+    set: GlobSet,
+}
+
+impl Patterns {
+    /// `match_dirs`: a pattern also matches all the files in the directories it matches,
+    /// e.g. `dist` matches `dist/a.js`
+    fn new(patterns: &[String], match_dirs: bool, path: &Path) -> Result<Self, Error> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            let pattern = match pattern.strip_prefix('/') {
+                Some(pattern) => pattern.to_string(),
+                None if pattern.contains('/') => pattern.to_string(),
+                None => format!("**/{pattern}"),
+            };
+            let globs =
+                if match_dirs { vec![format!("{pattern}/**"), pattern] } else { vec![pattern] };
+            for glob in globs {
+                let glob =
+                    GlobBuilder::new(&glob).literal_separator(true).build().map_err(|e| {
+                        Error::new(FailedToLoadConfigError(path.to_path_buf(), e.to_string()))
+                    })?;
+                builder.add(glob);
+            }
+        }
+        let set = builder
+            .build()
+            .map_err(|e| Error::new(FailedToLoadConfigError(path.to_path_buf(), e.to_string())))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        Ok(Self { dir, set })
+    }
+
+    fn is_match(&self, path: &Path) -> bool {
+        path.strip_prefix(&self.dir).is_ok_and(|path| self.set.is_match(path))
+    }
+}
+
+#[derive(Debug)]
+struct Override {
+    files: Patterns,
+    excluded_files: Patterns,
+    settings: Settings,
+}
+
+/// The rules, `env` and `globals` of a single file,
+/// built by applying the config files from the root directory to the nearest one
+#[derive(Debug, Default, Clone)]
+pub struct FileConfig {
+    /// Rule name -> severity and the configured rule,
+    /// the rule reports with its own severity when the severity is `None`
+    pub rules: FxHashMap<&'static str, (Option<AllowWarnDeny>, RuleEnum)>,
+    pub environment: Environment,
+}
+
+/// `env` and `globals` of a file
+#[derive(Debug, Default, Clone)]
+pub struct Environment {
+    /// Environment name, e.g. `browser` -> enabled
+    pub env: FxHashMap<String, bool>,
+    /// Global variable name -> writable
+    pub globals: FxHashMap<String, bool>,
+}
+
+impl FileConfig {
+    /// Enable `rules` with their default options and their own severity
+    pub fn from_rules<I: IntoIterator<Item = RuleEnum>>(rules: I) -> Self {
+        let rules = rules.into_iter().map(|rule| (rule.name(), (None, rule)));
+        Self { rules: rules.collect(), environment: Environment::default() }
+    }
+
+    /// The enabled rules and their severity, sorted by name for a stable diagnostics output ordering
+    pub fn enabled_rules(&self) -> Vec<(RuleEnum, Option<AllowWarnDeny>)> {
+        let mut rules = self
+            .rules
+            .values()
+            .filter(|(severity, _)| severity.map_or(true, AllowWarnDeny::is_enabled))
+            .map(|(severity, rule)| (rule.clone(), *severity))
+            .collect::<Vec<_>>();
+        rules.sort_unstable_by_key(|(rule, _)| rule.name());
+        rules
+    }
+
+    fn apply(&mut self, settings: &Settings) {
+        for setting in &settings.rules {
+            let name = setting.rule.name();
+            if !setting.has_options && let Some((severity, _)) = self.rules.get_mut(name) {
+                *severity = Some(setting.severity);
+            } else {
+                self.rules.insert(name, (Some(setting.severity), setting.rule.clone()));
+            }
+        }
+        let environment = &mut self.environment;
+        environment.env.extend(settings.env.iter().map(|(name, enabled)| (name.clone(), *enabled)));
+        for (name, writable) in &settings.globals {
+            match writable {
+                Some(writable) => environment.globals.insert(name.clone(), *writable),
+                None => environment.globals.remove(name),
+            };
+        }
+    }
+}
+
+/// A config file with its `extends` resolved
+#[derive(Debug)]
+pub struct LintConfig {
+    root: bool,
+    ignore_patterns: Vec<Patterns>,
+    /// The settings of the extended config files come first
+    settings: Settings,
+    overrides: Vec<Override>,
+}
+
+impl LintConfig {
+    /// Returns the config along with the diagnostics of the settings which are skipped,
+    /// e.g. unknown rules.
+    ///
+    /// # Errors
+    ///
+    /// * When the config file or one of the config files it extends can not be read or parsed.
+    pub fn from_file(path: &Path) -> Result<(Self, Vec<Error>), Error> {
+        let mut errors = vec![];
+        let config = Self::load(path, &mut vec![], &mut errors)?;
+        Ok((config, errors))
+    }
+
+    /// `path` is where the config would be read from,
+    /// relative `extends` and glob patterns are resolved from its directory.
+    ///
+    /// # Errors
+    ///
+    /// * When `json` or one of the config files it extends can not be parsed.
+    pub fn from_json_str(json: &str, path: &Path) -> Result<(Self, Vec<Error>), Error> {
+        let mut errors = vec![];
+        let config = Self::parse(json, path, &mut vec![], &mut errors)?;
+        Ok((config, errors))
+    }
+
+    /// The config files of the parent directories do not apply
+    pub fn is_root(&self) -> bool {
+        self.root
+    }
+
+    /// Whether `path` matches the `ignorePatterns`
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.ignore_patterns.iter().any(|patterns| patterns.is_match(path))
+    }
+
+    /// Apply the settings for `path` on top of `file_config`
+    pub fn apply(&self, path: &Path, file_config: &mut FileConfig) {
+        file_config.apply(&self.settings);
+        for r#override in &self.overrides {
+            if r#override.files.is_match(path) && !r#override.excluded_files.is_match(path) {
+                file_config.apply(&r#override.settings);
+            }
+        }
+    }
+
+    /// `stack` holds the config files being loaded, for detecting circular `extends`
+    fn load(path: &Path, stack: &mut Vec<PathBuf>, errors: &mut Vec<Error>) -> Result<Self, Error> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| Error::new(FailedToLoadConfigError(path.to_path_buf(), e.to_string())))?;
+        Self::parse(&json, path, stack, errors)
+    }
+
+    fn parse(
+        json: &str,
+        path: &Path,
+        stack: &mut Vec<PathBuf>,
+        errors: &mut Vec<Error>,
+    ) -> Result<Self, Error> {
+        // The same file may be reached by different paths, e.g. `./a/../.eslintrc.json`
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if stack.contains(&canonical_path) {
+            let message = "The config file extends itself".to_string();
+            return Err(FailedToLoadConfigError(path.to_path_buf(), message).into());
+        }
+        let file: ConfigFile = serde_json::from_str(&strip_json_comments(json))
+            .map_err(|e| Error::new(FailedToLoadConfigError(path.to_path_buf(), e.to_string())))?;
+
+        let mut config = Self {
+            root: file.root,
+            ignore_patterns: vec![],
+            settings: Settings::default(),
+            overrides: vec![],
+        };
+
+        stack.push(canonical_path);
+        for extends in file.extends.into_vec() {
+            match extends.as_str() {
+                "eslint:recommended" => {
+                    config
+                        .settings
+                        .extend(Settings::from_category(Some(RuleCategory::Correctness)));
+                }
+                "eslint:all" => config.settings.extend(Settings::from_category(None)),
+                _ if extends.starts_with('.') => {
+                    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                    let base = Self::load(&dir.join(&extends), stack, errors)?;
+                    config.ignore_patterns.extend(base.ignore_patterns);
+                    config.settings.extend(base.settings);
+                    config.overrides.extend(base.overrides);
+                }
+                _ => errors.push(UnsupportedExtendsError(extends, path.to_path_buf()).into()),
+            }
+        }
+        stack.pop();
+
+        if !file.ignore_patterns.is_empty() {
+            config.ignore_patterns.push(Patterns::new(&file.ignore_patterns, true, path)?);
+        }
+        config.settings.extend(Settings::parse(file.rules, file.env, file.globals, path, errors));
+        for r#override in file.overrides {
+            let settings =
+                Settings::parse(r#override.rules, r#override.env, r#override.globals, path, errors);
+            config.overrides.push(Override {
+                files: Patterns::new(&r#override.files.into_vec(), false, path)?,
+                excluded_files: Patterns::new(&r#override.excluded_files.into_vec(), false, path)?,
+                settings,
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+/// Replace the `//` and `/* */` comments allowed in `.eslintrc.json`,
+/// line breaks are kept so the line numbers of parse errors are still right
+fn strip_json_comments(json: &str) -> String {
+    let mut output = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|c| *c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        output.push(c);
+                    }
+                    prev = c;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                output.push(c);
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use super::{AllowWarnDeny, FileConfig, LintConfig};
+
+    fn parse(json: &str) -> LintConfig {
+        let (config, errors) =
+            LintConfig::from_json_str(json, Path::new(".eslintrc.json")).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        config
+    }
+
+    fn resolve(config: &LintConfig, path: &str) -> FileConfig {
+        let mut file_config = FileConfig::default();
+        config.apply(Path::new(path), &mut file_config);
+        file_config
+    }
+
+    fn severity(file_config: &FileConfig, name: &str) -> Option<AllowWarnDeny> {
+        file_config.rules.get(name).and_then(|(severity, _)| *severity)
+    }
+
+    #[test]
+    fn severity_levels() {
+        let config = parse(
+            r#"{
+                // comments are allowed
+                "rules": {
+                    "no-debugger": "off",
+                    "no-empty": "warn",
+                    "no-eval": "error",
+                    "no-bitwise": 0,
+                    "no-array-constructor": 1,
+                    /* block comment */
+                    "no-caller": [2]
+                },
+                "globals": { "//": "readonly", "/*": "readonly" }
+            }"#,
+        );
+        let file_config = resolve(&config, "a.js");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Allow));
+        assert_eq!(severity(&file_config, "no-empty"), Some(AllowWarnDeny::Warn));
+        assert_eq!(severity(&file_config, "no-eval"), Some(AllowWarnDeny::Deny));
+        assert_eq!(severity(&file_config, "no-bitwise"), Some(AllowWarnDeny::Allow));
+        assert_eq!(severity(&file_config, "no-array-constructor"), Some(AllowWarnDeny::Warn));
+        assert_eq!(severity(&file_config, "no-caller"), Some(AllowWarnDeny::Deny));
+        let names =
+            file_config.enabled_rules().iter().map(|(rule, _)| rule.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["no-array-constructor", "no-caller", "no-empty", "no-eval"]);
+        // Comments inside strings are kept
+        assert_eq!(file_config.environment.globals.len(), 2);
+    }
+
+    #[test]
+    fn invalid_config() {
+        let path = Path::new(".eslintrc.json");
+        let (_, errors) = LintConfig::from_json_str(
+            r#"{ "rules": { "no-foo": "error", "react/jsx-key": "error", "no-eval": "on", "deepscan/no-eval": 2 } }"#,
+            path,
+        )
+        .unwrap();
+        let mut errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        errors.sort_unstable();
+        assert_eq!(
+            errors,
+            [
+                "Invalid severity of rule \"no-eval\" in config \".eslintrc.json\"",
+                "Unknown rule \"deepscan/no-eval\" in config \".eslintrc.json\"",
+                "Unknown rule \"no-foo\" in config \".eslintrc.json\"",
+                "Unknown rule \"react/jsx-key\" in config \".eslintrc.json\"",
+            ]
+        );
+        assert!(LintConfig::from_json_str(r#"{ "rules": [] }"#, path).is_err());
+        assert!(LintConfig::from_json_str(r#"{ "overrides": [{ "files": "[" }] }"#, path).is_err());
+    }
+
+    #[test]
+    fn plugin_names() {
+        let config = parse(
+            r#"{ "rules": { "eslint/no-debugger": "warn", "deepscan/missing-throw": "error", "@typescript-eslint/isolated-declaration": "error" } }"#,
+        );
+        let file_config = resolve(&config, "a.ts");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Warn));
+        assert_eq!(severity(&file_config, "missing-throw"), Some(AllowWarnDeny::Deny));
+        assert_eq!(severity(&file_config, "isolated-declaration"), Some(AllowWarnDeny::Deny));
+    }
+
+    #[test]
+    fn extends() {
+        let config =
+            parse(r#"{ "extends": "eslint:recommended", "rules": { "no-debugger": "warn" } }"#);
+        let file_config = resolve(&config, "a.js");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Warn));
+        assert_eq!(severity(&file_config, "for-direction"), Some(AllowWarnDeny::Deny));
+        assert_eq!(severity(&file_config, "no-bitwise"), None);
+
+        let config = parse(r#"{ "extends": ["eslint:all"] }"#);
+        assert!(severity(&resolve(&config, "a.js"), "no-bitwise").is_some());
+
+        let (_, errors) = LintConfig::from_json_str(
+            r#"{ "extends": "plugin:react/recommended" }"#,
+            Path::new(".eslintrc.json"),
+        )
+        .unwrap();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn extends_file() {
+        // Unique per run so concurrent runs do not share files
+        let dir =
+            std::env::temp_dir().join(format!("oxc_linter_extends_file_{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(
+            dir.join("base.json"),
+            r#"{ "rules": { "no-eval": "warn", "no-bitwise": ["error", { "allow": ["~"] }] }, "ignorePatterns": ["dist"] }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("loop.json"), r#"{ "extends": "./loop.json" }"#).unwrap();
+        fs::write(dir.join("loop2.json"), r#"{ "extends": "./sub/../loop2.json" }"#).unwrap();
+        fs::write(
+            dir.join(".eslintrc.json"),
+            r#"{ "extends": "./base.json", "rules": { "no-bitwise": "warn", "no-eval": "off" } }"#,
+        )
+        .unwrap();
+
+        let (config, errors) = LintConfig::from_file(&dir.join(".eslintrc.json")).unwrap();
+        assert!(errors.is_empty());
+        let file_config = resolve(&config, dir.join("a.js").to_str().unwrap());
+        assert_eq!(severity(&file_config, "no-eval"), Some(AllowWarnDeny::Allow));
+        let (severity, rule) = &file_config.rules["no-bitwise"];
+        assert_eq!(*severity, Some(AllowWarnDeny::Warn));
+        // Only the severity changes, the options of the extended config are kept
+        assert!(format!("{rule:?}").contains('~'));
+        assert!(config.is_ignored(&dir.join("dist/a.js")));
+        assert!(!config.is_ignored(&dir.join("a.js")));
+
+        assert!(LintConfig::from_file(&dir.join("loop.json")).is_err());
+        assert!(LintConfig::from_file(&dir.join("loop2.json")).is_err());
+        assert!(LintConfig::from_file(&dir.join("missing.json")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn env_and_globals() {
+        let config = parse(
+            r#"{
+                "env": { "browser": true, "node": false },
+                "globals": {
+                    "a": "readonly",
+                    "b": "readable",
+                    "c": false,
+                    "d": "writable",
+                    "e": "writeable",
+                    "f": true,
+                    "g": "off"
+                }
+            }"#,
+        );
+        let mut file_config = resolve(&config, "a.js");
+        let environment = &file_config.environment;
+        assert_eq!(environment.env.get("browser"), Some(&true));
+        assert_eq!(environment.env.get("node"), Some(&false));
+        for name in ["a", "b", "c"] {
+            assert_eq!(environment.globals.get(name), Some(&false), "{name}");
+        }
+        for name in ["d", "e", "f"] {
+            assert_eq!(environment.globals.get(name), Some(&true), "{name}");
+        }
+        assert_eq!(environment.globals.get("g"), None);
+
+        // A later config replaces the settings of the same names and turns globals off
+        let config = parse(
+            r#"{
+                "env": { "browser": false },
+                "globals": { "a": "writable", "d": "off" }
+            }"#,
+        );
+        config.apply(Path::new("a.js"), &mut file_config);
+        let environment = &file_config.environment;
+        assert_eq!(environment.env.get("browser"), Some(&false));
+        assert_eq!(environment.env.get("node"), Some(&false));
+        assert_eq!(environment.globals.get("a"), Some(&true));
+        assert_eq!(environment.globals.get("d"), None);
+        assert_eq!(environment.globals.get("f"), Some(&true));
+    }
+
+    #[test]
+    fn overrides() {
+        let config = parse(
+            r#"{
+                "root": true,
+                "rules": { "no-debugger": "error" },
+                "env": { "browser": true },
+                "globals": { "foo": "readonly", "bar": "writable" },
+                "overrides": [
+                    {
+                        "files": ["*.test.js"],
+                        "excludedFiles": "src/skip/**",
+                        "rules": { "no-debugger": "off" },
+                        "env": { "jest": true },
+                        "globals": { "foo": "off", "baz": true }
+                    }
+                ]
+            }"#,
+        );
+        assert!(config.is_root());
+
+        let file_config = resolve(&config, "src/a.js");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Deny));
+        assert_eq!(file_config.environment.env.get("jest"), None);
+        assert_eq!(file_config.environment.globals.get("foo"), Some(&false));
+        assert_eq!(file_config.environment.globals.get("bar"), Some(&true));
+
+        let file_config = resolve(&config, "src/a.test.js");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Allow));
+        assert_eq!(file_config.environment.env.get("browser"), Some(&true));
+        assert_eq!(file_config.environment.env.get("jest"), Some(&true));
+        assert_eq!(file_config.environment.globals.get("foo"), None);
+        assert_eq!(file_config.environment.globals.get("baz"), Some(&true));
+
+        let file_config = resolve(&config, "src/skip/a.test.js");
+        assert_eq!(severity(&file_config, "no-debugger"), Some(AllowWarnDeny::Deny));
+    }
+
+    #[test]
+    fn ignore_patterns() {
+        let config = parse(r#"{ "ignorePatterns": ["build", "/lib/*.js", "**/*.gen.js"] }"#);
+        assert!(config.is_ignored(Path::new("build/a.js")));
+        assert!(config.is_ignored(Path::new("src/build/a.js")));
+        assert!(config.is_ignored(Path::new("lib/a.js")));
+        assert!(!config.is_ignored(Path::new("src/lib/a.js")));
+        assert!(config.is_ignored(Path::new("src/a.gen.js")));
+        assert!(!config.is_ignored(Path::new("src/a.js")));
+    }
+}
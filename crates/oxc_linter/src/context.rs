@@ -1,13 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
-use oxc_diagnostics::Error;
+use oxc_diagnostics::{Error, Severity};
 use oxc_formatter::{Formatter, FormatterOptions};
 use oxc_semantic::{AstNodes, JSDocComment, ScopeTree, Semantic, SymbolTable};
 use oxc_span::SourceType;
+use rustc_hash::FxHashMap;
 
 use crate::{
+    config::Environment,
     disable_directives::{DisableDirectives, DisableDirectivesBuilder},
-    fixer::{Fix, Message},
+    fixer::{CompositeFix, FixKind, Message},
     AstNode,
 };
 
@@ -21,7 +23,14 @@ pub struct LintContext<'a> {
     /// Whether or not to apply code fixes during linting.
     fix: bool,
 
+    /// `env` and `globals` from the config files
+    environment: Arc<Environment>,
+
     current_rule_name: &'static str,
+
+    /// The severity configured for the current rule,
+    /// the diagnostics keep their own severity when it is `None`
+    current_severity: Option<Severity>,
 }
 
 impl<'a> LintContext<'a> {
@@ -33,10 +42,18 @@ impl<'a> LintContext<'a> {
             diagnostics: RefCell::new(vec![]),
             disable_directives,
             fix,
+            environment: Arc::default(),
             current_rule_name: "",
+            current_severity: None,
         }
     }
 
+    #[must_use]
+    pub fn with_environment(mut self, environment: Arc<Environment>) -> Self {
+        self.environment = environment;
+        self
+    }
+
     pub fn semantic(&self) -> &Semantic<'a> {
         &self.semantic
     }
@@ -49,10 +66,24 @@ impl<'a> LintContext<'a> {
         self.semantic().source_type()
     }
 
+    /// Whether the environment, e.g. `browser`, is enabled in the config files
+    pub fn env_contains(&self, name: &str) -> bool {
+        self.environment.env.get(name).copied().unwrap_or_default()
+    }
+
+    /// Global variables declared in the config files, name -> writable
+    pub fn globals(&self) -> &FxHashMap<String, bool> {
+        &self.environment.globals
+    }
+
     pub fn with_rule_name(&mut self, name: &'static str) {
         self.current_rule_name = name;
     }
 
+    pub fn with_severity(&mut self, severity: Option<Severity>) {
+        self.current_severity = severity;
+    }
+
     /* Diagnostics */
 
     pub fn into_message(self) -> Vec<Message<'a>> {
@@ -60,6 +91,10 @@ impl<'a> LintContext<'a> {
     }
 
     fn add_diagnostic(&self, message: Message<'a>) {
+        let mut message = message.with_rule_name(self.current_rule_name);
+        if let Some(severity) = self.current_severity {
+            message = message.with_severity(severity);
+        }
         if !self.disable_directives.contains(self.current_rule_name, message.start()) {
             self.diagnostics.borrow_mut().push(message);
         }
@@ -69,18 +104,31 @@ impl<'a> LintContext<'a> {
         self.add_diagnostic(Message::new(diagnostic.into(), None));
     }
 
-    pub fn diagnostic_with_fix<T, F>(&self, diagnostic: T, fix: F)
+    /// The fix is only computed with `--fix`, it can be a single [`crate::fixer::Fix`] or several edits
+    pub fn diagnostic_with_fix<T, C, F>(&self, diagnostic: T, fix: F)
     where
         T: Into<Error>,
-        F: FnOnce() -> Fix<'a>,
+        C: Into<CompositeFix<'a>>,
+        F: FnOnce() -> C,
     {
         if self.fix {
-            self.add_diagnostic(Message::new(diagnostic.into(), Some(fix())));
+            self.add_diagnostic(Message::new(diagnostic.into(), Some(fix().into())));
         } else {
-            self.diagnostic(diagnostic);
+            self.add_diagnostic(Message::new(diagnostic.into(), None).with_fixable(true));
         }
     }
 
+    /// The suggestion is reported along with the diagnostic, but never applied by `--fix`
+    pub fn diagnostic_with_suggestion<T, C, F>(&self, diagnostic: T, suggestion: F)
+    where
+        T: Into<Error>,
+        C: Into<CompositeFix<'a>>,
+        F: FnOnce() -> C,
+    {
+        let suggestion = suggestion().into().with_kind(FixKind::Suggestion);
+        self.add_diagnostic(Message::new(diagnostic.into(), Some(suggestion)));
+    }
+
     pub fn nodes(&self) -> &AstNodes<'a> {
         self.semantic().nodes()
     }
@@ -1,6 +1,9 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt};
 
-use oxc_diagnostics::Error;
+use oxc_diagnostics::{
+    miette::{Diagnostic, LabeledSpan, SourceCode},
+    Error, Severity,
+};
 use oxc_span::Span;
 
 #[derive(Debug, Default)]
@@ -19,6 +22,80 @@ impl<'a> Fix<'a> {
     }
 }
 
+/// Fixes are applied by `--fix`, suggestions are only reported
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum FixKind {
+    #[default]
+    Fix,
+    Suggestion,
+}
+
+/// A fix consisting of one or more edits, which are applied together,
+/// e.g. adding an import and rewriting a call.
+#[derive(Debug, Default)]
+pub struct CompositeFix<'a> {
+    pub edits: Vec<Fix<'a>>,
+    pub kind: FixKind,
+}
+
+impl<'a> CompositeFix<'a> {
+    pub fn new(edits: Vec<Fix<'a>>) -> Self {
+        Self { edits, kind: FixKind::Fix }
+    }
+
+    #[must_use]
+    pub fn with_kind(mut self, kind: FixKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn is_suggestion(&self) -> bool {
+        self.kind == FixKind::Suggestion
+    }
+
+    /// The span from the start of the first edit to the end of the last one
+    pub fn span(&self) -> Span {
+        let start = self.edits.iter().map(|edit| edit.span.start).min().unwrap_or_default();
+        let end = self.edits.iter().map(|edit| edit.span.end).max().unwrap_or_default();
+        Span::new(start, end)
+    }
+
+    /// Merge the edits into a single edit of `source_text`, spanning from the first edit to the last one.
+    /// Returns `None` when there are no edits, or the edits overlap or have a reversed range.
+    pub fn merge(&self, source_text: &str) -> Option<Fix<'a>> {
+        let mut edits = self.edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|edit| edit.span);
+        if let [edit] = edits.as_slice() {
+            return (edit.span.start <= edit.span.end)
+                .then(|| Fix::new(edit.content.clone(), edit.span));
+        }
+        let start = edits.first()?.span.start;
+        let mut end = start;
+        let mut content = String::new();
+        for edit in edits {
+            if edit.span.start > edit.span.end || edit.span.start < end {
+                return None;
+            }
+            content.push_str(source_text.get(end as usize..edit.span.start as usize)?);
+            content.push_str(&edit.content);
+            end = edit.span.end;
+        }
+        Some(Fix::new(content, Span::new(start, end)))
+    }
+}
+
+impl<'a> From<Fix<'a>> for CompositeFix<'a> {
+    fn from(fix: Fix<'a>) -> Self {
+        Self::new(vec![fix])
+    }
+}
+
+impl<'a> From<Vec<Fix<'a>>> for CompositeFix<'a> {
+    fn from(edits: Vec<Fix<'a>>) -> Self {
+        Self::new(edits)
+    }
+}
+
 #[derive(Debug)]
 pub struct FixResult<'a> {
     pub fixed: bool,
@@ -31,13 +108,17 @@ pub struct Message<'a> {
     pub error: Error,
     start: u32,
     end: u32,
-    fix: Option<Fix<'a>>,
+    fix: Option<CompositeFix<'a>>,
     fixed: bool,
+    /// The rule reporting the diagnostic
+    rule_name: Option<&'static str>,
+    /// The rule has a fix for the diagnostic, it is only computed with `--fix`
+    fixable: bool,
 }
 
 impl<'a> Message<'a> {
     #[allow(clippy::cast_possible_truncation)] // for `as u32`
-    pub fn new(error: Error, fix: Option<Fix<'a>>) -> Self {
+    pub fn new(error: Error, fix: Option<CompositeFix<'a>>) -> Self {
         let labels = error.labels().map_or(vec![], Iterator::collect);
         let start =
             labels.iter().min_by_key(|span| span.offset()).map_or(0, |span| span.offset() as u32);
@@ -45,7 +126,41 @@ impl<'a> Message<'a> {
             .iter()
             .max_by_key(|span| span.offset() + span.len())
             .map_or(0, |span| (span.offset() + span.len()) as u32);
-        Self { error, start, end, fix, fixed: false }
+        let fixable = fix.as_ref().is_some_and(|fix| !fix.is_suggestion());
+        Self { error, start, end, fix, fixed: false, rule_name: None, fixable }
+    }
+
+    #[must_use]
+    pub fn with_rule_name(mut self, rule_name: &'static str) -> Self {
+        self.rule_name = Some(rule_name);
+        self
+    }
+
+    /// The rule has a fix for the diagnostic, which is not computed
+    #[must_use]
+    pub fn with_fixable(mut self, fixable: bool) -> Self {
+        self.fixable = fixable;
+        self
+    }
+
+    /// Report with `severity` instead of the severity of the rule diagnostic
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.error = Error::new(WithSeverity { error: self.error, severity });
+        self
+    }
+
+    pub fn rule_name(&self) -> Option<&'static str> {
+        self.rule_name
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        self.fixable
+    }
+
+    /// The suggested fix, which is reported but never applied by the [`Fixer`]
+    pub fn suggestion(&self) -> Option<&CompositeFix<'a>> {
+        self.fix.as_ref().filter(|fix| fix.is_suggestion())
     }
 
     pub fn start(&self) -> u32 {
@@ -57,6 +172,59 @@ impl<'a> Message<'a> {
     }
 }
 
+/// A diagnostic with the severity configured for its rule
+#[derive(Debug)]
+struct WithSeverity {
+    error: Error,
+    severity: Severity,
+}
+
+impl fmt::Display for WithSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for WithSeverity {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl Diagnostic for WithSeverity {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.error.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.error.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.error.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.error.diagnostic_source()
+    }
+}
+
 /// The fixer of the code.
 /// Note that our parser has handled the BOM, so we don't need to port the BOM test cases from `ESLint`.
 pub struct Fixer<'a> {
@@ -69,10 +237,24 @@ impl<'a> Fixer<'a> {
         Self { source_text, messages }
     }
 
+    /// Apply the fixes which do not overlap, suggestions are never applied.
+    /// The edits of a fix are applied together or not at all.
+    ///
     /// # Panics
     pub fn fix(mut self) -> FixResult<'a> {
         let source_text = self.source_text;
-        if self.messages.iter().all(|m| matches!(m.fix, None)) {
+        self.messages
+            .sort_by_key(|m| m.fix.as_ref().map_or_else(Span::default, CompositeFix::span));
+        let mut applicable = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| {
+                m.fix.as_ref().filter(|fix| !fix.is_suggestion()).map(|fix| (i, fix))
+            })
+            .filter_map(|(i, fix)| fix.merge(source_text).map(|fix| (i, fix)))
+            .collect::<Vec<_>>();
+        if applicable.is_empty() {
             return FixResult {
                 fixed: false,
                 fixed_code: Cow::Borrowed(source_text),
@@ -80,35 +262,29 @@ impl<'a> Fixer<'a> {
             };
         }
 
-        self.messages.sort_by_key(|m| m.fix.as_ref().unwrap_or(&Fix::default()).span);
+        applicable.sort_by_key(|(_, fix)| fix.span);
         let mut fixed = false;
         let mut output = String::with_capacity(source_text.len());
         let mut last_pos: i64 = -1;
-        self.messages.iter_mut().filter(|m| matches!(m.fix, Some(_))).for_each(|m| {
-            let Fix { content, span } = m.fix.as_ref().unwrap();
-            let start = span.start;
-            let end = span.end;
-            if start > end {
-                return;
-            }
-            if i64::from(start) <= last_pos {
-                return;
+        for (i, Fix { content, span }) in applicable {
+            if i64::from(span.start) <= last_pos {
+                continue;
             }
 
-            m.fixed = true;
+            self.messages[i].fixed = true;
             fixed = true;
             let offset = usize::try_from(last_pos.max(0)).ok().unwrap();
-            output.push_str(&source_text[offset..start as usize]);
-            output.push_str(content);
-            last_pos = i64::from(end);
-        });
+            output.push_str(&source_text[offset..span.start as usize]);
+            output.push_str(&content);
+            last_pos = i64::from(span.end);
+        }
 
         let offset = usize::try_from(last_pos.max(0)).ok().unwrap();
         output.push_str(&source_text[offset..]);
 
         let mut messages = self.messages.into_iter().filter(|m| !m.fixed).collect::<Vec<_>>();
         messages.sort_by_key(|m| (m.start, m.end));
-        return FixResult { fixed, fixed_code: Cow::Owned(output), messages };
+        FixResult { fixed, fixed_code: Cow::Owned(output), messages }
     }
 }
 
@@ -120,7 +296,7 @@ mod test {
     use oxc_diagnostics::{thiserror::Error, Error};
     use oxc_span::Span;
 
-    use super::{Fix, FixResult, Fixer, Message};
+    use super::{CompositeFix, Fix, FixKind, FixResult, Fixer, Message};
 
     const TEST_CODE: &str = "var answer = 6 * 7;";
 
@@ -190,7 +366,7 @@ mod test {
     }
 
     fn create_message<T: Into<Error>>(error: T, fix: Option<Fix>) -> Message {
-        Message::new(error.into(), fix)
+        Message::new(error.into(), fix.map(CompositeFix::from))
     }
 
     #[test]
@@ -392,4 +568,58 @@ mod test {
         assert_eq!(result.messages[1].error.to_string(), "nofix2");
         assert!(result.fixed);
     }
+
+    #[test]
+    fn apply_all_edits_of_a_fix() {
+        let fix = CompositeFix::new(vec![
+            Fix::new("let", Span::new(0, 3)),
+            Fix::new("42", Span::new(13, 18)),
+        ]);
+        let result = get_fix_result(vec![Message::new(ReplaceVar.into(), Some(fix))]);
+        assert_eq!(result.fixed_code, "let answer = 42;");
+        assert_eq!(result.messages.len(), 0);
+        assert!(result.fixed);
+    }
+
+    #[test]
+    fn apply_no_edits_of_a_fix_when_one_overlaps_another_fix() {
+        let fix = CompositeFix::new(vec![
+            Fix::new("let", Span::new(0, 3)),
+            Fix::new("foo", Span::new(4, 10)),
+        ]);
+        let result = get_fix_result(vec![
+            create_message(RemoveEnd, Some(REMOVE_END)),
+            create_message(RemoveMiddle(Span::default()), Some(REMOVE_MIDDLE)),
+            Message::new(ReplaceVar.into(), Some(fix)),
+        ]);
+        assert_eq!(result.fixed_code, "let foo = 6;");
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].error.to_string(), "removemiddle");
+    }
+
+    #[test]
+    fn ignore_overlapping_edits() {
+        let fix = CompositeFix::new(vec![
+            Fix::new("let", Span::new(0, 3)),
+            Fix::new("foo", Span::new(2, 10)),
+        ]);
+        let result = get_fix_result(vec![Message::new(ReplaceVar.into(), Some(fix))]);
+        assert_eq!(result.fixed_code, TEST_CODE);
+        assert_eq!(result.messages.len(), 1);
+        assert!(!result.fixed);
+    }
+
+    #[test]
+    fn never_apply_suggestions() {
+        let suggestion = CompositeFix::from(REPLACE_VAR).with_kind(FixKind::Suggestion);
+        let message = Message::new(ReplaceVar.into(), Some(suggestion));
+        assert!(!message.is_fixable());
+        assert!(message.suggestion().is_some());
+
+        let result = get_fix_result(vec![message, create_message(ReplaceId, Some(REPLACE_ID))]);
+        assert_eq!(result.fixed_code, TEST_CODE.replace("answer", "foo"));
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].error.to_string(), "let");
+        assert!(result.messages[0].suggestion().is_some());
+    }
 }
@@ -5,6 +5,7 @@
 mod tester;
 
 mod ast_util;
+mod config;
 mod context;
 mod disable_directives;
 mod fixer;
@@ -12,22 +13,31 @@ mod globals;
 pub mod rule;
 mod rules;
 
-use std::{fs, io::Write, rc::Rc};
+use std::{io::Write, rc::Rc, sync::Arc};
 
-pub use fixer::{FixResult, Fixer, Message};
+pub use fixer::{CompositeFix, Fix, FixKind, FixResult, Fixer, Message};
+use oxc_diagnostics::Severity;
 pub(crate) use oxc_semantic::AstNode;
 use oxc_semantic::Semantic;
 use rustc_hash::FxHashMap;
 
 use crate::context::LintContext;
 pub use crate::{
+    config::{
+        AllowWarnDeny, Environment, FailedToLoadConfigError, FileConfig, InvalidRuleSeverityError,
+        LintConfig, UnknownRuleError, UnsupportedExtendsError,
+    },
     rule::RuleCategory,
     rules::{RuleEnum, RULES},
 };
 
 #[derive(Debug)]
 pub struct Linter {
-    rules: Vec<RuleEnum>,
+    /// The rules and their configured severity
+    rules: Vec<(RuleEnum, Option<Severity>)>,
+
+    /// `env` and `globals` of the linted files
+    environment: Arc<Environment>,
 
     fix: bool,
 }
@@ -43,7 +53,26 @@ impl Linter {
     }
 
     pub fn from_rules(rules: Vec<RuleEnum>) -> Self {
-        Self { rules, fix: false }
+        let rules = rules.into_iter().map(|rule| (rule, None)).collect();
+        Self { rules, environment: Arc::default(), fix: false }
+    }
+
+    /// The enabled rules, `env` and `globals` of `config`,
+    /// the diagnostics of the rules are reported with the severity configured for them
+    pub fn from_file_config(config: FileConfig) -> Self {
+        let rules = config
+            .enabled_rules()
+            .into_iter()
+            .map(|(rule, severity)| {
+                let severity = severity.and_then(|severity| match severity {
+                    AllowWarnDeny::Allow => None,
+                    AllowWarnDeny::Warn => Some(Severity::Warning),
+                    AllowWarnDeny::Deny => Some(Severity::Error),
+                });
+                (rule, severity)
+            })
+            .collect();
+        Self { rules, environment: Arc::new(config.environment), fix: false }
     }
 
     pub fn has_fix(&self) -> bool {
@@ -82,16 +111,20 @@ impl Linter {
     }
 
     pub fn run<'a>(&self, semantic: &Rc<Semantic<'a>>) -> Vec<Message<'a>> {
-        let mut ctx = LintContext::new(semantic, self.fix);
+        let mut ctx =
+            LintContext::new(semantic, self.fix).with_environment(Arc::clone(&self.environment));
         for node in semantic.nodes().iter() {
-            for rule in &self.rules {
+            for (rule, severity) in &self.rules {
                 ctx.with_rule_name(rule.name());
+                ctx.with_severity(*severity);
                 rule.run(node, &ctx);
             }
         }
 
         for symbol in semantic.symbols().iter() {
-            for rule in &self.rules {
+            for (rule, severity) in &self.rules {
+                ctx.with_rule_name(rule.name());
+                ctx.with_severity(*severity);
                 rule.run_on_symbol(symbol, &ctx);
             }
         }
@@ -99,15 +132,6 @@ impl Linter {
         ctx.into_message()
     }
 
-    #[allow(unused)]
-    fn read_rules_configuration() -> Option<serde_json::Map<String, serde_json::Value>> {
-        fs::read_to_string(".eslintrc.json")
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .and_then(|v: serde_json::Value| v.get("rules").cloned())
-            .and_then(|v| v.as_object().cloned())
-    }
-
     pub fn print_rules<W: Write>(writer: &mut W) {
         let rules_by_category = RULES.iter().fold(FxHashMap::default(), |mut map, rule| {
             map.entry(rule.category()).or_insert_with(Vec::new).push(rule);
@@ -126,7 +150,15 @@ impl Linter {
 
 #[cfg(test)]
 mod test {
-    use super::Linter;
+    use std::{path::Path, rc::Rc};
+
+    use oxc_allocator::Allocator;
+    use oxc_diagnostics::Severity;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    use super::{FileConfig, LintConfig, Linter};
 
     #[test]
     fn print_rules() {
@@ -134,4 +166,42 @@ mod test {
         Linter::print_rules(&mut writer);
         assert!(!writer.is_empty());
     }
+
+    #[test]
+    fn configured_severity() {
+        let source_text = "debugger;";
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic = SemanticBuilder::new(source_text, source_type)
+            .with_trivias(&ret.trivias)
+            .build(program)
+            .semantic;
+        let semantic = Rc::new(semantic);
+
+        let severities = |json: &str| {
+            let path = Path::new(".eslintrc.json");
+            let (config, _) = LintConfig::from_json_str(json, path).unwrap();
+            let mut file_config = FileConfig::default();
+            config.apply(path, &mut file_config);
+            let linter = Linter::from_file_config(file_config);
+            linter.run(&semantic).iter().map(|message| message.error.severity()).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            severities(r#"{ "rules": { "no-debugger": "error" } }"#),
+            [Some(Severity::Error)]
+        );
+        assert_eq!(severities(r#"{ "rules": { "no-debugger": 1 } }"#), [Some(Severity::Warning)]);
+        assert!(severities(r#"{ "rules": { "no-debugger": "off" } }"#).is_empty());
+
+        // The rule diagnostic keeps its own severity when it is not configured
+        let linter = Linter::new();
+        let severities = linter
+            .run(&semantic)
+            .iter()
+            .map(|message| message.error.severity())
+            .collect::<Vec<_>>();
+        assert_eq!(severities, [Some(Severity::Warning)]);
+    }
 }
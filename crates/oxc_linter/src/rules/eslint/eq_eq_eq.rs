@@ -4,7 +4,7 @@ use oxc_diagnostics::{
     thiserror::{self, Error},
 };
 use oxc_macros::declare_oxc_lint;
-use oxc_span::Span;
+use oxc_span::{GetSpan, Span};
 use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
 
 use crate::{context::LintContext, fixer::Fix, rule::Rule, AstNode};
@@ -66,7 +66,21 @@ impl Rule for EqEqEq {
             let preferred_operator = to_strict_operator(binary_expr.operator).as_str();
             ctx.diagnostic_with_fix(
                 EqEqEqDiagnostic(operator, preferred_operator, binary_expr.span),
-                || Fix::new(preferred_operator, binary_expr.span),
+                || {
+                    // Replace the operator, the operands are kept
+                    let span = binary_expr.span;
+                    let (left_end, right_start) =
+                        (binary_expr.left.span().end, binary_expr.right.span().start);
+                    let source_text = ctx.source_text();
+                    let fixed = format!(
+                        "{}{}{}",
+                        &source_text[span.start as usize..left_end as usize],
+                        source_text[left_end as usize..right_start as usize]
+                            .replacen(operator, preferred_operator, 1),
+                        &source_text[right_start as usize..span.end as usize],
+                    );
+                    Fix::new(fixed, span)
+                },
             );
         }
     }
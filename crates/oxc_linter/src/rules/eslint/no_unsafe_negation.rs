@@ -65,7 +65,7 @@ impl Rule for NoUnsafeNegation {
         if self.should_check(expr.operator) {
             let Expression::UnaryExpression(left) = &expr.left else { return; };
             if left.operator == UnaryOperator::LogicalNot {
-                Self::report_with_fix(expr, ctx);
+                Self::report_with_suggestion(expr, ctx);
             }
         }
     }
@@ -78,11 +78,11 @@ impl NoUnsafeNegation {
 
     /// Precondition:
     /// expr.left is `UnaryExpression` whose operator is '!'
-    fn report_with_fix(expr: &BinaryExpression, ctx: &LintContext<'_>) {
+    fn report_with_suggestion(expr: &BinaryExpression, ctx: &LintContext<'_>) {
         // Diagnostic points at the unexpected negation
         let diagnostic = NoUnsafeNegationDiagnostic(expr.operator.as_str(), expr.left.span());
 
-        let fix_producer = || {
+        let suggestion = || {
             // modify `!a instance of B` to `!(a instanceof B)`
             let modified_code = {
                 let mut formatter = ctx.formatter();
@@ -98,7 +98,7 @@ impl NoUnsafeNegation {
             Fix::new(modified_code, expr.span)
         };
 
-        ctx.diagnostic_with_fix(diagnostic, fix_producer);
+        ctx.diagnostic_with_suggestion(diagnostic, suggestion);
     }
 }
 
@@ -29,12 +29,12 @@ fn main() {
     let source_type = SourceType::from_path(path).unwrap();
 
     let options = MinifierOptions { mangle, ..MinifierOptions::default() };
-    let printed = Minifier::new(&source_text, source_type, options).build();
+    let printed = Minifier::new(&source_text, source_type, options).build().code;
     println!("{printed}");
 
     if twice {
         let options = MinifierOptions { mangle, ..MinifierOptions::default() };
-        let printed = Minifier::new(&printed, source_type, options).build();
+        let printed = Minifier::new(&printed, source_type, options).build().code;
         println!("{printed}");
     }
 }
@@ -0,0 +1,333 @@
+//! Dead Code Elimination
+//!
+//! Removes unreachable statements, branches of `if` with a constant test and
+//! unreferenced declarations, using the resolved references of the symbol table.
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_hir::hir_util::get_boolean_value;
+use oxc_semantic::ScopeFlags;
+
+use super::{Compressor, SPAN};
+
+impl<'a> Compressor<'a> {
+    /// Replace `if` with a constant and side effect free test by the branch taken,
+    /// `if (false) { foo }` -> `{}`, `if (true) foo; else bar` -> `foo`.
+    /// The branch not taken is kept if it contains `var` declarations, which are hoisted.
+    /// In sloppy mode, functions declared in blocks of the branch not taken also declare a `var`
+    /// in the enclosing function (Annex B.3.3), `if (false) { function f() {} }` -> `var f;`.
+    /// Enabled by `compress.dead_code`
+    pub(super) fn remove_dead_branch<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        if !self.options.dead_code {
+            return;
+        }
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        let Some(test) = get_boolean_value(&if_stmt.test) else { return };
+        if self.may_have_side_effects(&if_stmt.test) {
+            return;
+        }
+        let if_stmt = &mut **if_stmt;
+        let (taken, not_taken) = if test {
+            (&mut if_stmt.consequent, &if_stmt.alternate)
+        } else {
+            (&mut if_stmt.alternate, &if_stmt.consequent)
+        };
+        if not_taken.as_ref().is_some_and(has_var_declaration) {
+            return;
+        }
+        let mut functions = std::vec::Vec::new();
+        if !self.strict_mode
+            && let Some(not_taken) = not_taken
+            && !collect_block_functions(not_taken, false, &mut functions)
+        {
+            return;
+        }
+        if !functions.iter().all(|id| self.is_declared_binding(id)) {
+            return;
+        }
+        let mut declarations = self.hir.new_vec();
+        for id in functions {
+            let Some(id) = self.annex_b_var_binding(id) else { continue };
+            let id = self.hir.binding_identifier_pattern(id);
+            let kind = VariableDeclarationKind::Var;
+            declarations.push(self.hir.variable_declarator(SPAN, kind, id, None, false));
+        }
+        let taken = taken.take();
+        *stmt = if declarations.is_empty() {
+            taken.unwrap_or_else(|| self.hir.block_statement(SPAN, self.hir.new_vec()))
+        } else {
+            let kind = VariableDeclarationKind::Var;
+            let decl = self.hir.variable_declaration(SPAN, kind, declarations);
+            let decl = Statement::Declaration(Declaration::VariableDeclaration(decl));
+            match taken {
+                Some(taken) => {
+                    let mut body = self.hir.new_vec_with_capacity(2);
+                    body.push(decl);
+                    body.push(taken);
+                    self.hir.block_statement(SPAN, body)
+                }
+                None => decl,
+            }
+        };
+    }
+
+    /// The symbol of `id` is in the symbol table, declared by `id`.
+    fn is_declared_binding(&self, id: &BindingIdentifier) -> bool {
+        let symbols = self.semantic.symbols();
+        let symbol_id = id.symbol_id.get();
+        symbol_id.index() < symbols.len()
+            && symbols.get_span(symbol_id) == id.span
+            && symbols.get_name(symbol_id) == &id.name
+    }
+
+    /// The `var` binding declared by Annex B.3.3 for the function `id` declared in a block,
+    /// `None` when no `var` is declared because it would conflict with a lexical declaration.
+    /// The binding reuses the symbol of the function without its span,
+    /// so it is never removed as unused by [Self::is_local_binding].
+    fn annex_b_var_binding(&mut self, id: &BindingIdentifier) -> Option<BindingIdentifier> {
+        let symbols = self.semantic.symbols();
+        let scopes = self.semantic.scopes();
+        let symbol_id = id.symbol_id.get();
+        let mut scope_id = symbols.get_scope_id(symbol_id);
+        while let Some(parent_id) = scopes.get_parent_id(scope_id) {
+            scope_id = parent_id;
+            let is_var_scope = scopes.get_flags(scope_id).intersects(ScopeFlags::Var);
+            if let Some(binding) = scopes.get_binding(scope_id, &id.name) {
+                let flag = symbols.get_flag(binding);
+                // `var` and functions in blocks are declared in their block by the lowering
+                let conflicts = if is_var_scope {
+                    !flag.is_function_scoped_declaration()
+                } else {
+                    !flag.is_catch_variable()
+                        && (!flag.is_function_scoped_declaration() || flag.is_function())
+                };
+                if conflicts {
+                    return None;
+                }
+            }
+            if is_var_scope {
+                break;
+            }
+        }
+        Some(self.hir.binding_identifier(SPAN, id.name.clone(), symbol_id))
+    }
+
+    /// Remove statements after `return`, `throw`, `break` and `continue`,
+    /// declarations of unreferenced symbols with side effect free initializers and empty blocks.
+    /// Blocks without lexical declarations are flattened into `stmts`.
+    /// Enabled by `compress.dead_code`
+    pub(super) fn remove_dead_code<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.dead_code {
+            return;
+        }
+
+        let mut new_stmts = self.hir.new_vec_with_capacity(stmts.len());
+        let mut unreachable = false;
+        for mut stmt in stmts.drain(..) {
+            if unreachable && !Self::keep_unreachable_statement(&mut stmt) {
+                continue;
+            }
+            match &mut stmt {
+                Statement::ReturnStatement(_)
+                | Statement::ThrowStatement(_)
+                | Statement::BreakStatement(_)
+                | Statement::ContinueStatement(_) => unreachable = true,
+                Statement::BlockStatement(block) => {
+                    if !block.body.iter().any(is_lexical_declaration) {
+                        new_stmts.extend(block.body.drain(..));
+                        continue;
+                    }
+                }
+                Statement::Declaration(Declaration::FunctionDeclaration(func)) => {
+                    if func.id.as_ref().is_some_and(|id| self.is_unused_binding(id)) {
+                        continue;
+                    }
+                }
+                Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                    decl.declarations.retain(|declarator| {
+                        let BindingPattern::BindingIdentifier(id) = &declarator.id else {
+                            return true;
+                        };
+                        !self.is_unused_binding(id)
+                            || declarator
+                                .init
+                                .as_ref()
+                                .is_some_and(|init| self.may_have_side_effects(init))
+                    });
+                    if decl.declarations.is_empty() {
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            new_stmts.push(stmt);
+        }
+        *stmts = new_stmts;
+    }
+
+    /// Unreachable function declarations and `var` declarations are still hoisted,
+    /// the initializers of `var` declarations are removed.
+    fn keep_unreachable_statement(stmt: &mut Statement<'a>) -> bool {
+        match stmt {
+            Statement::Declaration(Declaration::FunctionDeclaration(_)) => true,
+            Statement::Declaration(Declaration::VariableDeclaration(decl))
+                if decl.kind == VariableDeclarationKind::Var =>
+            {
+                for declarator in decl.declarations.iter_mut() {
+                    if declarator.id.is_binding_identifier() {
+                        declarator.init = None;
+                    }
+                }
+                true
+            }
+            _ => has_var_declaration(stmt),
+        }
+    }
+
+    /// The symbol is never referenced and removing its declaration cannot be observed.
+    fn is_unused_binding(&self, id: &BindingIdentifier) -> bool {
+        self.semantic.symbols().get_resolved_references(id.symbol_id.get()).is_empty()
+            && self.is_local_binding(id)
+    }
+
+    /// All the references to the symbol are in the resolved references of the symbol table.
+    /// Top level symbols of scripts are global, they are only local in modules when not exported.
+    /// Redeclarations are not local, e.g. `var` of a parameter is observable through `arguments`.
+    pub(super) fn is_local_binding(&self, id: &BindingIdentifier) -> bool {
+        if self.has_direct_eval || self.with_depth > 0 {
+            return false;
+        }
+        let symbol_id = id.symbol_id.get();
+        let symbols = self.semantic.symbols();
+        if symbols.get_span(symbol_id) != id.span {
+            return false;
+        }
+        let scopes = self.semantic.scopes();
+        let mut scope_id = symbols.get_scope_id(symbol_id);
+        // `var` and functions in blocks are not hoisted by the lowering,
+        // their references outside of the block are not resolved.
+        if symbols.get_flag(symbol_id).is_function_scoped_declaration()
+            && !scopes.get_flags(scope_id).intersects(ScopeFlags::Var)
+        {
+            return false;
+        }
+        while !scopes.get_flags(scope_id).intersects(ScopeFlags::Var)
+            && let Some(parent_id) = scopes.get_parent_id(scope_id)
+        {
+            scope_id = parent_id;
+        }
+        if scope_id == scopes.root_scope_id() {
+            return self.semantic.source_type().is_module()
+                && !self.exported_names.contains(symbols.get_name(symbol_id));
+        }
+        true
+    }
+}
+
+/// `let`, `const`, class and function declarations are scoped to their block
+pub(super) fn is_lexical_declaration(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Declaration(Declaration::VariableDeclaration(decl)) => decl.kind.is_lexical(),
+        Statement::Declaration(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether the statement contains a `var` declaration, outside of nested functions
+/// Collect the plain functions declared directly in blocks of `stmt` into `functions`,
+/// these declare a `var` in sloppy mode. Nested functions are not visited.
+/// Returns false when a function is declared directly as the body of a statement,
+/// e.g. `if (a) function f() {}`, its binding is not in a block.
+fn collect_block_functions<'s>(
+    stmt: &'s Statement,
+    in_block: bool,
+    functions: &mut std::vec::Vec<&'s BindingIdentifier>,
+) -> bool {
+    let mut collect = |stmt: &'s Option<Statement>| {
+        stmt.as_ref().map_or(true, |stmt| collect_block_functions(stmt, false, functions))
+    };
+    match stmt {
+        Statement::Declaration(Declaration::FunctionDeclaration(func)) => {
+            if !in_block {
+                return false;
+            }
+            if !func.r#async && !func.generator && let Some(id) = &func.id {
+                functions.push(id);
+            }
+            true
+        }
+        Statement::BlockStatement(block) => collect_statements(&block.body, functions),
+        Statement::IfStatement(stmt) => collect(&stmt.consequent) && collect(&stmt.alternate),
+        Statement::ForStatement(stmt) => collect(&stmt.body),
+        Statement::ForInStatement(stmt) => collect(&stmt.body),
+        Statement::ForOfStatement(stmt) => collect(&stmt.body),
+        Statement::WhileStatement(stmt) => collect(&stmt.body),
+        Statement::DoWhileStatement(stmt) => collect(&stmt.body),
+        Statement::LabeledStatement(stmt) => collect(&stmt.body),
+        Statement::WithStatement(stmt) => collect(&stmt.body),
+        Statement::TryStatement(stmt) => {
+            collect_statements(&stmt.block.body, functions)
+                && stmt
+                    .handler
+                    .as_ref()
+                    .map_or(true, |handler| collect_statements(&handler.body.body, functions))
+                && stmt
+                    .finalizer
+                    .as_ref()
+                    .map_or(true, |finalizer| collect_statements(&finalizer.body, functions))
+        }
+        Statement::SwitchStatement(stmt) => {
+            stmt.cases.iter().all(|case| collect_statements(&case.consequent, functions))
+        }
+        _ => true,
+    }
+}
+
+fn collect_statements<'s>(
+    stmts: &'s [Statement],
+    functions: &mut std::vec::Vec<&'s BindingIdentifier>,
+) -> bool {
+    stmts.iter().all(|stmt| collect_block_functions(stmt, true, functions))
+}
+
+fn has_var_declaration(stmt: &Statement) -> bool {
+    let is_var = |decl: &VariableDeclaration| decl.kind == VariableDeclarationKind::Var;
+    let has_var = |stmt: &Option<Statement>| stmt.as_ref().is_some_and(has_var_declaration);
+    match stmt {
+        Statement::Declaration(Declaration::VariableDeclaration(decl)) => is_var(decl),
+        Statement::BlockStatement(block) => block.body.iter().any(has_var_declaration),
+        Statement::IfStatement(stmt) => has_var(&stmt.consequent) || has_var(&stmt.alternate),
+        Statement::ForStatement(stmt) => {
+            matches!(&stmt.init, Some(ForStatementInit::VariableDeclaration(decl)) if is_var(decl))
+                || has_var(&stmt.body)
+        }
+        Statement::ForInStatement(stmt) => {
+            matches!(&stmt.left, ForStatementLeft::VariableDeclaration(decl) if is_var(decl))
+                || has_var(&stmt.body)
+        }
+        Statement::ForOfStatement(stmt) => {
+            matches!(&stmt.left, ForStatementLeft::VariableDeclaration(decl) if is_var(decl))
+                || has_var(&stmt.body)
+        }
+        Statement::WhileStatement(stmt) => has_var(&stmt.body),
+        Statement::DoWhileStatement(stmt) => has_var(&stmt.body),
+        Statement::LabeledStatement(stmt) => has_var(&stmt.body),
+        Statement::WithStatement(stmt) => has_var(&stmt.body),
+        Statement::TryStatement(stmt) => {
+            stmt.block.body.iter().any(has_var_declaration)
+                || stmt
+                    .handler
+                    .as_ref()
+                    .is_some_and(|handler| handler.body.body.iter().any(has_var_declaration))
+                || stmt
+                    .finalizer
+                    .as_ref()
+                    .is_some_and(|finalizer| finalizer.body.iter().any(has_var_declaration))
+        }
+        Statement::SwitchStatement(stmt) => {
+            stmt.cases.iter().any(|case| case.consequent.iter().any(has_var_declaration))
+        }
+        _ => false,
+    }
+}
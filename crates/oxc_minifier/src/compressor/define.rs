@@ -0,0 +1,218 @@
+//! Global constant definitions, `drop_console` and `pure_funcs`
+//!
+//! Only references to global variables are replaced or dropped,
+//! shadowed locals such as `let process = {}` are left untouched.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_parser::Parser;
+use oxc_semantic::{Reference, ReferenceFlag};
+use oxc_span::{Atom, SourceType};
+use oxc_syntax::{operator::UnaryOperator, NumberBase};
+
+use super::{Compressor, SPAN};
+
+/// A parsed entry of `define`, e.g. `process.env.NODE_ENV` -> `"production"`
+#[derive(Debug)]
+pub(super) struct Define {
+    /// Dotted global path, `["process", "env", "NODE_ENV"]`
+    path: Vec<Atom>,
+    value: DefineValue,
+}
+
+#[derive(Debug)]
+enum DefineValue {
+    Null,
+    Undefined,
+    Boolean(bool),
+    Number(f64, NumberBase),
+    String(Atom),
+    /// Another global, e.g. `globalThis` or `window.document`
+    Path(Vec<Atom>),
+}
+
+impl Define {
+    /// The key must be a dotted global path, e.g. `process.env.NODE_ENV`.
+    /// The value must be a literal or a dotted global path.
+    pub(super) fn parse(key: &str, value: &str) -> Result<Self, String> {
+        let allocator = Allocator::default();
+        let path = parse_expression(&allocator, key)
+            .as_ref()
+            .and_then(parse_path)
+            .ok_or_else(|| format!("`{key}` is not a global name or a dotted path"))?;
+        let value = parse_expression(&allocator, value)
+            .as_ref()
+            .and_then(parse_value)
+            .ok_or_else(|| format!("`{value}` is not a literal or a global name"))?;
+        Ok(Self { path, value })
+    }
+}
+
+fn parse_expression<'a>(
+    allocator: &'a Allocator,
+    source_text: &'a str,
+) -> Option<ast::Expression<'a>> {
+    // Parenthesized so string literals are not parsed as directives
+    let source_text = allocator.alloc(format!("({source_text})"));
+    let ret = Parser::new(allocator, source_text, SourceType::default()).parse();
+    if !ret.errors.is_empty() || ret.program.body.len() != 1 {
+        return None;
+    }
+    let mut program = ret.program;
+    let ast::Statement::ExpressionStatement(stmt) = program.body.remove(0) else { return None };
+    match stmt.unbox().expression {
+        ast::Expression::ParenthesizedExpression(expr) => Some(expr.unbox().expression),
+        expr => Some(expr),
+    }
+}
+
+fn parse_path(expr: &ast::Expression) -> Option<Vec<Atom>> {
+    match expr {
+        ast::Expression::Identifier(ident) => Some(vec![ident.name.clone()]),
+        ast::Expression::MemberExpression(member_expr) => match &**member_expr {
+            ast::MemberExpression::StaticMemberExpression(expr) if !expr.optional => {
+                let mut path = parse_path(&expr.object)?;
+                path.push(expr.property.name.clone());
+                Some(path)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_value(expr: &ast::Expression) -> Option<DefineValue> {
+    let value = match expr {
+        ast::Expression::NullLiteral(_) => DefineValue::Null,
+        ast::Expression::BooleanLiteral(lit) => DefineValue::Boolean(lit.value),
+        ast::Expression::NumberLiteral(lit) => DefineValue::Number(lit.value, lit.base),
+        ast::Expression::StringLiteral(lit) => DefineValue::String(lit.value.clone()),
+        ast::Expression::Identifier(ident) if ident.name == "undefined" => DefineValue::Undefined,
+        ast::Expression::UnaryExpression(unary_expr) => {
+            match (unary_expr.operator, &unary_expr.argument) {
+                (UnaryOperator::UnaryNegation, ast::Expression::NumberLiteral(lit)) => {
+                    DefineValue::Number(-lit.value, lit.base)
+                }
+                (UnaryOperator::Void, ast::Expression::NumberLiteral(_)) => DefineValue::Undefined,
+                _ => return None,
+            }
+        }
+        _ => DefineValue::Path(parse_path(expr)?),
+    };
+    Some(value)
+}
+
+impl<'a> Compressor<'a> {
+    /// Replace global constants by their definition,
+    /// `process.env.NODE_ENV` -> `"production"`.
+    /// Enabled by `compress.define`
+    pub(super) fn replace_define<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        if self.defines.is_empty() {
+            return;
+        }
+        let Some(index) =
+            self.defines.iter().position(|define| self.is_global_path(expr, &define.path))
+        else {
+            return;
+        };
+        *expr = match &self.defines[index].value {
+            DefineValue::Null => {
+                let lit = self.hir.null_literal(SPAN);
+                self.hir.literal_null_expression(lit)
+            }
+            DefineValue::Undefined => self.create_void_0(),
+            DefineValue::Boolean(value) => {
+                let lit = self.hir.boolean_literal(SPAN, *value);
+                self.hir.literal_boolean_expression(lit)
+            }
+            DefineValue::Number(value, base) => {
+                let raw = self.hir.new_str(&value.to_string());
+                let lit = self.hir.number_literal(SPAN, *value, raw, *base);
+                self.hir.literal_number_expression(lit)
+            }
+            DefineValue::String(value) => {
+                let lit = self.hir.string_literal(SPAN, value.clone());
+                self.hir.literal_string_expression(lit)
+            }
+            DefineValue::Path(path) => self.create_global_path(&path.clone()),
+        };
+    }
+
+    /// Replace calls to `console` methods by `void 0`,
+    /// the arguments are not evaluated.
+    /// Enabled by `compress.drop_console`
+    pub(super) fn drop_console_call<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        if self.is_console_call(expr) {
+            *expr = self.create_void_0();
+        }
+    }
+
+    /// Drop `console.log(foo)` statements.
+    /// Enabled by `compress.drop_console`
+    pub(super) fn drop_console<'b>(&mut self, stmt: &'b Statement<'a>) -> bool {
+        matches!(stmt, Statement::ExpressionStatement(stmt) if self.is_console_call(&stmt.expression))
+    }
+
+    fn is_console_call(&self, expr: &Expression<'a>) -> bool {
+        if !self.options.drop_console {
+            return false;
+        }
+        let Expression::CallExpression(call_expr) = expr else { return false };
+        let Expression::MemberExpression(member_expr) = &call_expr.callee else { return false };
+        let MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+            return false;
+        };
+        matches!(&member_expr.object, Expression::Identifier(ident)
+            if ident.name == "console" && self.is_global_reference(ident))
+    }
+
+    /// Whether the callee is listed in `compress.pure_funcs`
+    pub(super) fn is_pure_func(&self, callee: &Expression<'a>) -> bool {
+        self.pure_funcs.iter().any(|path| self.is_global_path(callee, path))
+    }
+
+    /// `expr` is the dotted `path` starting with a global reference, e.g. `process.env.NODE_ENV`
+    fn is_global_path(&self, expr: &Expression<'a>, path: &[Atom]) -> bool {
+        match expr {
+            Expression::Identifier(ident) => {
+                path.len() == 1 && ident.name == path[0] && self.is_global_reference(ident)
+            }
+            Expression::MemberExpression(member_expr) => match &**member_expr {
+                MemberExpression::StaticMemberExpression(expr) => {
+                    let Some((name, object_path)) = path.split_last() else { return false };
+                    !expr.optional
+                        && expr.property.name == *name
+                        && self.is_global_path(&expr.object, object_path)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    pub(super) fn is_global_reference(&self, ident: &IdentifierReference) -> bool {
+        self.semantic.symbols().is_global_reference(ident.reference_id.get())
+    }
+
+    /// `window.document`, the references are added to the symbol table as global references
+    fn create_global_path(&mut self, path: &[Atom]) -> Expression<'a> {
+        let (name, object_path) = path.split_last().unwrap();
+        if object_path.is_empty() {
+            let reference = Reference::new(SPAN, name.clone(), ReferenceFlag::Read);
+            let reference_id = self.semantic.symbols_mut().create_reference(reference);
+            let ident = self.hir.identifier_reference(
+                SPAN,
+                name.clone(),
+                reference_id,
+                ReferenceFlag::Read,
+            );
+            return self.hir.identifier_reference_expression(ident);
+        }
+        let object = self.create_global_path(object_path);
+        let property = self.hir.identifier_name(SPAN, name.clone());
+        let member_expr = self.hir.static_member_expression(SPAN, object, property, false);
+        self.hir.member_expression(member_expr)
+    }
+}
@@ -234,6 +234,30 @@ impl<'a> Compressor<'a> {
             }
             return match left {
                 Ty::Void | Ty::Null => Tri::True,
+                Ty::Str => match (left_expr, right_expr) {
+                    (Expression::StringLiteral(left), Expression::StringLiteral(right)) => {
+                        Tri::for_boolean(left.value == right.value)
+                    }
+                    _ => Tri::Unknown,
+                },
+                Ty::Number => {
+                    match (
+                        get_side_free_number_value(left_expr),
+                        get_side_free_number_value(right_expr),
+                    ) {
+                        (Some(NumberValue::NaN), Some(_)) | (Some(_), Some(NumberValue::NaN)) => {
+                            Tri::False
+                        }
+                        (Some(left), Some(right)) => Tri::for_boolean(left == right),
+                        _ => Tri::Unknown,
+                    }
+                }
+                Ty::Boolean => {
+                    match (get_boolean_value(left_expr), get_boolean_value(right_expr)) {
+                        (Some(left), Some(right)) => Tri::for_boolean(left == right),
+                        _ => Tri::Unknown,
+                    }
+                }
                 _ => Tri::Unknown,
             };
         }
@@ -0,0 +1,430 @@
+//! Constant propagation and single-use variable inlining
+//!
+//! `const a = 1; f(a)` -> `f(1)` and `let a = g(); return a + 1` -> `return g() + 1`,
+//! using the resolved references of the symbol table. The declarations left
+//! without references are removed by the dead code elimination.
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_hir::hir_util::MayHaveSideEffects;
+use oxc_semantic::SymbolId;
+use oxc_span::Atom;
+use oxc_syntax::operator::UnaryOperator;
+
+use super::{Compressor, SPAN};
+
+/// Constants read more often than this are not inlined, the copies would outweigh the declaration
+const MAX_CONSTANT_READS: usize = 3;
+
+/// Strings up to this length are always inlined, like numbers and booleans
+const MAX_SHORT_STRING_LEN: usize = 3;
+
+/// The length assumed for the name of a constant once mangled
+const MANGLED_NAME_LEN: usize = 1;
+
+/// The literal initializer of a `const` declaration
+#[derive(Debug)]
+pub(super) enum Constant<'a> {
+    Null,
+    Undefined,
+    Boolean(bool),
+    Number(NumberLiteral<'a>),
+    NegativeNumber(NumberLiteral<'a>),
+    String(Atom),
+}
+
+impl<'a> Constant<'a> {
+    fn from_expression(expr: &Expression<'a>) -> Option<Self> {
+        let constant = match expr {
+            Expression::NullLiteral(_) => Self::Null,
+            Expression::BooleanLiteral(lit) => Self::Boolean(lit.value),
+            Expression::NumberLiteral(lit) => Self::Number((**lit).clone()),
+            Expression::StringLiteral(lit) => Self::String(lit.value.clone()),
+            Expression::UnaryExpression(unary_expr) => {
+                match (unary_expr.operator, &unary_expr.argument) {
+                    (UnaryOperator::UnaryNegation, Expression::NumberLiteral(lit)) => {
+                        Self::NegativeNumber((**lit).clone())
+                    }
+                    _ if expr.is_void_0() => Self::Undefined,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(constant)
+    }
+
+    /// The copies of the literal are no bigger than the declaration and the reads they replace,
+    /// `const a='long string';f(a,a)`
+    fn is_worth_inlining(&self, reads: usize) -> bool {
+        let Self::String(value) = self else { return true };
+        if value.len() <= MAX_SHORT_STRING_LEN {
+            return true;
+        }
+        // The quotes, escapes are not counted
+        let literal_len = value.len() + 2;
+        // `a='long string',`
+        let declaration_len = MANGLED_NAME_LEN + 1 + literal_len + 1;
+        literal_len * reads <= declaration_len + reads * MANGLED_NAME_LEN
+    }
+}
+
+/// Result of searching the single use of a symbol in evaluation order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Substitution {
+    /// The reference was replaced
+    Done,
+    /// Not found yet, nothing evaluated so far can observe or change the replacement
+    Continue,
+    /// The replacement cannot be moved any further
+    Stop,
+}
+
+impl<'a> Compressor<'a> {
+    /// Record `const a = 1` when all the reads of `a` are after its declaration
+    /// and inlining does not make the output bigger,
+    /// before visiting the initializer which is compressed to `!0` for booleans.
+    /// Enabled by `compress.reduce_vars`
+    pub(super) fn collect_constant<'b>(&mut self, declarator: &'b VariableDeclarator<'a>) {
+        if !self.options.reduce_vars || declarator.kind != VariableDeclarationKind::Const {
+            return;
+        }
+        let (BindingPattern::BindingIdentifier(id), Some(init)) = (&declarator.id, &declarator.init)
+        else {
+            return;
+        };
+        let Some(constant) = Constant::from_expression(init) else { return };
+        let symbol_id = id.symbol_id.get();
+        let symbols = self.semantic.symbols();
+        let references = symbols.get_resolved_references(symbol_id);
+        // Reads before the declaration are in the temporal dead zone
+        if references.len() > MAX_CONSTANT_READS
+            || !constant.is_worth_inlining(references.len())
+            || !references.iter().all(|reference_id| {
+                let reference = symbols.get_reference(*reference_id);
+                reference.is_read() && reference.span().start >= declarator.span.end
+            })
+        {
+            return;
+        }
+        self.constants.insert(symbol_id, constant);
+    }
+
+    /// `const a = 1; f(a)` -> `const a = 1; f(1)`.
+    /// Enabled by `compress.reduce_vars`
+    pub(super) fn inline_constant<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        if self.constants.is_empty() || self.with_depth > 0 {
+            return;
+        }
+        let Expression::Identifier(ident) = expr else { return };
+        let reference_id = ident.reference_id.get();
+        let Some(symbol_id) = self.semantic.symbols().get_reference(reference_id).symbol_id()
+        else {
+            return;
+        };
+        let Some(constant) = self.constants.get(&symbol_id) else { return };
+        *expr = match constant {
+            Constant::Null => {
+                let lit = self.hir.null_literal(SPAN);
+                self.hir.literal_null_expression(lit)
+            }
+            Constant::Undefined => self.create_void_0(),
+            Constant::Boolean(value) => {
+                let lit = self.hir.boolean_literal(SPAN, *value);
+                self.hir.literal_boolean_expression(lit)
+            }
+            Constant::Number(lit) => self.hir.literal_number_expression(lit.clone()),
+            Constant::NegativeNumber(lit) => {
+                let argument = self.hir.literal_number_expression(lit.clone());
+                self.hir.unary_expression(SPAN, UnaryOperator::UnaryNegation, argument)
+            }
+            Constant::String(value) => {
+                let lit = self.hir.string_literal(SPAN, value.clone());
+                self.hir.literal_string_expression(lit)
+            }
+        };
+        self.semantic.symbols_mut().delete_resolved_reference(symbol_id, reference_id);
+    }
+
+    /// Move the initializer of a variable used once into the next statement,
+    /// `let a = g(); return a + 1` -> `return g() + 1`,
+    /// as long as nothing evaluated before the use can observe the difference.
+    /// Enabled by `compress.collapse_vars`
+    pub(super) fn collapse_single_use_vars<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.collapse_vars {
+            return;
+        }
+        let mut new_stmts = self.hir.new_vec_with_capacity(stmts.len());
+        for mut stmt in stmts.drain(..) {
+            // Declarations collapsed into `stmt` may expose a previous one, e.g. `let a = f(), b = a`
+            while let Some(Statement::Declaration(Declaration::VariableDeclaration(decl))) =
+                new_stmts.last_mut()
+                && let Some(declarator) = decl.declarations.last_mut()
+                && let Some(symbol_id) = self.get_single_use_symbol(declarator)
+            {
+                let init = declarator.init.take().unwrap();
+                let has_side_effects = init.may_have_side_effects();
+                let mut replacement = Some(init);
+                let substitution = self.substitute_in_statement(
+                    &mut stmt,
+                    symbol_id,
+                    &mut replacement,
+                    has_side_effects,
+                );
+                if substitution != Substitution::Done {
+                    declarator.init = replacement;
+                    break;
+                }
+                decl.declarations.pop();
+                if decl.declarations.is_empty() {
+                    new_stmts.pop();
+                }
+            }
+            new_stmts.push(stmt);
+        }
+        *stmts = new_stmts;
+    }
+
+    /// The symbol declared by `declarator` is local and read exactly once
+    fn get_single_use_symbol(&self, declarator: &VariableDeclarator<'a>) -> Option<SymbolId> {
+        let BindingPattern::BindingIdentifier(id) = &declarator.id else { return None };
+        declarator.init.as_ref()?;
+        let symbol_id = id.symbol_id.get();
+        let symbols = self.semantic.symbols();
+        let [reference_id] = symbols.get_resolved_references(symbol_id).as_slice() else {
+            return None;
+        };
+        (symbols.get_reference(*reference_id).is_read() && self.is_local_binding(id))
+            .then_some(symbol_id)
+    }
+
+    fn substitute_in_statement(
+        &mut self,
+        stmt: &mut Statement<'a>,
+        symbol_id: SymbolId,
+        replacement: &mut Option<Expression<'a>>,
+        has_side_effects: bool,
+    ) -> Substitution {
+        let mut substitute = |expr: &mut Expression<'a>| {
+            self.substitute(expr, symbol_id, replacement, has_side_effects)
+        };
+        match stmt {
+            Statement::ExpressionStatement(stmt) => substitute(&mut stmt.expression),
+            Statement::ReturnStatement(stmt) => {
+                stmt.argument.as_mut().map_or(Substitution::Stop, substitute)
+            }
+            Statement::ThrowStatement(stmt) => substitute(&mut stmt.argument),
+            Statement::IfStatement(stmt) => substitute(&mut stmt.test),
+            Statement::SwitchStatement(stmt) => substitute(&mut stmt.discriminant),
+            Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                for declarator in decl.declarations.iter_mut() {
+                    if let Some(init) = &mut declarator.init {
+                        let substitution = substitute(init);
+                        if substitution != Substitution::Continue {
+                            return substitution;
+                        }
+                    }
+                    // Destructuring may call getters or iterators
+                    if !declarator.id.is_binding_identifier() {
+                        return Substitution::Stop;
+                    }
+                }
+                Substitution::Stop
+            }
+            _ => Substitution::Stop,
+        }
+    }
+
+    /// Search the single use of `symbol_id` in evaluation order and replace it.
+    /// Only literals and reads of never assigned locals can be evaluated before the use,
+    /// the replacement is moved into conditionally evaluated code if it has no side effects.
+    #[allow(clippy::too_many_lines)]
+    fn substitute(
+        &mut self,
+        expr: &mut Expression<'a>,
+        symbol_id: SymbolId,
+        replacement: &mut Option<Expression<'a>>,
+        has_side_effects: bool,
+    ) -> Substitution {
+        macro_rules! substitute {
+            ($expr:expr) => {{
+                let substitution = self.substitute($expr, symbol_id, replacement, has_side_effects);
+                if substitution != Substitution::Continue {
+                    return substitution;
+                }
+            }};
+        }
+
+        match expr {
+            Expression::Identifier(ident) => {
+                let reference_id = ident.reference_id.get();
+                let symbols = self.semantic.symbols();
+                match symbols.get_reference(reference_id).symbol_id() {
+                    Some(id) if id == symbol_id => {
+                        *expr = replacement.take().unwrap();
+                        self.semantic.symbols_mut().delete_resolved_reference(id, reference_id);
+                        return Substitution::Done;
+                    }
+                    Some(id) if self.is_never_assigned(id) => {}
+                    _ => return Substitution::Stop,
+                }
+            }
+            Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BigintLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::ThisExpression(_) => {}
+            Expression::UnaryExpression(expr) if expr.operator != UnaryOperator::Delete => {
+                substitute!(&mut expr.argument);
+            }
+            Expression::BinaryExpression(expr) => {
+                substitute!(&mut expr.left);
+                substitute!(&mut expr.right);
+            }
+            Expression::LogicalExpression(expr) => {
+                substitute!(&mut expr.left);
+                if has_side_effects {
+                    return Substitution::Stop;
+                }
+                substitute!(&mut expr.right);
+            }
+            Expression::ConditionalExpression(expr) => {
+                substitute!(&mut expr.test);
+                return Substitution::Stop;
+            }
+            Expression::SequenceExpression(expr) => {
+                for expr in expr.expressions.iter_mut() {
+                    substitute!(expr);
+                }
+            }
+            Expression::MemberExpression(member_expr) => {
+                return self.substitute_in_member_expression(
+                    member_expr,
+                    symbol_id,
+                    replacement,
+                    has_side_effects,
+                );
+            }
+            Expression::CallExpression(expr) if !expr.optional => {
+                substitute!(&mut expr.callee);
+                return self.substitute_in_arguments(
+                    &mut expr.arguments,
+                    symbol_id,
+                    replacement,
+                    has_side_effects,
+                );
+            }
+            Expression::NewExpression(expr) => {
+                substitute!(&mut expr.callee);
+                return self.substitute_in_arguments(
+                    &mut expr.arguments,
+                    symbol_id,
+                    replacement,
+                    has_side_effects,
+                );
+            }
+            Expression::AssignmentExpression(expr) => {
+                match &mut expr.left {
+                    AssignmentTarget::SimpleAssignmentTarget(
+                        SimpleAssignmentTarget::AssignmentTargetIdentifier(_),
+                    ) => {}
+                    AssignmentTarget::SimpleAssignmentTarget(
+                        SimpleAssignmentTarget::MemberAssignmentTarget(member_expr),
+                    ) => {
+                        return self.substitute_in_member_expression(
+                            member_expr,
+                            symbol_id,
+                            replacement,
+                            has_side_effects,
+                        );
+                    }
+                    AssignmentTarget::AssignmentTargetPattern(_) => return Substitution::Stop,
+                }
+                substitute!(&mut expr.right);
+                return Substitution::Stop;
+            }
+            Expression::ArrayExpression(expr) => {
+                for element in expr.elements.iter_mut() {
+                    match element {
+                        ArrayExpressionElement::Expression(expr) => substitute!(expr),
+                        ArrayExpressionElement::SpreadElement(spread) => {
+                            substitute!(&mut spread.argument);
+                            return Substitution::Stop;
+                        }
+                        ArrayExpressionElement::Elision(_) => {}
+                    }
+                }
+            }
+            Expression::AwaitExpression(expr) => {
+                substitute!(&mut expr.argument);
+                return Substitution::Stop;
+            }
+            _ => return Substitution::Stop,
+        }
+        Substitution::Continue
+    }
+
+    fn substitute_in_member_expression(
+        &mut self,
+        expr: &mut MemberExpression<'a>,
+        symbol_id: SymbolId,
+        replacement: &mut Option<Expression<'a>>,
+        has_side_effects: bool,
+    ) -> Substitution {
+        let (object, property) = match expr {
+            MemberExpression::ComputedMemberExpression(expr) => {
+                (&mut expr.object, Some(&mut expr.expression))
+            }
+            MemberExpression::StaticMemberExpression(expr) => (&mut expr.object, None),
+            MemberExpression::PrivateFieldExpression(expr) => (&mut expr.object, None),
+        };
+        let substitution = self.substitute(object, symbol_id, replacement, has_side_effects);
+        if substitution != Substitution::Continue {
+            return substitution;
+        }
+        if let Some(property) = property {
+            let substitution = self.substitute(property, symbol_id, replacement, has_side_effects);
+            if substitution != Substitution::Continue {
+                return substitution;
+            }
+        }
+        // The property access may call a getter
+        Substitution::Stop
+    }
+
+    fn substitute_in_arguments(
+        &mut self,
+        arguments: &mut Vec<'a, Argument<'a>>,
+        symbol_id: SymbolId,
+        replacement: &mut Option<Expression<'a>>,
+        has_side_effects: bool,
+    ) -> Substitution {
+        for argument in arguments.iter_mut() {
+            let (expr, is_spread) = match argument {
+                Argument::Expression(expr) => (expr, false),
+                Argument::SpreadElement(spread) => (&mut spread.argument, true),
+            };
+            let substitution = self.substitute(expr, symbol_id, replacement, has_side_effects);
+            if substitution != Substitution::Continue {
+                return substitution;
+            }
+            // Spreading calls the iterator
+            if is_spread {
+                return Substitution::Stop;
+            }
+        }
+        // The call itself is evaluated after the arguments
+        Substitution::Stop
+    }
+
+    /// All the references to the symbol are reads
+    fn is_never_assigned(&self, symbol_id: SymbolId) -> bool {
+        let symbols = self.semantic.symbols();
+        symbols
+            .get_resolved_references(symbol_id)
+            .iter()
+            .all(|reference_id| symbols.get_reference(*reference_id).is_read())
+    }
+}
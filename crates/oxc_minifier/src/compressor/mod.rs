@@ -1,20 +1,30 @@
 #![allow(clippy::unused_self)]
 
+mod dce;
+mod define;
 mod fold;
+mod inline;
+mod side_effects;
+mod statements;
+
+use std::collections::{HashMap, HashSet};
 
 use oxc_allocator::{Allocator, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::{hir::*, HirBuilder, VisitMut};
-use oxc_semantic::Semantic;
-use oxc_span::Span;
+use oxc_semantic::{Semantic, SymbolId};
+use oxc_span::{Atom, Span};
 use oxc_syntax::{
     operator::{BinaryOperator, UnaryOperator},
     precedence::GetPrecedence,
     NumberBase,
 };
 
+use self::{define::Define, inline::Constant};
+use crate::{Printer, PrinterOptions};
+
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CompressOptions {
     /// Various optimizations for boolean context, for example `!!a ? b : c` → `a ? b : c`
     /// Default true
@@ -24,6 +34,10 @@ pub struct CompressOptions {
     /// Default true
     pub drop_debugger: bool,
 
+    /// Remove calls to `console` methods, e.g. `console.log(foo)`
+    /// Default false
+    pub drop_console: bool,
+
     /// Join consecutive var statements
     /// Default true
     pub join_vars: bool,
@@ -35,11 +49,103 @@ pub struct CompressOptions {
     /// Transforms `typeof foo == "undefined" into `foo === void 0`
     /// Default true
     pub typeofs: bool,
+
+    /// Remove unreachable code, branches of `if` with a constant test
+    /// and declarations of unreferenced variables and functions
+    /// Default true
+    pub dead_code: bool,
+
+    /// Remove unused expressions without side effects,
+    /// including calls annotated with `/*#__PURE__*/` or `/*@__NO_SIDE_EFFECTS__*/`
+    /// Default true
+    pub side_effects: bool,
+
+    /// Inline `const` declarations of literals read a few times,
+    /// e.g. `const a = 1; f(a)` → `f(1)`
+    /// Default true
+    pub reduce_vars: bool,
+
+    /// Move the initializer of variables used once into the next statement,
+    /// side effects permitting, e.g. `let a = g(); return a + 1` → `return g() + 1`
+    /// Default true
+    pub collapse_vars: bool,
+
+    /// Transform `if` statements into logical and conditional expressions,
+    /// e.g. `if (a) b()` → `a && b()`
+    /// Default true
+    pub conditionals: bool,
+
+    /// Join consecutive expression statements into comma sequences,
+    /// e.g. `a(); b()` → `a(), b()`
+    /// Default true
+    pub sequences: bool,
+
+    /// Optimizations for `if` and `return`, e.g. `if (a) return b; return c` → `return a ? b : c`,
+    /// remove `else` after `return` and `return` at the end of functions
+    /// Default true
+    pub if_return: bool,
+
+    /// Replace global constants, from a dotted global path to the source of a literal
+    /// or another global, e.g. `process.env.NODE_ENV` -> `"production"`.
+    /// Entries are checked by [CompressOptions::validate]
+    /// Default empty
+    pub define: HashMap<String, String>,
+
+    /// Global functions whose calls have no side effects, e.g. `Math.floor` or `log`
+    /// Default empty
+    pub pure_funcs: std::vec::Vec<String>,
+
+    /// Maximum number of passes over the program, a fold may expose further opportunities
+    /// to the next pass. Stops early once a pass no longer shrinks the output
+    /// Default 1
+    pub passes: usize,
 }
 
 impl Default for CompressOptions {
     fn default() -> Self {
-        Self { booleans: true, drop_debugger: true, join_vars: true, loops: true, typeofs: true }
+        Self {
+            booleans: true,
+            drop_debugger: true,
+            drop_console: false,
+            join_vars: true,
+            loops: true,
+            typeofs: true,
+            dead_code: true,
+            side_effects: true,
+            reduce_vars: true,
+            collapse_vars: true,
+            conditionals: true,
+            sequences: true,
+            if_return: true,
+            define: HashMap::new(),
+            pure_funcs: vec![],
+            passes: 1,
+        }
+    }
+}
+
+impl CompressOptions {
+    /// Check an entry of `define`, the key must be a dotted global path
+    /// and the value a literal or a dotted global path.
+    ///
+    /// # Errors
+    ///
+    /// * The key or the value is not supported
+    pub fn check_define(key: &str, value: &str) -> Result<(), String> {
+        Define::parse(key, value).map(|_| ())
+    }
+
+    /// Check the options which cannot be represented by their types, see [Self::check_define].
+    ///
+    /// # Errors
+    ///
+    /// * An entry of `define` is not supported
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, value) in &self.define {
+            Self::check_define(key, value)
+                .map_err(|err| format!("Invalid define {key}={value}: {err}"))?;
+        }
+        Ok(())
     }
 }
 
@@ -47,20 +153,107 @@ pub struct Compressor<'a> {
     hir: HirBuilder<'a>,
     semantic: Semantic<'a>,
     options: CompressOptions,
+
+    /* Dead code elimination */
+    /// Symbols may be referenced by a direct `eval`, no declaration can be removed
+    has_direct_eval: bool,
+    /// Number of `with` statements the current node is in
+    with_depth: usize,
+    /// The current node is strict mode code, functions declared in blocks are not hoisted
+    strict_mode: bool,
+    /// Local names exported by `export { name }`, which are not references in the symbol table
+    exported_names: HashSet<Atom>,
+
+    /* Side effects */
+    /// Functions annotated with `/*@__NO_SIDE_EFFECTS__*/`
+    no_side_effects_functions: HashSet<SymbolId>,
+
+    /* Inlining */
+    /// `const` declarations of literals which can be inlined
+    constants: HashMap<SymbolId, Constant<'a>>,
+
+    /* Global definitions */
+    /// Parsed `define`
+    defines: std::vec::Vec<Define>,
+    /// Parsed `pure_funcs`
+    pure_funcs: std::vec::Vec<std::vec::Vec<Atom>>,
 }
 
 const SPAN: Span = Span::new(0, 0);
 
 impl<'a> Compressor<'a> {
+    /// # Panics
+    ///
+    /// * The options are invalid, see [CompressOptions::validate]
     pub fn new(allocator: &'a Allocator, semantic: Semantic<'a>, options: CompressOptions) -> Self {
-        Self { hir: HirBuilder::new(allocator), semantic, options }
+        let has_direct_eval = semantic.scopes().root_unresolved_references().contains_key("eval");
+        if let Err(err) = options.validate() {
+            panic!("{err}");
+        }
+        // Every entry is parsed by `validate` above
+        let defines = options
+            .define
+            .iter()
+            .filter_map(|(key, value)| Define::parse(key, value).ok())
+            .collect();
+        let pure_funcs = options
+            .pure_funcs
+            .iter()
+            .map(|name| name.split('.').map(Atom::from).collect())
+            .collect();
+        Self {
+            hir: HirBuilder::new(allocator),
+            semantic,
+            options,
+            has_direct_eval,
+            with_depth: 0,
+            strict_mode: false,
+            exported_names: HashSet::new(),
+            no_side_effects_functions: HashSet::new(),
+            constants: HashMap::new(),
+            defines,
+            pure_funcs,
+        }
     }
 
     pub fn build<'b>(mut self, program: &'b mut Program<'a>) -> Semantic<'a> {
+        self.strict_mode = self.semantic.scopes().root_flags().is_strict_mode()
+            || program.directives.iter().any(|d| d.directive == "use strict");
+        self.exported_names = program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::ModuleDeclaration(decl) => match &**decl {
+                    ModuleDeclaration::ExportNamedDeclaration(decl) if decl.source.is_none() => {
+                        Some(decl.specifiers.iter().map(|specifier| specifier.local.name().clone()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        self.collect_no_side_effects_functions(program);
         self.visit_program(program);
+        if self.options.passes > 1 {
+            let mut size = Self::output_size(program);
+            for _ in 1..self.options.passes {
+                self.visit_program(program);
+                let new_size = Self::output_size(program);
+                if new_size >= size {
+                    break;
+                }
+                size = new_size;
+            }
+        }
         self.semantic
     }
 
+    /// Size of the printed program, for deciding whether to run another pass
+    fn output_size(program: &Program<'a>) -> usize {
+        Printer::new(0, PrinterOptions::default()).build(program).code.len()
+    }
+
     /* Utilities */
 
     /// `void 0`
@@ -256,19 +449,70 @@ impl<'a> Compressor<'a> {
 
 impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
     fn visit_statements(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
-        stmts.retain(|stmt| !self.drop_debugger(stmt));
+        stmts.retain(|stmt| !self.drop_debugger(stmt) && !self.drop_console(stmt));
 
+        // Collapsed before joining, only the last declarator of a declaration can be collapsed
+        self.collapse_single_use_vars(stmts);
         self.join_vars(stmts);
 
         for stmt in stmts.iter_mut() {
             self.visit_statement(stmt);
         }
+
+        self.drop_unused_expressions(stmts);
+        self.remove_dead_code(stmts);
+        self.minimize_statements(stmts);
     }
 
     fn visit_statement(&mut self, stmt: &'b mut Statement<'a>) {
         self.compress_block(stmt);
         self.compress_while(stmt);
         self.visit_statement_match(stmt);
+        // The test is folded after visiting, the branch taken may be a single line block
+        self.remove_dead_branch(stmt);
+        self.compress_block(stmt);
+        self.compress_if_statement(stmt);
+    }
+
+    fn visit_function_body(&mut self, body: &'b mut FunctionBody<'a>) {
+        let strict_mode = self.strict_mode;
+        self.strict_mode |= body.directives.iter().any(|d| d.directive == "use strict");
+        for directive in body.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut body.statements);
+        self.remove_trailing_return(body);
+        self.strict_mode = strict_mode;
+    }
+
+    fn visit_class_body(&mut self, body: &'b mut ClassBody<'a>) {
+        // All parts of a class are strict mode code
+        let strict_mode = std::mem::replace(&mut self.strict_mode, true);
+        for elem in body.body.iter_mut() {
+            self.visit_class_element(elem);
+        }
+        self.strict_mode = strict_mode;
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        // The body of `() => a` is the returned value, not an unused expression statement
+        if expr.expression {
+            for stmt in expr.body.statements.iter_mut() {
+                self.visit_statement(stmt);
+            }
+        } else {
+            self.visit_function_body(&mut expr.body);
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &'b mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+        self.with_depth += 1;
+        if let Some(stmt) = &mut stmt.body {
+            self.visit_statement(stmt);
+        }
+        self.with_depth -= 1;
     }
 
     fn visit_return_statement(&mut self, stmt: &'b mut ReturnStatement<'a>) {
@@ -280,6 +524,10 @@ impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
     }
 
     fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        // Replaced before visiting so the definitions are folded
+        self.replace_define(expr);
+        self.drop_console_call(expr);
+        self.inline_constant(expr);
         self.visit_expression_match(expr);
         self.fold_expression(expr);
         if !self.compress_undefined(expr) {
@@ -287,6 +535,30 @@ impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
         }
     }
 
+    fn visit_variable_declarator(&mut self, declarator: &'b mut VariableDeclarator<'a>) {
+        self.collect_constant(declarator);
+        self.visit_binding_pattern(&mut declarator.id);
+        if let Some(init) = &mut declarator.init {
+            self.visit_expression(init);
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &'b mut CallExpression<'a>) {
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        self.visit_expression(&mut expr.callee);
+        self.mark_pure_call(expr);
+    }
+
+    fn visit_new_expression(&mut self, expr: &'b mut NewExpression<'a>) {
+        self.visit_expression(&mut expr.callee);
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        self.mark_pure_new(expr);
+    }
+
     fn visit_binary_expression(&mut self, expr: &'b mut BinaryExpression<'a>) {
         self.visit_expression(&mut expr.left);
         self.visit_expression(&mut expr.right);
@@ -0,0 +1,303 @@
+//! Side effect annotations
+//!
+//! Calls to functions annotated with `/*@__NO_SIDE_EFFECTS__*/` and to global builtins are marked
+//! as pure, unused expressions without side effects are removed.
+
+use std::collections::HashSet;
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_hir::{hir_util::MayHaveSideEffects, VisitMut};
+use oxc_semantic::SymbolId;
+use oxc_syntax::operator::UnaryOperator;
+
+use super::Compressor;
+
+/// Global functions without side effects when called with primitive arguments
+const BUILTIN_FUNCTIONS_WITHOUT_SIDE_EFFECTS: [&str; 5] =
+    ["Boolean", "Error", "Number", "Object", "String"];
+
+/// Global constructors without side effects when called with `new` and primitive arguments
+const CONSTRUCTORS_WITHOUT_SIDE_EFFECTS: [&str; 6] =
+    ["Boolean", "Date", "Error", "Number", "Object", "String"];
+
+/// `Math` methods, without side effects when called with primitive arguments
+const MATH_FUNCTIONS: [&str; 35] = [
+    "abs", "acos", "acosh", "asin", "asinh", "atan", "atan2", "atanh", "cbrt", "ceil", "clz32",
+    "cos", "cosh", "exp", "expm1", "floor", "fround", "hypot", "imul", "log", "log10", "log1p",
+    "log2", "max", "min", "pow", "random", "round", "sign", "sin", "sinh", "sqrt", "tan", "tanh",
+    "trunc",
+];
+
+/// Global objects whose properties are read without side effects
+const GLOBAL_NAMESPACES: [&str; 8] =
+    ["Array", "JSON", "Math", "Number", "Object", "Reflect", "String", "Symbol"];
+
+impl<'a> Compressor<'a> {
+    /// Collect the symbols of the functions annotated with `/*@__NO_SIDE_EFFECTS__*/`,
+    /// before compressing because function declarations are hoisted.
+    pub(super) fn collect_no_side_effects_functions<'b>(&mut self, program: &'b mut Program<'a>) {
+        let mut collector = NoSideEffectsCollector::default();
+        collector.visit_program(program);
+        self.no_side_effects_functions = collector.symbols;
+    }
+
+    /// `f()` -> `/*#__PURE__*/ f()` when `f` is annotated with `/*@__NO_SIDE_EFFECTS__*/`
+    /// or listed in `compress.pure_funcs`, `Math.max(1, 2)` -> `/*#__PURE__*/ Math.max(1, 2)`
+    pub(super) fn mark_pure_call<'b>(&self, expr: &'b mut CallExpression<'a>) {
+        if expr.pure {
+            return;
+        }
+        if self.is_pure_func(&expr.callee) || self.is_pure_builtin_call(expr) {
+            expr.pure = true;
+            return;
+        }
+        if let Expression::Identifier(ident) = &expr.callee
+            && let Some(symbol_id) =
+                self.semantic.symbols().get_reference(ident.reference_id.get()).symbol_id()
+        {
+            expr.pure = self.no_side_effects_functions.contains(&symbol_id);
+        }
+    }
+
+    /// `new Date(0)` -> `/*#__PURE__*/ new Date(0)`
+    pub(super) fn mark_pure_new<'b>(&self, expr: &'b mut NewExpression<'a>) {
+        if expr.pure || expr.arguments.iter().any(|arg| !is_primitive_argument(arg)) {
+            return;
+        }
+        expr.pure = matches!(&expr.callee, Expression::Identifier(ident)
+            if CONSTRUCTORS_WITHOUT_SIDE_EFFECTS.contains(&ident.name.as_str())
+                && self.is_builtin_reference(ident));
+    }
+
+    /// A call of a global builtin function or `Math` method with primitive arguments,
+    /// other arguments may be converted by user code, e.g. `valueOf`.
+    fn is_pure_builtin_call(&self, expr: &CallExpression<'a>) -> bool {
+        if expr.optional || expr.arguments.iter().any(|arg| !is_primitive_argument(arg)) {
+            return false;
+        }
+        match &expr.callee {
+            Expression::Identifier(ident) => {
+                BUILTIN_FUNCTIONS_WITHOUT_SIDE_EFFECTS.contains(&ident.name.as_str())
+                    && self.is_builtin_reference(ident)
+            }
+            Expression::MemberExpression(member_expr) => matches!(&**member_expr,
+                MemberExpression::StaticMemberExpression(expr)
+                    if !expr.optional
+                        && MATH_FUNCTIONS.contains(&expr.property.name.as_str())
+                        && matches!(&expr.object, Expression::Identifier(ident)
+                            if ident.name == "Math" && self.is_builtin_reference(ident))),
+            _ => false,
+        }
+    }
+
+    /// `ident` refers to the global builtin, it is neither declared nor inside `with`
+    fn is_builtin_reference(&self, ident: &IdentifierReference) -> bool {
+        self.with_depth == 0 && self.is_global_reference(ident)
+    }
+
+    /// Like [MayHaveSideEffects], but reading a property of a global namespace,
+    /// e.g. `Math.PI`, has no side effects, while reading an undeclared variable throws
+    /// a `ReferenceError` and reading any variable inside `with` may call a getter.
+    pub(super) fn may_have_side_effects(&self, expr: &Expression<'a>) -> bool {
+        if let Expression::MemberExpression(member_expr) = expr
+            && let MemberExpression::StaticMemberExpression(expr) = &**member_expr
+            && let Expression::Identifier(ident) = &expr.object
+            && GLOBAL_NAMESPACES.contains(&ident.name.as_str())
+            && self.is_builtin_reference(ident)
+        {
+            return false;
+        }
+        expr.may_have_side_effects() || self.reads_unknown_variable(expr)
+    }
+
+    /// An identifier read which [MayHaveSideEffects] considers free is not known to succeed,
+    /// it is undeclared or inside `with`. `undefined`, `NaN` and `Infinity` always exist.
+    fn reads_unknown_variable(&self, expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::Identifier(ident) => {
+                self.with_depth > 0
+                    || (self.is_global_reference(ident)
+                        && !matches!(ident.name.as_str(), "undefined" | "NaN" | "Infinity"))
+            }
+            // `typeof undefinedVar` does not throw
+            Expression::UnaryExpression(unary_expr) => match &unary_expr.argument {
+                Expression::Identifier(_) if unary_expr.operator == UnaryOperator::Typeof => {
+                    self.with_depth > 0
+                }
+                argument => self.reads_unknown_variable(argument),
+            },
+            Expression::LogicalExpression(logical_expr) => {
+                self.reads_unknown_variable(&logical_expr.left)
+                    || self.reads_unknown_variable(&logical_expr.right)
+            }
+            Expression::ConditionalExpression(conditional_expr) => {
+                self.reads_unknown_variable(&conditional_expr.test)
+                    || self.reads_unknown_variable(&conditional_expr.consequent)
+                    || self.reads_unknown_variable(&conditional_expr.alternate)
+            }
+            Expression::SequenceExpression(sequence_expr) => {
+                sequence_expr.expressions.iter().any(|expr| self.reads_unknown_variable(expr))
+            }
+            Expression::CallExpression(call_expr) => {
+                call_expr.arguments.iter().any(|arg| self.argument_reads_unknown_variable(arg))
+            }
+            Expression::NewExpression(new_expr) => {
+                new_expr.arguments.iter().any(|arg| self.argument_reads_unknown_variable(arg))
+            }
+            Expression::ObjectExpression(object_expr) => {
+                object_expr.properties.iter().any(|property| match property {
+                    ObjectPropertyKind::ObjectProperty(property) => {
+                        matches!(&property.key, PropertyKey::Expression(key)
+                            if self.reads_unknown_variable(key))
+                            || self.reads_unknown_variable(&property.value)
+                    }
+                    ObjectPropertyKind::SpreadProperty(_) => true,
+                })
+            }
+            Expression::ArrayExpression(array_expr) => {
+                array_expr.elements.iter().any(|element| match element {
+                    ArrayExpressionElement::Expression(expr) => self.reads_unknown_variable(expr),
+                    ArrayExpressionElement::SpreadElement(_) => true,
+                    ArrayExpressionElement::Elision(_) => false,
+                })
+            }
+            _ => false,
+        }
+    }
+
+    fn argument_reads_unknown_variable(&self, arg: &Argument<'a>) -> bool {
+        match arg {
+            Argument::Expression(expr) => self.reads_unknown_variable(expr),
+            Argument::SpreadElement(_) => true,
+        }
+    }
+
+    /// Remove unused expressions without side effects,
+    /// `/*#__PURE__*/ foo(); a(), new Foo, Math.PI` -> `a()`,
+    /// unused pure calls are reduced to their arguments, `/*#__PURE__*/ g(h())` -> `h()`.
+    /// Nothing is removed inside `with`, where reading a variable may call a getter.
+    /// Enabled by `compress.side_effects`
+    pub(super) fn drop_unused_expressions<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.side_effects || self.with_depth > 0 {
+            return;
+        }
+        for stmt in stmts.iter_mut() {
+            let Statement::ExpressionStatement(expr_stmt) = stmt else { continue };
+            if let Expression::SequenceExpression(sequence_expr) = &mut expr_stmt.expression {
+                for expr in sequence_expr.expressions.iter_mut() {
+                    self.reduce_unused_pure_call(expr);
+                }
+                sequence_expr.expressions.retain(|expr| self.may_have_side_effects(expr));
+                if sequence_expr.expressions.len() == 1 {
+                    expr_stmt.expression = sequence_expr.expressions.pop().unwrap();
+                }
+            } else {
+                self.reduce_unused_pure_call(&mut expr_stmt.expression);
+            }
+        }
+        stmts.retain(|stmt| match stmt {
+            Statement::ExpressionStatement(expr_stmt) => match &expr_stmt.expression {
+                Expression::SequenceExpression(sequence_expr) => {
+                    !sequence_expr.expressions.is_empty()
+                }
+                expr => self.may_have_side_effects(expr),
+            },
+            _ => true,
+        });
+    }
+
+    /// Replace an unused pure call or `new` with its arguments which have side effects,
+    /// `/*#__PURE__*/ g(h(), 1)` -> `h()`. Calls with spread arguments are kept.
+    fn reduce_unused_pure_call(&mut self, expr: &mut Expression<'a>) {
+        let (span, arguments) = match expr {
+            Expression::CallExpression(call_expr) if call_expr.pure => {
+                (call_expr.span, &mut call_expr.arguments)
+            }
+            Expression::NewExpression(new_expr) if new_expr.pure => {
+                (new_expr.span, &mut new_expr.arguments)
+            }
+            _ => return,
+        };
+        if arguments.iter().any(|arg| matches!(arg, Argument::SpreadElement(_)))
+            || !arguments.iter().any(|arg| {
+                matches!(arg, Argument::Expression(expr)
+                if self.may_have_side_effects(expr))
+            })
+        {
+            return;
+        }
+        let mut expressions = self.hir.new_vec();
+        for arg in arguments.drain(..) {
+            if let Argument::Expression(mut arg) = arg
+                && self.may_have_side_effects(&arg)
+            {
+                self.reduce_unused_pure_call(&mut arg);
+                expressions.push(arg);
+            }
+        }
+        *expr = if expressions.len() == 1 {
+            expressions.pop().unwrap()
+        } else {
+            self.hir.sequence_expression(span, expressions)
+        };
+    }
+}
+
+/// Numbers, strings, booleans and `null`, which are converted without calling user code.
+/// BigInts are excluded because `Math` methods and `new Date` throw on them.
+fn is_primitive_argument(arg: &Argument) -> bool {
+    matches!(arg, Argument::Expression(expr) if is_primitive(expr))
+}
+
+fn is_primitive(expr: &Expression) -> bool {
+    match expr {
+        Expression::NumberLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_) => true,
+        Expression::UnaryExpression(unary_expr) => {
+            matches!(unary_expr.operator, UnaryOperator::UnaryNegation | UnaryOperator::Void)
+                && is_primitive(&unary_expr.argument)
+        }
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct NoSideEffectsCollector {
+    symbols: HashSet<SymbolId>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for NoSideEffectsCollector {
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        if func.pure && let Some(id) = &func.id {
+            self.symbols.insert(id.symbol_id.get());
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &'b mut VariableDeclarator<'a>) {
+        if let (BindingPattern::BindingIdentifier(id), Some(init)) =
+            (&declarator.id, &declarator.init)
+            && is_annotated_function(init)
+        {
+            self.symbols.insert(id.symbol_id.get());
+        }
+        if let Some(init) = &mut declarator.init {
+            self.visit_expression(init);
+        }
+    }
+}
+
+fn is_annotated_function(expr: &Expression) -> bool {
+    match expr {
+        Expression::FunctionExpression(func) => func.pure,
+        Expression::ArrowExpression(arrow) => arrow.pure,
+        _ => false,
+    }
+}
@@ -0,0 +1,304 @@
+//! Statement level minimization
+//!
+//! Ported from esbuild's `mangleIf` and `mangleStmts`:
+//! `if` statements become logical or conditional expressions, consecutive
+//! expression statements are joined into comma sequences and `if` returns
+//! are merged into a single `return` of a conditional expression.
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_syntax::operator::{LogicalOperator, UnaryOperator};
+
+use super::{dce::is_lexical_declaration, Compressor, SPAN};
+
+impl<'a> Compressor<'a> {
+    /// `if (a) b()` -> `a && b()`, `if (!a) b()` -> `a || b()`,
+    /// `if (a) b(); else c()` -> `a ? b() : c()`.
+    /// Enabled by `compress.conditionals`
+    ///
+    /// `if (a) return b; else return c` -> `return a ? b : c`.
+    /// Enabled by `compress.if_return`
+    pub(super) fn compress_if_statement<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        let if_stmt = &mut **if_stmt;
+        let is_empty = |stmt: &Option<Statement>| match stmt {
+            None => true,
+            Some(Statement::BlockStatement(block)) => block.body.is_empty(),
+            _ => false,
+        };
+        match (&if_stmt.consequent, &if_stmt.alternate) {
+            // `if (a) {}` -> `a`
+            (consequent, alternate)
+                if self.options.conditionals && is_empty(consequent) && is_empty(alternate) =>
+            {
+                let test = self.take_test(if_stmt);
+                *stmt = self.hir.expression_statement(SPAN, test);
+            }
+            (Some(Statement::ExpressionStatement(_)), None) if self.options.conditionals => {
+                let test = self.take_test(if_stmt);
+                let Some(Statement::ExpressionStatement(consequent)) = if_stmt.consequent.take()
+                else {
+                    unreachable!()
+                };
+                let expr = self.create_logical(test, consequent.unbox().expression);
+                *stmt = self.hir.expression_statement(SPAN, expr);
+            }
+            (Some(Statement::ExpressionStatement(_)), Some(Statement::ExpressionStatement(_)))
+                if self.options.conditionals =>
+            {
+                let test = self.take_test(if_stmt);
+                let (
+                    Some(Statement::ExpressionStatement(consequent)),
+                    Some(Statement::ExpressionStatement(alternate)),
+                ) = (if_stmt.consequent.take(), if_stmt.alternate.take())
+                else {
+                    unreachable!()
+                };
+                let expr = self.create_conditional(
+                    test,
+                    consequent.unbox().expression,
+                    alternate.unbox().expression,
+                );
+                *stmt = self.hir.expression_statement(SPAN, expr);
+            }
+            (
+                Some(Statement::ReturnStatement(consequent)),
+                Some(Statement::ReturnStatement(alternate)),
+            ) if self.options.if_return && has_argument(consequent, alternate) => {
+                let test = self.take_test(if_stmt);
+                let (Some(Statement::ReturnStatement(consequent)), Some(Statement::ReturnStatement(alternate))) =
+                    (if_stmt.consequent.take(), if_stmt.alternate.take())
+                else {
+                    unreachable!()
+                };
+                *stmt = self.create_conditional_return(
+                    test,
+                    consequent.unbox().argument,
+                    alternate.unbox().argument,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Rebuild the statements with the rules below, each statement is pushed
+    /// with [Compressor::push_statement] so the rules apply to the result of the previous ones.
+    ///
+    /// * `a(); b()` -> `a(), b()` and `a(); return b` -> `return a(), b`.
+    ///   Enabled by `compress.sequences`
+    /// * `if (a) return b; else c()` -> `if (a) return b; c()` and
+    ///   `if (a) return b; return c` -> `return a ? b : c`.
+    ///   Enabled by `compress.if_return`
+    pub(super) fn minimize_statements<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.sequences && !self.options.if_return {
+            return;
+        }
+        let mut new_stmts = self.hir.new_vec_with_capacity(stmts.len());
+        for stmt in stmts.drain(..) {
+            self.push_statement(&mut new_stmts, stmt);
+        }
+        *stmts = new_stmts;
+    }
+
+    /// Remove `return` at the end of function bodies.
+    /// Enabled by `compress.if_return`
+    pub(super) fn remove_trailing_return<'b>(&mut self, body: &'b mut FunctionBody<'a>) {
+        if self.options.if_return
+            && matches!(body.statements.last(), Some(Statement::ReturnStatement(stmt)) if stmt.argument.is_none())
+        {
+            body.statements.pop();
+        }
+    }
+
+    fn push_statement(&mut self, stmts: &mut Vec<'a, Statement<'a>>, mut stmt: Statement<'a>) {
+        // `else` after a jump, the alternate is only reached when the test is falsy
+        if self.options.if_return
+            && let Statement::IfStatement(if_stmt) = &mut stmt
+            && if_stmt.alternate.is_some()
+            && if_stmt.consequent.as_ref().is_some_and(ends_with_jump)
+        {
+            let alternate = if_stmt.alternate.take().unwrap();
+            self.push_statement(stmts, stmt);
+            match alternate {
+                Statement::BlockStatement(mut block)
+                    if !block.body.iter().any(is_lexical_declaration) =>
+                {
+                    for stmt in block.body.drain(..) {
+                        self.push_statement(stmts, stmt);
+                    }
+                }
+                alternate => self.push_statement(stmts, alternate),
+            }
+            return;
+        }
+
+        // `if (a) return b; return c` -> `return a ? b : c`
+        if self.options.if_return
+            && let Statement::ReturnStatement(alternate) = &stmt
+            && let Some(Statement::IfStatement(if_stmt)) = stmts.last()
+            && if_stmt.alternate.is_none()
+            && let Some(Statement::ReturnStatement(consequent)) = &if_stmt.consequent
+            && has_argument(consequent, alternate)
+        {
+            let Some(Statement::IfStatement(if_stmt)) = stmts.pop() else { unreachable!() };
+            let if_stmt = if_stmt.unbox();
+            let Some(Statement::ReturnStatement(consequent)) = if_stmt.consequent else {
+                unreachable!()
+            };
+            let Statement::ReturnStatement(alternate) = stmt else { unreachable!() };
+            let stmt = self.create_conditional_return(
+                if_stmt.test,
+                consequent.unbox().argument,
+                alternate.unbox().argument,
+            );
+            self.push_statement(stmts, stmt);
+            return;
+        }
+
+        // `a(); b()` -> `a(), b()`, `a(); if (b) c()` -> `if (a(), b) c()`
+        if self.options.sequences && let Some(Statement::ExpressionStatement(_)) = stmts.last() {
+            // The first statement is kept so its position stays in the source map
+            if let Statement::ExpressionStatement(expr_stmt) = stmt {
+                let Some(Statement::ExpressionStatement(prev)) = stmts.last_mut() else {
+                    unreachable!()
+                };
+                let dummy = self.hir.this_expression(SPAN);
+                let left = std::mem::replace(&mut prev.expression, dummy);
+                prev.expression = self.join_sequence(left, expr_stmt.unbox().expression);
+                return;
+            }
+            let joined = match &mut stmt {
+                Statement::ReturnStatement(stmt) => stmt.argument.as_mut(),
+                Statement::ThrowStatement(stmt) => Some(&mut stmt.argument),
+                Statement::IfStatement(stmt) => Some(&mut stmt.test),
+                Statement::SwitchStatement(stmt) => Some(&mut stmt.discriminant),
+                _ => None,
+            };
+            if let Some(expr) = joined {
+                let Some(Statement::ExpressionStatement(prev)) = stmts.pop() else {
+                    unreachable!()
+                };
+                let dummy = self.hir.this_expression(SPAN);
+                let right = std::mem::replace(expr, dummy);
+                *expr = self.join_sequence(prev.unbox().expression, right);
+            }
+        }
+
+        stmts.push(stmt);
+    }
+
+    /// `a, b` -> `a, b, c`, nested sequences are flattened
+    fn join_sequence(&mut self, left: Expression<'a>, right: Expression<'a>) -> Expression<'a> {
+        let mut expressions = match left {
+            Expression::SequenceExpression(sequence_expr) => sequence_expr.unbox().expressions,
+            left => self.hir.new_vec_single(left),
+        };
+        match right {
+            Expression::SequenceExpression(sequence_expr) => {
+                expressions.extend(sequence_expr.unbox().expressions);
+            }
+            right => expressions.push(right),
+        }
+        self.hir.sequence_expression(SPAN, expressions)
+    }
+
+    /// `return test ? consequent : alternate`, a missing argument is `void 0`.
+    /// `if (a) return; return` is left alone, `return a ? void 0 : void 0` is longer
+    fn create_conditional_return(
+        &mut self,
+        test: Expression<'a>,
+        consequent: Option<Expression<'a>>,
+        alternate: Option<Expression<'a>>,
+    ) -> Statement<'a> {
+        let consequent = consequent.unwrap_or_else(|| self.create_void_0());
+        let alternate = alternate.unwrap_or_else(|| self.create_void_0());
+        let argument = self.create_conditional(test, consequent, alternate);
+        self.hir.return_statement(SPAN, Some(argument))
+    }
+
+    /// `test && consequent`, `!a && b` -> `a || b` and `(a, b) && c` -> `a, b && c`
+    fn create_logical(
+        &mut self,
+        test: Expression<'a>,
+        consequent: Expression<'a>,
+    ) -> Expression<'a> {
+        self.map_sequence_test(test, |compressor, test| {
+            let (test, negated) = split_negation(test);
+            let operator = if negated { LogicalOperator::Or } else { LogicalOperator::And };
+            compressor.hir.logical_expression(SPAN, test, operator, consequent)
+        })
+    }
+
+    /// `test ? consequent : alternate`, `!a ? b : c` -> `a ? c : b`
+    /// and `(a, b) ? c : d` -> `a, b ? c : d`
+    fn create_conditional(
+        &mut self,
+        test: Expression<'a>,
+        consequent: Expression<'a>,
+        alternate: Expression<'a>,
+    ) -> Expression<'a> {
+        self.map_sequence_test(test, |compressor, test| match split_negation(test) {
+            (test, true) => {
+                compressor.hir.conditional_expression(SPAN, test, alternate, consequent)
+            }
+            (test, false) => {
+                compressor.hir.conditional_expression(SPAN, test, consequent, alternate)
+            }
+        })
+    }
+
+    /// Apply `f` to the last expression of a sequence test, the others are evaluated first
+    fn map_sequence_test(
+        &mut self,
+        test: Expression<'a>,
+        f: impl FnOnce(&mut Self, Expression<'a>) -> Expression<'a>,
+    ) -> Expression<'a> {
+        match test {
+            Expression::SequenceExpression(sequence_expr) => {
+                let mut expressions = sequence_expr.unbox().expressions;
+                let last = expressions.pop().unwrap();
+                expressions.push(f(self, last));
+                self.hir.sequence_expression(SPAN, expressions)
+            }
+            test => f(self, test),
+        }
+    }
+
+    fn take_test(&mut self, if_stmt: &mut IfStatement<'a>) -> Expression<'a> {
+        let dummy = self.hir.this_expression(SPAN);
+        std::mem::replace(&mut if_stmt.test, dummy)
+    }
+}
+
+/// `!a` -> `(a, true)`
+fn split_negation(expr: Expression) -> (Expression, bool) {
+    match expr {
+        Expression::UnaryExpression(unary_expr)
+            if unary_expr.operator == UnaryOperator::LogicalNot =>
+        {
+            (unary_expr.unbox().argument, true)
+        }
+        expr => (expr, false),
+    }
+}
+
+fn has_argument(consequent: &ReturnStatement, alternate: &ReturnStatement) -> bool {
+    consequent.argument.is_some() || alternate.argument.is_some()
+}
+
+/// The statement always ends with `return`, `throw`, `break` or `continue`
+fn ends_with_jump(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::ReturnStatement(_)
+        | Statement::ThrowStatement(_)
+        | Statement::BreakStatement(_)
+        | Statement::ContinueStatement(_) => true,
+        Statement::BlockStatement(block) => block.body.last().is_some_and(ends_with_jump),
+        Statement::IfStatement(stmt) => {
+            stmt.consequent.as_ref().is_some_and(ends_with_jump)
+                && stmt.alternate.as_ref().is_some_and(ends_with_jump)
+        }
+        _ => false,
+    }
+}
@@ -9,51 +9,125 @@ mod printer;
 
 use oxc_allocator::Allocator;
 use oxc_ast_lower::AstLower;
+use oxc_diagnostics::Error;
 use oxc_parser::Parser;
+use oxc_sourcemap::SourceMap;
 use oxc_span::SourceType;
 
 pub use crate::{
     compressor::{CompressOptions, Compressor},
-    mangler::ManglerBuilder,
-    printer::{Printer, PrinterOptions},
+    mangler::{
+        MangleOptions, ManglePropertiesOptions, Mangler, ManglerBuilder, NameCache,
+        PropertyMangler, PropertyManglerBuilder,
+    },
+    printer::{Printer, PrinterOptions, PrinterReturn},
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct MinifierOptions {
     pub mangle: bool,
+    /// Names to keep when `mangle` is enabled
+    pub mangle_options: MangleOptions,
+    /// Mangle property names, disabled by default
+    pub mangle_properties: Option<ManglePropertiesOptions>,
+    /// Mangled names of a previous build, see [NameCache]
+    pub name_cache: Option<NameCache>,
     pub compress: CompressOptions,
     pub print: PrinterOptions,
+    /// Generate a source map alongside the minified code
+    pub source_map: bool,
+}
+
+impl MinifierOptions {
+    /// Check the options before building a [Minifier].
+    ///
+    /// # Errors
+    ///
+    /// * The compress options are invalid, see [CompressOptions::validate]
+    pub fn validate(&self) -> Result<(), String> {
+        self.compress.validate()
+    }
 }
 
 impl Default for MinifierOptions {
     fn default() -> Self {
-        Self { mangle: true, compress: CompressOptions::default(), print: PrinterOptions }
+        Self {
+            mangle: true,
+            mangle_options: MangleOptions::default(),
+            mangle_properties: None,
+            name_cache: None,
+            compress: CompressOptions::default(),
+            print: PrinterOptions::default(),
+            source_map: false,
+        }
     }
 }
 
+pub struct MinifierReturn {
+    pub code: String,
+    /// `Some` when `MinifierOptions::source_map` is enabled
+    pub source_map: Option<SourceMap>,
+    /// Syntax errors of the source text, `code` is not reliable when there are any
+    pub errors: Vec<Error>,
+    /// The input name cache updated with the names mangled by this build,
+    /// `Some` when `MinifierOptions::mangle` or `MinifierOptions::mangle_properties` is enabled
+    pub name_cache: Option<NameCache>,
+}
+
 pub struct Minifier<'a> {
     source_text: &'a str,
     source_type: SourceType,
+    source_name: &'a str,
     options: MinifierOptions,
 }
 
 impl<'a> Minifier<'a> {
     pub fn new(source_text: &'a str, source_type: SourceType, options: MinifierOptions) -> Self {
-        Self { source_text, source_type, options }
+        Self { source_text, source_type, source_name: "", options }
+    }
+
+    /// Name of the original file recorded in the `sources` field of the source map.
+    #[must_use]
+    pub fn with_source_name(mut self, source_name: &'a str) -> Self {
+        self.source_name = source_name;
+        self
     }
 
-    pub fn build(self) -> String {
+    /// # Panics
+    ///
+    /// * The options are invalid, see [MinifierOptions::validate]
+    pub fn build(self) -> MinifierReturn {
         let allocator = Allocator::default();
         let ret = Parser::new(&allocator, self.source_text, self.source_type).parse();
-        let ret = AstLower::new(&allocator, self.source_text, self.source_type).build(&ret.program);
+        let errors = ret.errors;
+        let trivias = ret.trivias;
+        let ret = AstLower::new(&allocator, self.source_text, self.source_type)
+            .with_trivias(&trivias)
+            .build(&ret.program);
         let program = allocator.alloc(ret.program);
         let semantic = ret.semantic;
         let _semantic = Compressor::new(&allocator, semantic, self.options.compress).build(program);
         let mut printer = Printer::new(self.source_text.len(), self.options.print);
+        printer.with_comments(self.source_text, &trivias);
+        let mut name_cache = self.options.name_cache.clone().unwrap_or_default();
         if self.options.mangle {
-            let mangler = ManglerBuilder::new(self.source_text, self.source_type).build(program);
+            let mangler = ManglerBuilder::new(self.source_text, self.source_type)
+                .with_options(self.options.mangle_options.clone())
+                .with_name_cache(name_cache)
+                .build(program);
+            name_cache = mangler.name_cache().clone();
             printer.with_mangler(mangler);
         }
-        printer.build(program)
+        if let Some(options) = &self.options.mangle_properties {
+            let mangler = PropertyManglerBuilder::new(options).build(program, &mut name_cache);
+            printer.with_property_mangler(mangler);
+        }
+        let name_cache =
+            (self.options.mangle || self.options.mangle_properties.is_some()).then_some(name_cache);
+        if self.options.source_map {
+            printer.with_source_map(self.source_name, self.source_text);
+        }
+        let PrinterReturn { code, source_map } = printer.build(program);
+        MinifierReturn { code, source_map, errors, name_cache }
     }
 }
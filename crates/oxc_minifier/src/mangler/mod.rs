@@ -1,15 +1,45 @@
+mod name_cache;
+mod properties;
+
+use std::collections::{HashMap, HashSet};
+
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::hir::*;
 use oxc_hir::Visit;
 use oxc_index::{index_vec, IndexVec};
-use oxc_semantic::{Reference, ReferenceFlag, ReferenceId, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_semantic::{
+    Reference, ReferenceFlag, ReferenceId, ScopeId, ScopeTree, SemanticBuilder, SymbolId,
+    SymbolTable,
+};
 use oxc_span::{Atom, SourceType};
 use oxc_syntax::{scope::ScopeFlags, symbol::SymbolFlags};
 
+pub use self::{
+    name_cache::NameCache,
+    properties::{ManglePropertiesOptions, PropertyMangler, PropertyManglerBuilder},
+};
+
 type Slot = usize;
 
+#[derive(Debug, Default, Clone)]
+pub struct MangleOptions {
+    /// Names which are never used as mangled names,
+    /// bindings with these names are not renamed, e.g. `$` or `require`
+    pub reserved: Vec<String>,
+    /// Keep the names of function declarations
+    pub keep_fnames: bool,
+    /// Keep the names of class declarations
+    pub keep_classnames: bool,
+    /// Mangle the top level bindings of scripts, they are global variables visible to other scripts.
+    /// The top level bindings of modules are always mangled, except exported declarations
+    pub top_level: bool,
+}
+
 pub struct Mangler {
     symbol_table: SymbolTable,
+    /// Top level bindings by their original name, for printing `export { foo }`
+    top_level_bindings: HashMap<Atom, SymbolId>,
+    name_cache: NameCache,
 }
 
 impl Mangler {
@@ -21,6 +51,17 @@ impl Mangler {
         let symbol_id = self.symbol_table.get_reference(reference_id).symbol_id()?;
         Some(self.symbol_table.get_name(symbol_id))
     }
+
+    /// The mangled name of the top level binding named `name`
+    pub fn get_top_level_name(&self, name: &Atom) -> Option<&Atom> {
+        let symbol_id = self.top_level_bindings.get(name)?;
+        Some(self.symbol_table.get_name(*symbol_id))
+    }
+
+    /// The input name cache updated with the top level names mangled by this build
+    pub fn name_cache(&self) -> &NameCache {
+        &self.name_cache
+    }
 }
 
 /// # Name Mangler / Symbol Minification
@@ -64,8 +105,25 @@ impl Mangler {
 ///     }
 /// }
 /// ```
+///
+/// Top level bindings found in the [NameCache] keep their previous mangled name,
+/// so rebuilding a file does not rename them.
+///
+/// Bindings are not renamed when they can be looked up by name at runtime:
+/// * top level bindings of scripts, unless [MangleOptions::top_level] is enabled
+/// * exported declarations of modules, `export function foo() {}`
+/// * bindings of scopes containing a direct `eval` or `with` statement and their ancestors
 pub struct ManglerBuilder<'a> {
     semantic: SemanticBuilder<'a>,
+    options: MangleOptions,
+    name_cache: NameCache,
+
+    /// Visiting the declaration of an `export` statement
+    in_export_declaration: bool,
+    exported_symbols: Vec<SymbolId>,
+
+    /// Scopes containing a direct `eval` or `with` statement
+    dynamic_scopes: Vec<ScopeId>,
 }
 
 impl<'a> Visit<'a> for ManglerBuilder<'a> {
@@ -86,6 +144,11 @@ impl<'a> Visit<'a> for ManglerBuilder<'a> {
         let symbol_id =
             self.semantic.declare_symbol_for_mangler(ident.span, &ident.name, includes, excludes);
         ident.symbol_id.replace(symbol_id);
+        if self.in_export_declaration
+            && self.semantic.current_scope_id == self.semantic.scope.root_scope_id()
+        {
+            self.exported_symbols.push(symbol_id);
+        }
     }
 
     fn visit_identifier_reference(&mut self, ident: &'a IdentifierReference) {
@@ -93,11 +156,57 @@ impl<'a> Visit<'a> for ManglerBuilder<'a> {
         let reference_id = self.semantic.declare_reference(reference);
         ident.reference_id.replace(reference_id);
     }
+
+    fn visit_export_named_declaration(&mut self, decl: &'a ExportNamedDeclaration<'a>) {
+        if let Some(decl) = &decl.declaration {
+            self.in_export_declaration = true;
+            self.visit_declaration(decl);
+            self.in_export_declaration = false;
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &'a CallExpression<'a>) {
+        if matches!(&expr.callee, Expression::Identifier(ident) if ident.name == "eval") {
+            self.dynamic_scopes.push(self.semantic.current_scope_id);
+        }
+        for arg in &expr.arguments {
+            self.visit_argument(arg);
+        }
+        self.visit_expression(&expr.callee);
+    }
+
+    fn visit_with_statement(&mut self, stmt: &'a WithStatement<'a>) {
+        self.dynamic_scopes.push(self.semantic.current_scope_id);
+        self.visit_expression(&stmt.object);
+        if let Some(stmt) = &stmt.body {
+            self.visit_statement(stmt);
+        }
+    }
 }
 
 impl<'a> ManglerBuilder<'a> {
     pub fn new(source_text: &'a str, source_type: SourceType) -> Self {
-        Self { semantic: SemanticBuilder::new(source_text, source_type) }
+        Self {
+            semantic: SemanticBuilder::new(source_text, source_type),
+            options: MangleOptions::default(),
+            name_cache: NameCache::default(),
+            in_export_declaration: false,
+            exported_symbols: vec![],
+            dynamic_scopes: vec![],
+        }
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: MangleOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Mangled names of a previous build
+    #[must_use]
+    pub fn with_name_cache(mut self, name_cache: NameCache) -> Self {
+        self.name_cache = name_cache;
+        self
     }
 
     #[must_use]
@@ -109,8 +218,40 @@ impl<'a> ManglerBuilder<'a> {
     /// Mangle the symbol table by computing slots from the scope tree.
     /// A slot is the occurrence index of a binding identifier inside a scope.
     pub fn mangle(self) -> Mangler {
+        let is_module = self.semantic.source_type.is_module();
         let semantic = self.semantic.build2();
         let (mut symbol_table, scope_tree) = semantic.into_symbol_table_and_scope_tree();
+        let options = self.options;
+        let mut name_cache = self.name_cache;
+
+        // Symbols keeping their original name, either by the options or from the name cache
+        let (mut skipped, mut reserved_names) = Self::collect_kept_symbols(
+            &options,
+            &symbol_table,
+            &scope_tree,
+            options.top_level || is_module,
+            &self.exported_symbols,
+            &self.dynamic_scopes,
+        );
+        let kept = skipped.clone();
+        let top_level_bindings = scope_tree.get_bindings(scope_tree.root_scope_id());
+        let unresolved_references = scope_tree.root_unresolved_references();
+        let mut cached_names = vec![];
+        for (name, symbol_id) in top_level_bindings {
+            if !skipped[*symbol_id] && let Some(cached_name) = name_cache.vars.get(name.as_str()) {
+                let cached_name = Atom::from(cached_name.as_str());
+                // A cached name clashing with a global or a kept name of this file is not reused
+                if reserved_names.contains(&cached_name)
+                    || unresolved_references.contains_key(&cached_name)
+                {
+                    continue;
+                }
+                skipped[*symbol_id] = true;
+                cached_names.push((*symbol_id, cached_name));
+            }
+        }
+        // Cached names are reserved even when they are not used by this file
+        reserved_names.extend(name_cache.vars.values().map(|name| Atom::from(name.as_str())));
 
         // Total number of slots for all scopes
         let mut total_number_of_slots: Slot = 0;
@@ -145,24 +286,26 @@ impl<'a> ManglerBuilder<'a> {
         }
 
         let frequencies =
-            Self::tally_slot_frequencies(&symbol_table, total_number_of_slots, &slots);
+            Self::tally_slot_frequencies(&symbol_table, total_number_of_slots, &slots, &skipped);
 
-        let unresolved_references = scope_tree
-            .root_unresolved_references()
-            .keys()
-            // It is unlike to get a 5 letter mangled identifier, which is a lot of slots.
-            // .filter(|name| name.len() < 5)
-            .collect::<Vec<_>>();
+        reserved_names.extend(
+            scope_tree
+                .root_unresolved_references()
+                .keys()
+                // It is unlike to get a 5 letter mangled identifier, which is a lot of slots.
+                // .filter(|name| name.len() < 5)
+                .cloned(),
+        );
 
-        let mut names = Vec::with_capacity(total_number_of_slots);
+        let mut names = Vec::with_capacity(frequencies.len());
 
         let mut count = 0;
-        for _ in 0..total_number_of_slots {
+        for _ in 0..frequencies.len() {
             names.push(loop {
                 let name = Atom::base54(count);
                 count += 1;
-                // Do not mangle keywords and unresolved references
-                if !is_keyword(&name) && !unresolved_references.iter().any(|n| **n == name) {
+                // Do not mangle keywords, unresolved references and reserved names
+                if !is_keyword(&name) && !reserved_names.contains(&name) {
                     break name;
                 }
             });
@@ -211,17 +354,80 @@ impl<'a> ManglerBuilder<'a> {
             }
         }
 
-        Mangler { symbol_table }
+        for (symbol_id, name) in cached_names {
+            symbol_table.set_name(symbol_id, name);
+        }
+
+        for (name, symbol_id) in top_level_bindings {
+            if !kept[*symbol_id] {
+                name_cache
+                    .vars
+                    .insert(name.to_string(), symbol_table.get_name(*symbol_id).to_string());
+            }
+        }
+
+        let top_level_bindings =
+            top_level_bindings.iter().map(|(name, symbol_id)| (name.clone(), *symbol_id)).collect();
+        Mangler { symbol_table, top_level_bindings, name_cache }
+    }
+
+    /// Symbols which are not renamed and the names which can not be used as mangled names,
+    /// the names of the kept symbols are reserved so they are not shadowed by mangled names
+    fn collect_kept_symbols(
+        options: &MangleOptions,
+        symbol_table: &SymbolTable,
+        scope_tree: &ScopeTree,
+        mangle_top_level: bool,
+        exported_symbols: &[SymbolId],
+        dynamic_scopes: &[ScopeId],
+    ) -> (IndexVec<SymbolId, bool>, HashSet<Atom>) {
+        let mut kept: IndexVec<SymbolId, bool> = index_vec![false; symbol_table.len()];
+        for symbol_id in exported_symbols {
+            kept[*symbol_id] = true;
+        }
+
+        // `eval` and `with` can reference the bindings of their scope and all its ancestors
+        let mut kept_scopes: IndexVec<ScopeId, bool> = index_vec![false; scope_tree.len()];
+        for scope_id in dynamic_scopes {
+            for scope_id in scope_tree.ancestors(*scope_id) {
+                kept_scopes[scope_id] = true;
+            }
+        }
+        if !mangle_top_level {
+            kept_scopes[scope_tree.root_scope_id()] = true;
+        }
+        for scope_id in scope_tree.descendants().filter(|scope_id| kept_scopes[*scope_id]) {
+            for symbol_id in scope_tree.get_bindings(scope_id).values() {
+                kept[*symbol_id] = true;
+            }
+        }
+
+        let mut reserved_names =
+            options.reserved.iter().map(|name| Atom::from(name.as_str())).collect::<HashSet<_>>();
+        for symbol_id in symbol_table.iter() {
+            let flag = symbol_table.get_flag(symbol_id);
+            let name = symbol_table.get_name(symbol_id);
+            if kept[symbol_id]
+                || reserved_names.contains(name)
+                || (options.keep_fnames && flag.is_function())
+                || (options.keep_classnames && flag.is_class())
+            {
+                kept[symbol_id] = true;
+                reserved_names.insert(name.clone());
+            }
+        }
+        (kept, reserved_names)
     }
 
     fn tally_slot_frequencies(
         symbol_table: &SymbolTable,
         total_number_of_slots: usize,
         slots: &IndexVec<SymbolId, Slot>,
+        skipped: &IndexVec<SymbolId, bool>,
     ) -> Vec<SlotFrequency> {
         let mut frequencies = vec![SlotFrequency::default(); total_number_of_slots];
         for (symbol_id, slot) in slots.iter_enumerated() {
-            if !symbol_table.get_flag(symbol_id).is_variable() {
+            if !symbol_table.get_flag(symbol_id).is_variable() || skipped[symbol_id] {
                 continue;
             }
             let index = *slot;
@@ -229,6 +435,8 @@ impl<'a> ManglerBuilder<'a> {
             frequencies[index].frequency += symbol_table.get_resolved_references(symbol_id).len();
             frequencies[index].symbol_ids.push(symbol_id);
         }
+        // Slots of kept symbols only do not need a name
+        frequencies.retain(|x| !x.symbol_ids.is_empty());
         frequencies.sort_by_key(|x| (std::cmp::Reverse(x.frequency)));
         frequencies
     }
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Mangled names shared between builds.
+///
+/// Pass the cache of a previous build to [crate::MinifierOptions::name_cache] so chunks
+/// built separately agree on the mangled names, the updated cache is returned in
+/// [crate::MinifierReturn::name_cache].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameCache {
+    /// Original top level name -> mangled top level name
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// Original property name -> mangled property name
+    #[serde(default)]
+    pub props: BTreeMap<String, String>,
+}
+
+impl NameCache {
+    /// # Errors
+    ///
+    /// * When `json` is not a valid name cache.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// # Panics
+    ///
+    /// * When the cache fails to serialize, which never happens for string maps.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
@@ -0,0 +1,181 @@
+//! Property name mangling
+//!
+//! Only properties selected by [ManglePropertiesOptions] are renamed, every occurrence
+//! of a selected name is renamed the same way regardless of the object it belongs to.
+
+use std::collections::{HashMap, HashSet};
+
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_hir::Visit;
+use oxc_span::Atom;
+use regex::Regex;
+
+use super::NameCache;
+
+/// Names with a special meaning to the runtime, they are never mangled
+const ALWAYS_RESERVED: &[&str] = &["constructor", "prototype", "__proto__"];
+
+#[derive(Debug, Clone)]
+pub struct ManglePropertiesOptions {
+    /// Only property names matching the regex are mangled, e.g. `^_`
+    pub regex: Regex,
+    /// Property names which are never mangled, even when they match `regex`
+    pub reserved: Vec<String>,
+}
+
+impl ManglePropertiesOptions {
+    pub fn new(regex: Regex) -> Self {
+        Self { regex, reserved: vec![] }
+    }
+
+    fn should_mangle(&self, name: &str) -> bool {
+        !ALWAYS_RESERVED.contains(&name)
+            && !self.reserved.iter().any(|reserved| reserved == name)
+            && self.regex.is_match(name)
+    }
+}
+
+pub struct PropertyMangler {
+    names: HashMap<Atom, Atom>,
+}
+
+impl PropertyMangler {
+    /// The mangled name of the property, `None` when it is kept as is
+    pub fn get_property_name(&self, name: &Atom) -> Option<&Atom> {
+        self.names.get(name)
+    }
+}
+
+/// Collects the property names of
+///
+/// * static member expressions `a._foo`
+/// * property keys of objects, classes and destructuring `{ _foo: 1 }`
+/// * shorthand assignment targets `({ _foo } = a)`
+///
+/// Quoted names such as `a["_foo"]`, `{ "_foo": 1 }` and `"_foo" in a` are kept as is,
+/// a name appearing in any string literal of the program is not mangled at all.
+pub struct PropertyManglerBuilder<'a> {
+    options: &'a ManglePropertiesOptions,
+
+    /// Names to mangle in order of first appearance
+    candidates: Vec<Atom>,
+
+    /// Number of occurrences of the names to mangle
+    frequencies: HashMap<Atom, usize>,
+
+    /// Names which are kept as is, they can not be used as mangled names
+    unmangled: HashSet<Atom>,
+}
+
+impl<'a> Visit<'a> for PropertyManglerBuilder<'a> {
+    fn visit_static_member_expression(&mut self, expr: &'a StaticMemberExpression<'a>) {
+        self.visit_expression(&expr.object);
+        self.add_name(&expr.property.name);
+    }
+
+    fn visit_string_literal(&mut self, lit: &'a StringLiteral) {
+        self.unmangled.insert(lit.value.clone());
+    }
+
+    fn visit_template_literal(&mut self, lit: &'a TemplateLiteral<'a>) {
+        // `` o[`_foo`] `` is the same as `o['_foo']`
+        if lit.is_no_substitution_template() && let Some(value) = lit.quasi() {
+            self.unmangled.insert(value.clone());
+        }
+        for expr in &lit.expressions {
+            self.visit_expression(expr);
+        }
+    }
+
+    fn visit_property_key(&mut self, key: &'a PropertyKey<'a>) {
+        match key {
+            PropertyKey::Identifier(ident) => self.add_name(&ident.name),
+            PropertyKey::PrivateIdentifier(_) => {}
+            PropertyKey::Expression(expr) => self.visit_expression(expr),
+        }
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &'a AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.add_name(&ident.binding.name);
+        self.visit_identifier_reference(&ident.binding);
+        if let Some(expr) = &ident.init {
+            self.visit_expression(expr);
+        }
+    }
+}
+
+impl<'a> PropertyManglerBuilder<'a> {
+    pub fn new(options: &'a ManglePropertiesOptions) -> Self {
+        Self { options, candidates: vec![], frequencies: HashMap::new(), unmangled: HashSet::new() }
+    }
+
+    /// Mangle the collected property names.
+    /// Names found in `name_cache` keep their previous mangled name unless it is a kept name,
+    /// the newly mangled names are added to `name_cache`.
+    #[must_use]
+    pub fn build(
+        mut self,
+        program: &'a Program<'a>,
+        name_cache: &mut NameCache,
+    ) -> PropertyMangler {
+        self.visit_program(program);
+        self.mangle(name_cache)
+    }
+
+    fn add_name(&mut self, name: &Atom) {
+        if !self.options.should_mangle(name) {
+            self.unmangled.insert(name.clone());
+            return;
+        }
+        let frequency = self.frequencies.entry(name.clone()).or_insert(0);
+        if *frequency == 0 {
+            self.candidates.push(name.clone());
+        }
+        *frequency += 1;
+    }
+
+    fn mangle(self, name_cache: &mut NameCache) -> PropertyMangler {
+        let Self { mut candidates, frequencies, unmangled, .. } = self;
+        candidates.retain(|name| !unmangled.contains(name));
+
+        let mut names = HashMap::with_capacity(candidates.len());
+
+        // Names from previous builds come first so all chunks agree on them,
+        // a cached name clashing with a kept name of this build is mangled again
+        candidates.retain(|name| {
+            let Some(mangled) = name_cache.props.get(name.as_str()) else { return true };
+            let mangled = Atom::from(mangled.as_str());
+            if unmangled.contains(&mangled) {
+                return true;
+            }
+            names.insert(name.clone(), mangled);
+            false
+        });
+
+        let mut used = unmangled;
+        used.extend(name_cache.props.values().map(|name| Atom::from(name.as_str())));
+
+        // The most frequent names get the shorter names,
+        // the sort is stable so the others keep the order of first appearance
+        candidates.sort_by_key(|name| std::cmp::Reverse(frequencies[name]));
+
+        let mut count = 0;
+        for name in candidates {
+            let mangled = loop {
+                let mangled = Atom::base54(count);
+                count += 1;
+                if !used.contains(&mangled) {
+                    break mangled;
+                }
+            };
+            name_cache.props.insert(name.to_string(), mangled.to_string());
+            names.insert(name, mangled);
+        }
+
+        PropertyMangler { names }
+    }
+}
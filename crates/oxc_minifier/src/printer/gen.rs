@@ -2,6 +2,7 @@ use oxc_allocator::{Box, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::hir::*;
 use oxc_hir::precedence;
+use oxc_span::GetSpan;
 use oxc_syntax::{
     operator::{
         AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator,
@@ -44,12 +45,23 @@ impl<'a> Gen for Program<'a> {
             hashbang.gen(p, ctx);
         }
         for directive in &self.directives {
+            p.print_statement_separator();
+            p.print_comments_before(directive.span.start);
             directive.gen(p, ctx);
         }
         for stmt in &self.body {
-            p.print_semicolon_if_needed();
+            p.print_statement_separator();
             stmt.gen(p, ctx);
         }
+        // Comments after the last statement
+        if !p.comments.is_empty() {
+            p.print_semicolon_if_needed();
+            p.print_comments_before(u32::MAX);
+            p.code.pop();
+        }
+        if p.options.beautify {
+            p.print_semicolon_if_needed();
+        }
     }
 }
 
@@ -57,6 +69,8 @@ impl<'a> Gen for Hashbang<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"#!");
         p.print_str(self.value.as_bytes());
+        // The value ends with the line terminator when code follows
+        p.line_start = p.code_len();
     }
 }
 
@@ -71,6 +85,10 @@ impl<'a> Gen for Directive<'a> {
 
 impl<'a> Gen for Statement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
+        if p.options.comments && self.span().size() > 0 {
+            p.print_comments_before(self.span().start);
+        }
+        p.add_source_mapping(self.span());
         match self {
             Self::BlockStatement(stmt) => stmt.gen(p, ctx),
             Self::BreakStatement(stmt) => stmt.gen(p, ctx),
@@ -125,19 +143,21 @@ impl<'a> Gen for IfStatement<'a> {
 
 fn print_if(if_stmt: &IfStatement<'_>, p: &mut Printer, ctx: Context) {
     p.print_str(b"if");
+    p.print_soft_space();
     p.print(b'(');
     if_stmt.test.gen_expr(p, Precedence::lowest(), Context::default());
     p.print(b')');
+    p.print_soft_space();
 
     match &if_stmt.consequent {
         Some(Statement::BlockStatement(block)) => {
             p.print_block1(block, ctx);
         }
         Some(stmt) if wrap_to_avoid_ambiguous_else(stmt) => {
-            p.print(b'{');
+            p.print_block_start();
+            p.print_statement_separator();
             stmt.gen(p, ctx);
-            p.print(b'}');
-            p.needs_semicolon = false;
+            p.print_block_end();
         }
         Some(stmt) => {
             stmt.gen(p, ctx);
@@ -205,6 +225,7 @@ impl<'a> Gen for BlockStatement<'a> {
 impl<'a> Gen for ForStatement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"for");
+        p.print_soft_space();
         p.print(b'(');
 
         if let Some(init) = self.init.as_ref() {
@@ -220,16 +241,19 @@ impl<'a> Gen for ForStatement<'a> {
         p.print_semicolon();
 
         if let Some(test) = self.test.as_ref() {
+            p.print_soft_space();
             test.gen_expr(p, Precedence::lowest(), Context::default());
         }
 
         p.print_semicolon();
 
         if let Some(update) = self.update.as_ref() {
+            p.print_soft_space();
             update.gen_expr(p, Precedence::lowest(), Context::default());
         }
 
         p.print(b')');
+        p.print_soft_space();
         self.body.gen(p, ctx);
     }
 }
@@ -237,6 +261,7 @@ impl<'a> Gen for ForStatement<'a> {
 impl<'a> Gen for ForInStatement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"for");
+        p.print_soft_space();
         p.print(b'(');
         self.left.gen(p, ctx);
         p.print(b' ');
@@ -244,6 +269,7 @@ impl<'a> Gen for ForInStatement<'a> {
         p.print(b' ');
         self.right.gen_expr(p, Precedence::lowest(), Context::default());
         p.print(b')');
+        p.print_soft_space();
         self.body.gen(p, ctx);
     }
 }
@@ -254,6 +280,7 @@ impl<'a> Gen for ForOfStatement<'a> {
         if self.r#await {
             p.print_str(b" await");
         }
+        p.print_soft_space();
         p.print(b'(');
         self.left.gen(p, ctx);
         p.print(b' ');
@@ -261,6 +288,7 @@ impl<'a> Gen for ForOfStatement<'a> {
         p.print(b' ');
         self.right.gen_expr(p, Precedence::Assign, Context::default());
         p.print(b')');
+        p.print_soft_space();
         self.body.gen(p, ctx);
     }
 }
@@ -277,9 +305,11 @@ impl<'a> Gen for ForStatementLeft<'a> {
 impl<'a> Gen for WhileStatement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"while");
+        p.print_soft_space();
         p.print(b'(');
         self.test.gen_expr(p, Precedence::lowest(), Context::default());
         p.print(b')');
+        p.print_soft_space();
         self.body.gen(p, ctx);
     }
 }
@@ -294,7 +324,9 @@ impl<'a> Gen for DoWhileStatement<'a> {
             self.body.gen(p, ctx);
             p.print_semicolon_if_needed();
         }
+        p.print_soft_space();
         p.print_str(b"while");
+        p.print_soft_space();
         p.print(b'(');
         self.test.gen_expr(p, Precedence::lowest(), Context::default());
         p.print(b')');
@@ -327,21 +359,21 @@ impl Gen for BreakStatement {
 impl<'a> Gen for SwitchStatement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"switch");
+        p.print_soft_space();
         p.print(b'(');
         self.discriminant.gen_expr(p, Precedence::lowest(), Context::default());
         p.print(b')');
-        p.print(b'{');
+        p.print_block_start();
         for case in &self.cases {
+            p.print_statement_separator();
             case.gen(p, ctx);
         }
-        p.print(b'}');
-        p.needs_semicolon = false;
+        p.print_block_end();
     }
 }
 
 impl<'a> Gen for SwitchCase<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print_semicolon_if_needed();
         match &self.test {
             Some(test) => {
                 p.print_str(b"case");
@@ -350,11 +382,13 @@ impl<'a> Gen for SwitchCase<'a> {
             }
             None => p.print_str(b"default"),
         }
-        p.print_colon();
+        p.print(b':');
+        p.indent += 1;
         for item in &self.consequent {
-            p.print_semicolon_if_needed();
+            p.print_statement_separator();
             item.gen(p, ctx);
         }
+        p.indent -= 1;
     }
 }
 
@@ -382,8 +416,10 @@ impl<'a> Gen for TryStatement<'a> {
         p.print_str(b"try");
         p.print_block1(&self.block, ctx);
         if let Some(handler) = &self.handler {
+            p.print_soft_space();
             p.print_str(b"catch");
             if let Some(param) = &handler.param {
+                p.print_soft_space();
                 p.print_str(b"(");
                 param.gen(p, ctx);
                 p.print_str(b")");
@@ -391,6 +427,7 @@ impl<'a> Gen for TryStatement<'a> {
             p.print_block1(&handler.body, ctx);
         }
         if let Some(finalizer) = &self.finalizer {
+            p.print_soft_space();
             p.print_str(b"finally");
             p.print_block1(finalizer, ctx);
         }
@@ -409,9 +446,11 @@ impl<'a> Gen for ThrowStatement<'a> {
 impl<'a> Gen for WithStatement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"with");
+        p.print_soft_space();
         p.print(b'(');
         self.object.gen_expr(p, Precedence::lowest(), Context::default());
         p.print(b')');
+        p.print_soft_space();
         self.body.gen(p, ctx);
     }
 }
@@ -505,8 +544,9 @@ impl<'a> Gen for Function<'a> {
 
 impl<'a> Gen for FunctionBody<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print(b'{');
+        p.print_block_start();
         for directive in &self.directives {
+            p.print_statement_separator();
             directive.gen(p, ctx);
         }
         p.needs_semicolon = if let Some(Statement::ExpressionStatement(expr_stmt)) = self.statements.get(0)
@@ -516,11 +556,10 @@ impl<'a> Gen for FunctionBody<'a> {
                 false
             };
         for stmt in &self.statements {
-            p.print_semicolon_if_needed();
+            p.print_statement_separator();
             stmt.gen(p, ctx);
         }
-        p.print(b'}');
-        p.needs_semicolon = false;
+        p.print_block_end();
     }
 }
 
@@ -647,8 +686,16 @@ impl<'a> Gen for ExportNamedDeclaration<'a> {
             Some(decl) => decl.gen(p, ctx),
             None => {
                 p.print(b'{');
-                if !self.specifiers.is_empty() {
+                if self.source.is_some() {
                     p.print_list(&self.specifiers, ctx);
+                } else {
+                    // The local bindings may be mangled, `export { a as foo }`
+                    for (index, specifier) in self.specifiers.iter().enumerate() {
+                        if index != 0 {
+                            p.print_comma();
+                        }
+                        gen_local_export_specifier(specifier, p, ctx);
+                    }
                 }
                 p.print(b'}');
                 if let Some(source) = &self.source {
@@ -671,11 +718,29 @@ impl Gen for ExportSpecifier {
     }
 }
 
+fn gen_local_export_specifier(specifier: &ExportSpecifier, p: &mut Printer, ctx: Context) {
+    let local_name = match &specifier.local {
+        ModuleExportName::Identifier(ident) => {
+            p.mangler.as_ref().and_then(|mangler| mangler.get_top_level_name(&ident.name)).cloned()
+        }
+        ModuleExportName::StringLiteral(_) => None,
+    };
+    let Some(local_name) = local_name else {
+        specifier.gen(p, ctx);
+        return;
+    };
+    p.print_identifier(&local_name);
+    if local_name != *specifier.exported.name() {
+        p.print_str(b" as ");
+        specifier.exported.gen(p, ctx);
+    }
+}
+
 impl Gen for ModuleExportName {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         match self {
             Self::Identifier(identifier) => {
-                p.print_str(identifier.name.as_bytes());
+                p.print_identifier(&identifier.name);
             }
             Self::StringLiteral(literal) => literal.gen(p, ctx),
         };
@@ -723,6 +788,10 @@ impl<'a> Gen for ExportDefaultDeclarationKind<'a> {
 
 impl<'a> GenExpr for Expression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
+        // Identifiers add their own mapping with the original name
+        if !matches!(self, Self::Identifier(_)) {
+            p.add_source_mapping(self.span());
+        }
         match self {
             Self::BooleanLiteral(lit) => lit.gen(p, ctx),
             Self::NullLiteral(lit) => lit.gen(p, ctx),
@@ -766,28 +835,31 @@ impl Gen for IdentifierReference {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         if let Some(mangler) = &p.mangler
             && let Some(name) = mangler.get_reference_name(self.reference_id.clone().into_inner()) {
-            p.print_str(name.clone().as_bytes());
+            let name = name.clone();
+            p.add_source_mapping_with_name(self.span, (name != self.name).then_some(self.name.as_str()));
+            p.print_identifier(&name);
         } else {
-            p.print_str(self.name.as_bytes());
+            p.add_source_mapping(self.span);
+            p.print_identifier(&self.name);
         }
     }
 }
 
 impl Gen for IdentifierName {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print_str(self.name.as_bytes());
+        p.print_identifier(&self.name);
     }
 }
 
 impl Gen for BindingIdentifier {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print_symbol(self.symbol_id.clone().into_inner(), &self.name);
+        p.print_symbol(self.span, self.symbol_id.clone().into_inner(), &self.name);
     }
 }
 
 impl Gen for LabelIdentifier {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print_str(self.name.as_bytes());
+        p.print_identifier(&self.name);
     }
 }
 
@@ -919,7 +991,7 @@ impl Gen for RegExpLiteral {
             p.print(b' ');
         }
         p.print(b'/');
-        p.print_str(self.regex.pattern.as_bytes());
+        p.print_raw_str(self.regex.pattern.as_str());
         p.print(b'/');
         p.print_str(self.regex.flags.to_string().as_bytes());
         p.prev_reg_exp_end = p.code().len();
@@ -928,11 +1000,7 @@ impl Gen for RegExpLiteral {
 
 impl Gen for StringLiteral {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        p.print(b'\'');
-        for c in self.value.chars() {
-            p.print_str(c.escape_default().to_string().as_bytes());
-        }
-        p.print(b'\'');
+        p.print_quoted_str(&self.value);
     }
 }
 
@@ -977,7 +1045,7 @@ impl<'a> GenExpr for StaticMemberExpression<'a> {
             p.print(b' ');
         }
         p.print(b'.');
-        self.property.gen(p, ctx);
+        p.print_property_name(&self.property.name);
     }
 }
 
@@ -995,6 +1063,9 @@ impl<'a> GenExpr for PrivateFieldExpression<'a> {
 impl<'a> GenExpr for CallExpression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_pure_annotation();
+            }
             self.callee.gen_expr(p, self.precedence(), ctx);
             if self.optional {
                 p.print_str(b"?.");
@@ -1120,7 +1191,7 @@ impl<'a> Gen for ObjectProperty<'a> {
 impl<'a> Gen for PropertyKey<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         match self {
-            Self::Identifier(ident) => ident.gen(p, ctx),
+            Self::Identifier(ident) => p.print_property_name(&ident.name),
             Self::PrivateIdentifier(ident) => ident.gen(p, ctx),
             Self::Expression(expr) => expr.gen_expr(p, Precedence::Assign, Context::default()),
         }
@@ -1152,7 +1223,9 @@ impl<'a> GenExpr for ArrowExpression<'a> {
             p.wrap(!nowrap, |p| {
                 self.params.gen(p, ctx);
             });
+            p.print_soft_space();
             p.print_str(b"=>");
+            p.print_soft_space();
             if self.expression {
                 if let Statement::ExpressionStatement(stmt) = &self.body.statements[0] {
                     p.start_of_arrow_expr = p.code_len();
@@ -1227,6 +1300,7 @@ impl<'a> GenExpr for BinaryExpression<'a> {
         let wrap = precedence > self.precedence() || wrap_in;
         p.wrap(wrap, |p| {
             self.left.gen_expr(p, self.precedence(), ctx);
+            p.print_soft_space();
             if self.operator.is_keyword() {
                 p.print_space_before_identifier();
             }
@@ -1248,6 +1322,7 @@ impl Gen for BinaryOperator {
             p.print_str(operator);
             p.prev_op = Some(op);
             p.prev_op_end = p.code().len();
+            p.print_soft_space();
         }
     }
 }
@@ -1271,7 +1346,9 @@ impl<'a> GenExpr for LogicalExpression<'a> {
         );
         p.wrap(mixed || (precedence > self.precedence()), |p| {
             self.left.gen_expr(p, self.precedence(), ctx);
+            p.print_soft_space();
             p.print_str(self.operator.as_str().as_bytes());
+            p.print_soft_space();
             let precedence = match self.operator {
                 LogicalOperator::And | LogicalOperator::Coalesce => Precedence::BitwiseOr,
                 LogicalOperator::Or => Precedence::LogicalAnd,
@@ -1286,9 +1363,13 @@ impl<'a> GenExpr for ConditionalExpression<'a> {
         let wrap = precedence > self.precedence();
         p.wrap(wrap, |p| {
             self.test.gen_expr(p, self.precedence(), ctx);
+            p.print_soft_space();
             p.print(b'?');
+            p.print_soft_space();
             self.consequent.gen_expr(p, Precedence::Assign, ctx.and_in(true));
+            p.print_soft_space();
             p.print(b':');
+            p.print_soft_space();
             self.alternate.gen_expr(p, Precedence::Assign, ctx.union_in_if(wrap));
         });
     }
@@ -1307,7 +1388,9 @@ impl<'a> GenExpr for AssignmentExpression<'a> {
             );
         p.wrap(wrap || precedence > self.precedence(), |p| {
             self.left.gen(p, ctx);
+            p.print_soft_space();
             p.print_str(self.operator.as_str().as_bytes());
+            p.print_soft_space();
             self.right.gen_expr(p, Precedence::Assign, ctx);
         });
     }
@@ -1411,6 +1494,16 @@ impl<'a> Gen for AssignmentTargetProperty<'a> {
 
 impl<'a> Gen for AssignmentTargetPropertyIdentifier<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
+        // `({ _a } = b)` -> `({ a: _a } = b)` when the property is mangled
+        if let Some(mangled) = p
+            .property_mangler
+            .as_ref()
+            .and_then(|mangler| mangler.get_property_name(&self.binding.name))
+        {
+            let mangled = mangled.clone();
+            p.print_identifier(&mangled);
+            p.print_colon();
+        }
         self.binding.gen(p, ctx);
         if let Some(expr) = &self.init {
             p.print_equal();
@@ -1423,7 +1516,7 @@ impl<'a> Gen for AssignmentTargetPropertyProperty<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         match &self.name {
             PropertyKey::Identifier(ident) => {
-                ident.gen(p, ctx);
+                p.print_property_name(&ident.name);
             }
             PropertyKey::PrivateIdentifier(ident) => {
                 ident.gen(p, ctx);
@@ -1465,7 +1558,7 @@ impl<'a> Gen for TemplateLiteral<'a> {
         let mut expressions = self.expressions.iter();
 
         for quasi in &self.quasis {
-            p.print_str(quasi.value.raw.as_bytes());
+            p.print_raw_str(quasi.value.raw.as_str());
 
             if let Some(expr) = expressions.next() {
                 p.print_str(b"${");
@@ -1512,6 +1605,9 @@ impl<'a> GenExpr for ChainExpression<'a> {
 impl<'a> GenExpr for NewExpression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_pure_annotation();
+            }
             p.print_str(b"new ");
             self.callee.gen_expr(p, self.precedence(), ctx);
             p.wrap(true, |p| {
@@ -1544,9 +1640,9 @@ impl<'a> Gen for Class<'a> {
                 p.print_str(b" extends ");
                 super_class.gen_expr(p, Precedence::Call, Context::default());
             }
-            p.print(b'{');
+            p.print_block_start();
             for item in &self.body.body {
-                p.print_semicolon_if_needed();
+                p.print_statement_separator();
                 item.gen(p, ctx);
                 if matches!(
                     item,
@@ -1556,8 +1652,7 @@ impl<'a> Gen for Class<'a> {
                 } else {
                 }
             }
-            p.needs_semicolon = false;
-            p.print(b'}');
+            p.print_block_end();
         });
     }
 }
@@ -1767,13 +1862,12 @@ impl<'a> Gen for JSXFragment<'a> {
 impl<'a> Gen for StaticBlock<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print_str(b"static");
-        p.print(b'{');
+        p.print_block_start();
         for stmt in &self.body {
-            p.print_semicolon_if_needed();
+            p.print_statement_separator();
             stmt.gen(p, ctx);
         }
-        p.needs_semicolon = false;
-        p.print(b'}');
+        p.print_block_end();
     }
 }
 
@@ -1858,7 +1952,7 @@ impl<'a> Gen for AccessorProperty<'a> {
 impl Gen for PrivateIdentifier {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         p.print(b'#');
-        p.print_str(self.name.as_bytes());
+        p.print_identifier(&self.name);
     }
 }
 
@@ -1897,7 +1991,7 @@ impl<'a> Gen for BindingProperty<'a> {
         if self.computed {
             p.print(b']');
         }
-        p.print(b':');
+        p.print_colon();
         self.value.gen(p, ctx);
     }
 }
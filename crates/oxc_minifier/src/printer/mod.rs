@@ -9,10 +9,12 @@ mod operator;
 
 use std::{rc::Rc, str::from_utf8_unchecked};
 
+use oxc_ast::Trivias;
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::hir::*;
 use oxc_hir::precedence;
 use oxc_semantic::{SymbolId, SymbolTable};
+use oxc_sourcemap::{SourceMap, SourceMapBuilder};
 use oxc_span::{Atom, Span};
 use oxc_syntax::{
     identifier::is_identifier_part,
@@ -27,19 +29,45 @@ use self::{
     gen::{Gen, GenExpr},
     operator::Operator,
 };
-use crate::mangler::Mangler;
+use crate::mangler::{Mangler, PropertyMangler};
 
 #[derive(Debug, Default, Clone, Copy)]
-pub struct PrinterOptions;
+pub struct PrinterOptions {
+    /// Print one statement per line with indentation and spaces around operators,
+    /// for reading and debugging the minified code
+    pub beautify: bool,
+
+    /// Escape non-ASCII characters in strings, identifiers, templates and regexes
+    pub ascii_only: bool,
+
+    /// Keep legal comments, i.e. `/*! ... */` and comments containing `@license` or `@preserve`
+    pub comments: bool,
+
+    /// Break lines longer than this after a `,` or `;`, `0` for no limit
+    pub max_line_len: usize,
+}
 
 pub struct Printer {
     options: PrinterOptions,
 
     mangler: Option<Mangler>,
 
+    property_mangler: Option<PropertyMangler>,
+
+    source_map: Option<SourceMapBuilder>,
+
+    /// Legal comments to print, as (start in the source text, comment text) in source order
+    comments: Vec<(u32, String)>,
+
     /// Output Code
     code: Vec<u8>,
 
+    /// Start of the current line in `code`, for `PrinterOptions::max_line_len`
+    line_start: usize,
+
+    /// Indentation level for `PrinterOptions::beautify`
+    indent: usize,
+
     // states
     prev_op_end: usize,
     prev_reg_exp_end: usize,
@@ -55,6 +83,11 @@ pub struct Printer {
     start_of_default_export: usize,
 }
 
+pub struct PrinterReturn {
+    pub code: String,
+    pub source_map: Option<SourceMap>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Separator {
     Comma,
@@ -72,7 +105,12 @@ impl Printer {
         Self {
             options,
             mangler: None,
+            property_mangler: None,
+            source_map: None,
+            comments: vec![],
             code: Vec::with_capacity(capacity),
+            line_start: 0,
+            indent: 0,
             needs_semicolon: false,
             need_space_before_dot: 0,
             prev_op_end: 0,
@@ -88,9 +126,49 @@ impl Printer {
         self.mangler = Some(mangler);
     }
 
-    pub fn build(mut self, program: &Program<'_>) -> String {
+    pub fn with_property_mangler(&mut self, mangler: PropertyMangler) {
+        self.property_mangler = Some(mangler);
+    }
+
+    /// Generate a source map for `source_text`, which is the text the HIR was lowered from.
+    pub fn with_source_map(&mut self, source_name: &str, source_text: &str) {
+        self.source_map = Some(SourceMapBuilder::new(source_name, source_text));
+    }
+
+    /// Collect the legal comments of `source_text` when `PrinterOptions::comments` is enabled.
+    /// Each comment is printed before the first statement following it.
+    pub fn with_comments(&mut self, source_text: &str, trivias: &Trivias) {
+        if !self.options.comments {
+            return;
+        }
+        self.comments = trivias
+            .comments()
+            .iter()
+            .filter_map(|(start, comment)| {
+                let text = &source_text[*start as usize..comment.end() as usize];
+                let is_legal = text.starts_with('!')
+                    || text.contains("@license")
+                    || text.contains("@preserve");
+                if !is_legal {
+                    return None;
+                }
+                let text = if comment.is_multi_line() {
+                    format!("/*{text}*/")
+                } else {
+                    // The span of a single line comment includes the line terminator
+                    format!("//{}", text.trim_end_matches(['\n', '\r', '\u{2028}', '\u{2029}']))
+                };
+                Some((*start, text))
+            })
+            .collect();
+        // Printed from the back
+        self.comments.reverse();
+    }
+
+    pub fn build(mut self, program: &Program<'_>) -> PrinterReturn {
         program.gen(&mut self, Context::default());
-        self.into_code()
+        let source_map = self.source_map.take().map(SourceMapBuilder::into_source_map);
+        PrinterReturn { code: self.into_code(), source_map }
     }
 
     fn into_code(self) -> String {
@@ -117,10 +195,189 @@ impl Printer {
 
     fn print_semicolon(&mut self) {
         self.print(b';');
+        self.print_line_break_if_too_long();
     }
 
     fn print_comma(&mut self) {
         self.print(b',');
+        if !self.print_line_break_if_too_long() {
+            self.print_soft_space();
+        }
+    }
+
+    fn print_newline(&mut self) {
+        self.print(b'\n');
+        self.line_start = self.code.len();
+    }
+
+    /// Break the line once it is longer than `PrinterOptions::max_line_len`,
+    /// only called after a `,` or `;` where a line break never changes the meaning of the code
+    fn print_line_break_if_too_long(&mut self) -> bool {
+        let too_long = self.options.max_line_len > 0
+            && self.code.len() - self.line_start > self.options.max_line_len;
+        if too_long {
+            self.print_newline();
+        }
+        too_long
+    }
+
+    /// A space printed only when beautifying
+    fn print_soft_space(&mut self) {
+        if self.options.beautify && !matches!(self.code.last(), None | Some(b' ' | b'\n')) {
+            self.print(b' ');
+        }
+    }
+
+    fn print_indent(&mut self) {
+        if !self.options.beautify {
+            return;
+        }
+        for _ in 0..self.indent {
+            self.print_str(b"  ");
+        }
+    }
+
+    /// Called before each statement of a statement list,
+    /// beautified statements start on a new line
+    fn print_statement_separator(&mut self) {
+        self.print_semicolon_if_needed();
+        if self.options.beautify && !self.code.is_empty() {
+            if self.code.last() != Some(&b'\n') {
+                self.print_newline();
+            }
+            self.print_indent();
+        }
+    }
+
+    /// `/*#__PURE__*/` before a pure call or `new`, so that bundlers can still remove it.
+    /// A statement, arrow body or default export starting with the call now starts after the
+    /// annotation, e.g. `/*#__PURE__*/(function(){})()` keeps its parentheses.
+    fn print_pure_annotation(&mut self) {
+        // `a/ /*#__PURE__*/f()` is not a line comment
+        if self.code.last() == Some(&b'/') {
+            self.print(b' ');
+        }
+        let n = self.code_len();
+        self.print_str(b"/*#__PURE__*/");
+        let end = self.code_len();
+        for start in [
+            &mut self.start_of_stmt,
+            &mut self.start_of_arrow_expr,
+            &mut self.start_of_default_export,
+        ] {
+            if *start == n {
+                *start = end;
+            }
+        }
+    }
+
+    /// Print the legal comments found before `start` in the source text
+    fn print_comments_before(&mut self, start: u32) {
+        while let Some((comment_start, _)) = self.comments.last() && *comment_start < start {
+            let (_, comment) = self.comments.pop().unwrap();
+            if self.code[self.line_start..].iter().any(|b| *b != b' ') {
+                self.print_newline();
+                self.print_indent();
+            }
+            self.print_str(comment.as_bytes());
+            self.print_newline();
+            self.print_indent();
+        }
+    }
+
+    fn print_block_start(&mut self) {
+        self.print_soft_space();
+        self.print(b'{');
+        self.indent += 1;
+    }
+
+    /// Closes a block opened by `print_block_start`, the last statement
+    /// of the block is terminated only when beautifying
+    fn print_block_end(&mut self) {
+        self.indent -= 1;
+        if self.options.beautify && self.code.last() != Some(&b'{') {
+            self.print_semicolon_if_needed();
+            self.print_newline();
+            self.print_indent();
+        }
+        self.needs_semicolon = false;
+        self.print(b'}');
+    }
+
+    /// Print an identifier, escaping non-ASCII characters with `PrinterOptions::ascii_only`
+    fn print_identifier(&mut self, name: &str) {
+        if !self.options.ascii_only || name.is_ascii() {
+            self.print_str(name.as_bytes());
+            return;
+        }
+        for c in name.chars() {
+            if c.is_ascii() {
+                self.print(c as u8);
+            } else if c as u32 <= 0xFFFF {
+                self.print_str(format!("\\u{:04X}", c as u32).as_bytes());
+            } else {
+                self.print_str(format!("\\u{{{:X}}}", c as u32).as_bytes());
+            }
+        }
+    }
+
+    /// Print the source of a regex or the raw text of a template literal,
+    /// escaping non-ASCII characters with `PrinterOptions::ascii_only`
+    fn print_raw_str(&mut self, s: &str) {
+        if !self.options.ascii_only || s.is_ascii() {
+            self.print_str(s.as_bytes());
+            return;
+        }
+        let mut escaped = false;
+        for c in s.chars() {
+            if c.is_ascii() {
+                self.print(c as u8);
+                escaped = c == '\\' && !escaped;
+                continue;
+            }
+            let mut buf = [0; 2];
+            for (i, unit) in c.encode_utf16(&mut buf).iter().enumerate() {
+                // `\é` is already an escape, only the `u` of `\u00E9` is missing
+                if i > 0 || !escaped {
+                    self.print(b'\\');
+                }
+                self.print_str(format!("u{unit:04X}").as_bytes());
+            }
+            escaped = false;
+        }
+    }
+
+    /// Print a single quoted string literal
+    fn print_quoted_str(&mut self, s: &str) {
+        self.print(b'\'');
+        for c in s.chars() {
+            match c {
+                '\'' => self.print_str(b"\\'"),
+                '\\' => self.print_str(b"\\\\"),
+                '\n' => self.print_str(b"\\n"),
+                '\r' => self.print_str(b"\\r"),
+                '\t' => self.print_str(b"\\t"),
+                // Line terminators in string literals are only valid since ES2019
+                '\u{2028}' | '\u{2029}' => {
+                    self.print_str(format!("\\u{:04X}", c as u32).as_bytes());
+                }
+                c if c.is_ascii_control() => {
+                    self.print_str(format!("\\x{:02X}", c as u32).as_bytes());
+                }
+                c if c.is_ascii() => self.print(c as u8),
+                c if self.options.ascii_only => {
+                    let mut buf = [0; 2];
+                    for unit in c.encode_utf16(&mut buf) {
+                        self.print_str(format!("\\u{unit:04X}").as_bytes());
+                    }
+                }
+                c => {
+                    let mut buf = [0; 4];
+                    self.print_str(c.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+        self.print(b'\'');
     }
 
     fn print_space_before_operator(&mut self, next: Operator) {
@@ -187,10 +444,13 @@ impl Printer {
 
     fn print_colon(&mut self) {
         self.print(b':');
+        self.print_soft_space();
     }
 
     fn print_equal(&mut self) {
+        self.print_soft_space();
         self.print(b'=');
+        self.print_soft_space();
     }
 
     fn print_sequence<T: Gen>(&mut self, items: &[T], separator: Separator, ctx: Context) {
@@ -199,7 +459,7 @@ impl Printer {
             item.gen(self, ctx);
             match separator {
                 Separator::Semicolon => self.print_semicolon(),
-                Separator::Comma => self.print(b','),
+                Separator::Comma => self.print_comma(),
                 Separator::None => {}
             }
             if index != len - 1 {}
@@ -215,13 +475,12 @@ impl Printer {
     }
 
     fn print_block1(&mut self, stmt: &BlockStatement<'_>, ctx: Context) {
-        self.print(b'{');
+        self.print_block_start();
         for item in &stmt.body {
-            self.print_semicolon_if_needed();
+            self.print_statement_separator();
             item.gen(self, ctx);
         }
-        self.needs_semicolon = false;
-        self.print(b'}');
+        self.print_block_end();
     }
 
     fn print_block<T: Gen>(&mut self, items: &[T], separator: Separator, ctx: Context) {
@@ -250,15 +509,45 @@ impl Printer {
         }
     }
 
-    fn print_symbol(&mut self, symbol_id: SymbolId, fallback: &Atom) {
+    /// Map the current output position to the start of `span`.
+    /// Nodes created by the compressor have empty spans and are skipped.
+    fn add_source_mapping(&mut self, span: Span) {
+        self.add_source_mapping_with_name(span, None);
+    }
+
+    /// Same as `add_source_mapping`, also recording the original `name` for renamed identifiers.
+    fn add_source_mapping_with_name(&mut self, span: Span, name: Option<&str>) {
+        if let Some(source_map) = &mut self.source_map && span.size() > 0 {
+            let output = unsafe { from_utf8_unchecked(&self.code) };
+            source_map.add_mapping(output, span.start, name);
+        }
+    }
+
+    fn print_symbol(&mut self, span: Span, symbol_id: SymbolId, fallback: &Atom) {
         if let Some(mangler) = &self.mangler {
-            let name = mangler.get_symbol_name(symbol_id);
-            self.print_str(name.clone().as_bytes());
+            let name = mangler.get_symbol_name(symbol_id).clone();
+            self.add_source_mapping_with_name(
+                span,
+                (name != *fallback).then_some(fallback.as_str()),
+            );
+            self.print_identifier(&name);
         } else {
-            self.print_str(fallback.as_bytes());
+            self.add_source_mapping(span);
+            self.print_identifier(fallback);
         }
     }
 
+    /// Print the name of a property, mangled by the property mangler if any
+    fn print_property_name(&mut self, name: &Atom) {
+        let name = self
+            .property_mangler
+            .as_ref()
+            .and_then(|mangler| mangler.get_property_name(name))
+            .unwrap_or(name)
+            .clone();
+        self.print_identifier(&name);
+    }
+
     fn wrap<F: FnMut(&mut Self)>(&mut self, wrap: bool, mut f: F) {
         if wrap {
             self.print(b'(');
@@ -0,0 +1,127 @@
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+use super::test;
+
+fn test_module(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(expected, minified, "for source {source_text}");
+}
+
+#[test]
+fn unreachable() {
+    test("function f() { return 1; foo(); }", "function f(){return 1}");
+    test("function f() { throw 1; foo(); }", "function f(){throw 1}");
+    test("for (;;) { break; foo(); }", "for(;;)break");
+    test("for (;;) { continue; foo(); }", "for(;;)continue");
+    // Hoisted declarations are kept, without initializers
+    test(
+        "function f() { return g(x); var x = 1; function g(a) { return a } }",
+        "function f(){return g(x);var x;function g(a){return a}}",
+    );
+    test(
+        "function f() { return x; if (a) { var x = 1 } let y = 2 }",
+        "function f(){return x;if(a){var x=1}}",
+    );
+}
+
+#[test]
+fn dead_branch() {
+    test("if (false) { foo() }", "");
+    test("if (true) { foo() }", "foo()");
+    test("if (false) foo(); else { bar(); baz() }", "bar(),baz()");
+    test("if (!1) foo(); else bar()", "bar()");
+    test("if ('') foo()", "");
+    test("var a; if (a && false) foo(); else bar()", "var a;bar()");
+    // Reading an undeclared variable throws
+    test("if (a && false) foo(); else bar()", "a&&!1?foo():bar()");
+    test("if (a() && false) foo(); else bar()", "a()&&!1?foo():bar()");
+    test("if (x && true) foo()", "x&&!0&&foo()");
+    // `var` in the branch not taken is hoisted
+    test("if (false) { var x = 1 }", "if(!1){var x=1}");
+    // Lexical declarations keep their block
+    test("if (true) { let x = 1; foo(x) }", "{let x=1;foo(x)}");
+}
+
+#[test]
+fn dead_branch_block_functions() {
+    // Functions in blocks also declare a `var` in sloppy mode
+    test("if (false) { function f() {} }", "var f");
+    test("if (false) { function f() {} } else foo()", "var f;foo()");
+    test(
+        "function g() { if (false) { { function f() {} } } return f }",
+        "function g(){var f;return f}",
+    );
+    test("if (false) { async function f() {} }", "");
+    // No `var` when it would conflict with a lexical declaration
+    test("let f; if (false) { function f() {} }", "let f");
+    test("{ let f; if (false) { function f() {} } }", "{let f}");
+    // Functions are block scoped in strict mode
+    test("'use strict'; if (false) { function f() {} }", "\"use strict\";");
+    test(
+        "function g() { 'use strict'; if (false) { function f() {} } }",
+        "function g(){\"use strict\";}",
+    );
+    test("class A { m() { if (false) { function f() {} } } }", "class A{m(){}}");
+    test_module("if (false) { function f() {} }", "");
+}
+
+#[test]
+fn unused_declarations() {
+    test("function f() { var x = 1; }", "function f(){}");
+    test("function f() { var x = 1, y = g(), z; return z }", "function f(){var y=g(),z;return z}");
+    test("function f() { function g() {} }", "function f(){}");
+    test("function f() { let x = function () {}; const y = [1, 2] }", "function f(){}");
+    test("function f() { let x = () => {} }", "function f(){}");
+    test("function f() { let x = g() }", "function f(){let x=g()}");
+    test("function f() { let { a } = b }", "function f(){let {a:a}=b}");
+    test("function f() { var x; x = 1 }", "function f(){var x;x=1}");
+    // Top level declarations of scripts are global
+    test("var x = 1; function f() {}", "var x=1;function f(){}");
+    test("function init() {}\nvar counter = 0;", "function init(){}var counter=0");
+    test("let x = 1; const y = 2; class C {}", "let x=1;const y=2;class C{}");
+    // Redeclared parameters are observable through `arguments`
+    test(
+        "function f(a) { var a = 1; return arguments }",
+        "function f(a){var a=1;return arguments}",
+    );
+}
+
+#[test]
+fn unused_declarations_in_blocks() {
+    // A block without lexical declarations is flattened into the case
+    test(
+        "function f() { switch (a) { case b: { var c; return c } default: g() } }",
+        "function f(){switch(a){case b:var c;return c;default:g()}}",
+    );
+    // A local `undefined` is an ordinary variable
+    test(
+        "var undefined = 1; function f() { var undefined = 2; var x = undefined; }",
+        "var undefined=1;function f(){}",
+    );
+}
+
+#[test]
+fn unused_declarations_in_module() {
+    test_module("var x = 1; function f() {} export const y = 1", "export const y=1");
+    test_module("const x = 1; export { x }", "const x=1;export {x}");
+    test_module("const x = f(); export default x", "const x=f();export default x");
+}
+
+#[test]
+fn bailout() {
+    test("function f() { var x = 1; eval('x') }", "function f(){var x=1;eval('x')}");
+    test("with (o) { (function () { var x = 1 })() }", "with(o)(function(){var x=1})()");
+}
+
+#[test]
+fn disabled() {
+    let source_type = SourceType::default();
+    let compress = CompressOptions { dead_code: false, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let source_text = "function f() { var x = 1; return; foo() } if (false) bar()";
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(minified, "function f(){var x=1;return;foo()}1||bar()");
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+fn test_with_options(source_text: &str, expected: &str, compress: CompressOptions) {
+    let source_type = SourceType::default();
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(expected, minified, "for source {source_text}");
+}
+
+fn test_define(source_text: &str, expected: &str, define: &[(&str, &str)]) {
+    let define =
+        define.iter().map(|(key, value)| ((*key).to_string(), (*value).to_string())).collect();
+    test_with_options(
+        source_text,
+        expected,
+        CompressOptions { define, ..CompressOptions::default() },
+    );
+}
+
+#[test]
+fn define() {
+    let define = [("process.env.NODE_ENV", "\"production\""), ("__DEV__", "false")];
+    test_define("x = process.env.NODE_ENV", "x='production'", &define);
+    test_define("x = __DEV__", "x=!1", &define);
+    test_define("if (process.env.NODE_ENV !== 'production') { check() } run()", "run()", &define);
+    test_define("if (process.env.NODE_ENV === 'development') a(); else b()", "b()", &define);
+    test_define("if (__DEV__) { check() }", "", &define);
+    // Only the full path is replaced
+    test_define(
+        "x = process.env; y = process.env.NODE_ENV.length",
+        "x=process.env,y='production'.length",
+        &define,
+    );
+    test_define("x = a.process.env.NODE_ENV", "x=a.process.env.NODE_ENV", &define);
+    // Assignments are not replaced
+    test_define("__DEV__ = 1", "__DEV__=1", &define);
+}
+
+#[test]
+fn define_shadowed() {
+    let define = [("process.env.NODE_ENV", "'production'"), ("__DEV__", "true")];
+    test_define(
+        "function f(process) { return process.env.NODE_ENV }",
+        "function f(process){return process.env.NODE_ENV}",
+        &define,
+    );
+    test_define(
+        "function f() { let __DEV__ = g(); h(); return __DEV__ }",
+        "function f(){let __DEV__=g();return h(),__DEV__}",
+        &define,
+    );
+}
+
+#[test]
+fn define_values() {
+    test_define("x = A", "x=null", &[("A", "null")]);
+    test_define("x = A", "x=void 0", &[("A", "undefined")]);
+    test_define("x = A", "x=void 0", &[("A", "void 0")]);
+    test_define("x = A", "x=1.5", &[("A", "1.5")]);
+    test_define("x = A", "x=-1", &[("A", "-1")]);
+    test_define("x = global.A", "x=globalThis.A", &[("global", "globalThis")]);
+    test_define("x = A", "x=window.document", &[("A", "window.document")]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid define A=f()")]
+fn invalid_define() {
+    test_define("x = A", "x=A", &[("A", "f()")]);
+}
+
+#[test]
+fn check_define() {
+    assert!(CompressOptions::check_define("process.env.NODE_ENV", "'production'").is_ok());
+    assert!(CompressOptions::check_define("__DEV__", "false").is_ok());
+    assert!(CompressOptions::check_define("global", "globalThis").is_ok());
+    assert!(CompressOptions::check_define("a[0]", "1").is_err());
+    assert!(CompressOptions::check_define("a", "b()").is_err());
+    assert!(CompressOptions::check_define("a", "{").is_err());
+}
+
+#[test]
+fn validate() {
+    let define = |key: &str, value: &str| HashMap::from([(key.to_string(), value.to_string())]);
+    let compress =
+        CompressOptions { define: define("__DEV__", "false"), ..CompressOptions::default() };
+    assert!(compress.validate().is_ok());
+    let compress = CompressOptions { define: define("a()", "1"), ..CompressOptions::default() };
+    let options = MinifierOptions { compress, ..MinifierOptions::default() };
+    assert!(options.validate().unwrap_err().starts_with("Invalid define a()=1"));
+}
+
+#[test]
+fn drop_console() {
+    let compress = CompressOptions { drop_console: true, ..CompressOptions::default() };
+    test_with_options("console.log(a()); foo()", "foo()", compress.clone());
+    test_with_options("x = a && console.warn(b)", "x=a&&void 0", compress.clone());
+    test_with_options(
+        "function f(console) { console.log(1) }",
+        "function f(console){console.log(1)}",
+        compress,
+    );
+    test_with_options("console.log(a)", "console.log(a)", CompressOptions::default());
+}
+
+#[test]
+fn pure_funcs() {
+    let pure_funcs = vec!["Math.floor".to_string(), "log".to_string()];
+    let compress = CompressOptions { pure_funcs, ..CompressOptions::default() };
+    test_with_options("Math.floor(a); log(1); log(b())", "a,b()", compress.clone());
+    test_with_options("function f(log) { log(1) }", "function f(log){log(1)}", compress);
+}
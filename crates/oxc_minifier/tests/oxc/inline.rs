@@ -0,0 +1,98 @@
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+use super::{test, test_same};
+
+fn test_with_options(source_text: &str, expected: &str, compress: CompressOptions) {
+    let source_type = SourceType::default();
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(expected, minified, "for source {source_text}");
+}
+
+#[test]
+fn reduce_vars() {
+    test("function f() { const DEBUG = false; if (DEBUG) log() }", "function f(){}");
+    test(
+        "function f() { const a = -1, b = 'x', c = null, d = void 0; return [a, b, c, d] }",
+        "function f(){return [-1,'x',null,void 0]}",
+    );
+    test("function f(x) { const a = 1; return x === a }", "function f(x){return x===1}");
+    // Top level declarations of scripts are global
+    test("const a = 1; f(a)", "const a=1;f(1)");
+}
+
+#[test]
+fn reduce_vars_bailout() {
+    // Temporal dead zone
+    test_same("function f(){g(a);const a=1}");
+    // Read too many times
+    test_same("function f(){const a=1;return [a,a,a,a]}");
+    // The copies of a long string are bigger than the declaration
+    test_same("function f(){const a='a long string literal';return [a,a,a]}");
+    test(
+        "function f() { const a = 'a long string literal'; return a }",
+        "function f(){return 'a long string literal'}",
+    );
+    test(
+        "function f() { const a = 'abc'; return [a, a, a] }",
+        "function f(){return ['abc','abc','abc']}",
+    );
+    test("function f(){let a=1;a++;return a}", "function f(){let a=1;return a++,a}");
+    test("function f(){const a=g();h();return a}", "function f(){const a=g();return h(),a}");
+    test_same("function f(){const a=1;with(o)a}");
+}
+
+#[test]
+fn collapse_vars() {
+    test("function f() { let a = g(); return a + 1 }", "function f(){return g()+1}");
+    test("function f() { let a = g(); let b = a + 1; return b }", "function f(){return g()+1}");
+    test("function f() { let a = g(), b = a; return b }", "function f(){return g()}");
+    test("function f() { var a = g(); if (a) h() }", "function f(){g()&&h()}");
+    test("function f() { let a = g(); a.b = 1 }", "function f(){g().b=1}");
+    // Reads of parameters never assigned cannot observe `g()`
+    test("function f(b) { let a = g(); return b + a }", "function f(b){return b+g()}");
+    // Moved into a branch when it has no side effects
+    test("function f(b) { let a = 1; return b && a }", "function f(b){return b&&1}");
+    // A local `undefined` is an ordinary variable
+    test(
+        "var undefined = 1; function f() { var undefined = 2; var x = undefined; return x }",
+        "var undefined=1;function f(){return 2}",
+    );
+}
+
+#[test]
+fn collapse_vars_bailout() {
+    test("function f(){let a=g();h();return a}", "function f(){let a=g();return h(),a}");
+    test_same("function f(){let a=g();return x+a}");
+    test_same("function f(b){let a=g();return b&&a}");
+    test("function f(b){let a=g();b=1;return a}", "function f(b){let a=g();return b=1,a}");
+    test_same("function f(){let a=g();return a+a}");
+    test_same("function f(){let a=g();return ()=>a}");
+    test("function f(){let a=g();eval('');return a}", "function f(){let a=g();return eval(''),a}");
+    // Top level declarations of scripts are global
+    test_same("let a=g();f(a)");
+}
+
+#[test]
+fn disabled() {
+    let compress =
+        CompressOptions { reduce_vars: false, collapse_vars: false, ..CompressOptions::default() };
+    test_with_options(
+        "function f() { const a = 1; let b = g(); return a + b }",
+        "function f(){const a=1;let b=g();return a+b}",
+        compress,
+    );
+}
+
+#[test]
+fn passes() {
+    // Inlining `c` removes the branch, which only exposes `a` to the next pass
+    let source_text = "function f() { let a = g(); const c = 1; if (c) return a }";
+    test(source_text, "function f(){let a=g();return a}");
+    let compress = CompressOptions { passes: 3, ..CompressOptions::default() };
+    test_with_options(source_text, "function f(){return g()}", compress);
+    // Stops once the output no longer shrinks
+    let compress = CompressOptions { passes: 100, ..CompressOptions::default() };
+    test_with_options(source_text, "function f(){return g()}", compress);
+}
@@ -0,0 +1,87 @@
+use oxc_minifier::{ManglePropertiesOptions, Minifier, MinifierOptions, NameCache};
+use oxc_span::SourceType;
+use regex::Regex;
+
+fn mangle_properties(
+    source_text: &str,
+    options: ManglePropertiesOptions,
+    name_cache: Option<NameCache>,
+) -> (String, NameCache) {
+    let source_type = SourceType::default();
+    let options = MinifierOptions {
+        mangle: false,
+        mangle_properties: Some(options),
+        name_cache,
+        ..MinifierOptions::default()
+    };
+    let ret = Minifier::new(source_text, source_type, options).build();
+    (ret.code, ret.name_cache.unwrap())
+}
+
+fn test(source_text: &str, expected: &str) {
+    let options = ManglePropertiesOptions::new(Regex::new(r"^_\w+$").unwrap());
+    let (code, _) = mangle_properties(source_text, options, None);
+    assert_eq!(expected, code, "for source {source_text}");
+}
+
+#[test]
+fn regex() {
+    test("x._foo = x.bar", "x.a=x.bar");
+    test("x = { _foo: 1, bar: 2 }", "x={a:1,bar:2}");
+    test("class A { _foo() {} _bar = 1 }", "class A{a(){}b=1}");
+    // The most frequent name gets the shortest name
+    test("x._foo, y._bar, z._bar", "x.b,y.a,z.a");
+}
+
+#[test]
+fn destructuring() {
+    test("let { _foo } = x", "let {a:_foo}=x");
+    test("({ _foo } = x)", "({a:_foo}=x)");
+    test("({ _foo: y } = x)", "({a:y}=x)");
+}
+
+#[test]
+fn unmangled() {
+    // Quoted names are kept everywhere
+    test("x._foo = x['_foo']", "x._foo=x['_foo']");
+    test("x._foo = { '_foo': 1 }", "x._foo={'_foo':1}");
+    test("x._foo = '_foo' in y", "x._foo='_foo'in y");
+    test("x._foo = f('_foo')", "x._foo=f('_foo')");
+    test("x._foo = x[`_foo`]", "x._foo=x[`_foo`]");
+    test("x._foo = x[`${_foo}`]", "x.a=x[`${_foo}`]");
+    test("x.__proto__ = x.constructor", "x.__proto__=x.constructor");
+    // Mangled names do not collide with the kept names
+    test("x._foo = x.a", "x.b=x.a");
+    let options = ManglePropertiesOptions {
+        regex: Regex::new(r"^_\w+$").unwrap(),
+        reserved: vec!["_keep".to_string()],
+    };
+    let (code, _) = mangle_properties("x._foo = x._keep", options, None);
+    assert_eq!(code, "x.a=x._keep");
+}
+
+#[test]
+fn name_cache() {
+    let options = ManglePropertiesOptions::new(Regex::new(r"^_\w+$").unwrap());
+    let (code, name_cache) = mangle_properties("x._foo = x._bar", options.clone(), None);
+    assert_eq!(code, "x.a=x.b");
+
+    // A separate chunk reuses the names of the first one
+    let (code, name_cache) =
+        mangle_properties("y._baz = y._bar", options.clone(), Some(name_cache));
+    assert_eq!(code, "y.c=y.b");
+    assert_eq!(name_cache.props.len(), 3);
+
+    // The cache survives a round trip through json
+    let name_cache = NameCache::from_json(&name_cache.to_json()).unwrap();
+    let (code, name_cache) =
+        mangle_properties("z._foo = z._baz", options.clone(), Some(name_cache));
+    assert_eq!(code, "z.a=z.c");
+
+    // Kept names of this build win over the cache
+    let (code, _) =
+        mangle_properties("z._foo = z['_baz']", options.clone(), Some(name_cache.clone()));
+    assert_eq!(code, "z.a=z['_baz']");
+    let (code, _) = mangle_properties("z._foo = z.a", options, Some(name_cache));
+    assert_eq!(code, "z.d=z.a");
+}
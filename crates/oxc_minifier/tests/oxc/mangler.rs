@@ -0,0 +1,158 @@
+use oxc_minifier::{MangleOptions, Minifier, MinifierOptions, NameCache};
+use oxc_span::SourceType;
+
+fn mangle(
+    source_text: &str,
+    mangle_options: MangleOptions,
+    name_cache: Option<NameCache>,
+) -> (String, NameCache) {
+    let source_type = SourceType::default().with_module(true);
+    let options = MinifierOptions { mangle_options, name_cache, ..MinifierOptions::default() };
+    let ret = Minifier::new(source_text, source_type, options).build();
+    (ret.code, ret.name_cache.unwrap())
+}
+
+fn test(source_text: &str, expected: &str, mangle_options: MangleOptions) {
+    let (code, _) = mangle(source_text, mangle_options, None);
+    assert_eq!(expected, code, "for source {source_text}");
+}
+
+fn test_script(source_text: &str, expected: &str, mangle_options: MangleOptions) {
+    let options = MinifierOptions { mangle_options, ..MinifierOptions::default() };
+    let code = Minifier::new(source_text, SourceType::default(), options).build().code;
+    assert_eq!(expected, code, "for source {source_text}");
+}
+
+#[test]
+fn top_level() {
+    let source_text = "var foo = f(); function bar(x) { return foo + x }";
+    // Top level bindings of scripts are global variables
+    test_script(source_text, "var foo=f();function bar(a){return foo+a}", MangleOptions::default());
+    let options = MangleOptions { top_level: true, ..MangleOptions::default() };
+    test_script(source_text, "var a=f();function b(c){return a+c}", options);
+    // Top level bindings of modules are local to the module
+    test(
+        "let foo = f(); function bar(x) { return foo + x } bar(foo)",
+        "let a=f();function b(c){return a+c}b(a)",
+        MangleOptions::default(),
+    );
+}
+
+#[test]
+fn module_exports() {
+    let options = MangleOptions::default;
+    test(
+        "let foo = f(); export function bar(x) { return foo + x }",
+        "let a=f();export function bar(b){return a+b}",
+        options(),
+    );
+    test("export const foo = f(), bar = g()", "export const foo=f(),bar=g()", options());
+    test("let foo = f(); export { foo }", "let a=f();export {a as foo}", options());
+    test("let foo = f(); export { foo as bar }", "let a=f();export {a as bar}", options());
+    test("let foo = f(); export default foo", "let a=f();export default a", options());
+    test("export { foo } from 'foo'", "export {foo}from'foo'", options());
+}
+
+#[test]
+fn eval_bailout() {
+    let options = MangleOptions::default;
+    // Bindings visible from a direct `eval` are kept
+    test(
+        "function f(foo) { let bar = 1; return eval(foo) + bar } f()",
+        "function f(foo){let bar=1;return eval(foo)+bar}f()",
+        options(),
+    );
+    // Nested scopes are mangled, without shadowing the kept names
+    test(
+        "function f(foo) { function g(a, b) { return a + b } return eval(foo) + g() } f()",
+        "function f(foo){function g(a,b){return a+b}return eval(foo)+g()}f()",
+        options(),
+    );
+    test(
+        "function f(foo) { eval(foo) } function g(bar) { return bar } f(), g()",
+        "function f(foo){eval(foo)}function g(a){return a}f(),g()",
+        options(),
+    );
+    // Indirect `eval` only sees global variables
+    test(
+        "function f(foo) { return (0, eval)(foo) } f()",
+        "function a(b){return (0,eval)(b)}a()",
+        options(),
+    );
+}
+
+#[test]
+fn with_bailout() {
+    test_script(
+        "(function (foo, bar) { with (foo) { return bar } })()",
+        "(function(foo,bar){with(foo)return bar})()",
+        MangleOptions::default(),
+    );
+    test_script(
+        "(function (foo) { with (foo) {} return function (bar) { return bar } })()",
+        "(function(foo){with(foo){}return function(a){return a}})()",
+        MangleOptions::default(),
+    );
+}
+
+#[test]
+fn reserved() {
+    let source_text = "function f($, require) { return $(require) } f()";
+    test(source_text, "function a(b,c){return b(c)}a()", MangleOptions::default());
+    let options = MangleOptions { reserved: vec!["$".into()], ..MangleOptions::default() };
+    test(source_text, "function a($,b){return $(b)}a()", options);
+    // Reserved names are not used as mangled names either
+    let options = MangleOptions { reserved: vec!["a".into()], ..MangleOptions::default() };
+    test("function f(x) { return x } f()", "function b(c){return c}b()", options);
+}
+
+#[test]
+fn keep_names() {
+    let source_text = "(function () { function foo() {} class Bar {} return [foo, Bar] })()";
+    test(
+        source_text,
+        "(function(){function a(){}class b{}return [a,b]})()",
+        MangleOptions::default(),
+    );
+    let options = MangleOptions { keep_fnames: true, ..MangleOptions::default() };
+    test(source_text, "(function(){function foo(){}class a{}return [foo,a]})()", options);
+    let options = MangleOptions { keep_classnames: true, ..MangleOptions::default() };
+    test(source_text, "(function(){function a(){}class Bar{}return [a,Bar]})()", options);
+}
+
+#[test]
+fn name_cache() {
+    let (code, name_cache) =
+        mangle("let foo = f(), bar = g(); h(foo, bar, bar)", MangleOptions::default(), None);
+    assert_eq!(code, "let a=f(),b=g();h(a,b,b)");
+    assert_eq!(name_cache.vars.len(), 2);
+
+    // A new top level binding does not rename the existing ones
+    let (code, name_cache) = mangle(
+        "let baz = e(), foo = f(), bar = g(); h(baz, baz, baz, foo, bar, bar)",
+        MangleOptions::default(),
+        Some(name_cache),
+    );
+    assert_eq!(code, "let c=e(),a=f(),b=g();h(c,c,c,a,b,b)");
+    assert_eq!(name_cache.vars.len(), 3);
+
+    let name_cache = NameCache::from_json(&name_cache.to_json()).unwrap();
+    let (code, _) = mangle("let bar = g(); h(bar)", MangleOptions::default(), Some(name_cache));
+    assert_eq!(code, "let b=g();h(b)");
+}
+
+#[test]
+fn name_cache_clash() {
+    let (_, name_cache) = mangle("let foo = f(); h(foo)", MangleOptions::default(), None);
+    assert_eq!(name_cache.vars["foo"], "a");
+    // Cached names are not used when they are globals or kept names of this file
+    let (code, _) =
+        mangle("let foo = f(); a(foo)", MangleOptions::default(), Some(name_cache.clone()));
+    assert_eq!(code, "let b=f();a(b)");
+    let options = MangleOptions { reserved: vec!["a".into()], ..MangleOptions::default() };
+    let (code, _) = mangle("let foo = f(); h(foo)", options, Some(name_cache.clone()));
+    assert_eq!(code, "let b=f();h(b)");
+    let options = MangleOptions { keep_fnames: true, ..MangleOptions::default() };
+    let (code, _) = mangle("function a() {} let foo = f(); h(a, foo)", options, Some(name_cache));
+    assert_eq!(code, "function a(){}let b=f();h(a,b)");
+}
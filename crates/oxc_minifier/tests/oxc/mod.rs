@@ -1 +1,25 @@
+mod dead_code;
+mod define;
+mod inline;
+mod mangle_properties;
+mod mangler;
 mod precedence;
+mod printer_options;
+mod side_effects;
+mod sourcemap;
+mod statements;
+
+use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+/// Minify with the default compress options
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default();
+    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(expected, minified, "for source {source_text}");
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
@@ -0,0 +1,67 @@
+use oxc_minifier::{Minifier, MinifierOptions, PrinterOptions};
+use oxc_span::SourceType;
+
+fn test(source_text: &str, expected: &str, print: PrinterOptions) {
+    let options = MinifierOptions { mangle: false, print, ..MinifierOptions::default() };
+    let code = Minifier::new(source_text, SourceType::default(), options).build().code;
+    assert_eq!(expected, code, "for source {source_text}");
+}
+
+#[test]
+fn beautify() {
+    let print = PrinterOptions { beautify: true, ..PrinterOptions::default() };
+    test(
+        "function foo(a, b) { if (a) { return a + b } for (let i = 0; i < b; i++) g(i, a ? b : 1) } foo(1, 2)",
+        "function foo(a, b) {\n  if (a) return a + b;\n  for (let i = 0; i < b; i++) g(i, a ? b : 1);\n}\nfoo(1, 2);",
+        print,
+    );
+    test(
+        "switch (x) { case 1: f(); break; default: g() } try { f() } catch (e) { g() } finally {}",
+        "switch (x) {\n  case 1:\n    f();\n    break;\n  default:\n    g();\n}\ntry {\n  f();\n} catch (e) {\n  g();\n} finally {}",
+        print,
+    );
+    test(
+        "class A { static { f() } foo() { return 1 } } x = () => { y() }",
+        "class A {\n  static {\n    f();\n  }\n  foo() {\n    return 1;\n  }\n}\nx = () => {\n  y();\n};",
+        print,
+    );
+}
+
+#[test]
+fn ascii_only() {
+    let source_text = "a = 'é😀\u{7}\u{2028}'; é = 1; x.ü";
+    test(source_text, "a='é😀\\x07\\u2028',é=1,x.ü", PrinterOptions::default());
+    let print = PrinterOptions { ascii_only: true, ..PrinterOptions::default() };
+    test(source_text, "a='\\u00E9\\uD83D\\uDE00\\x07\\u2028',\\u00E9=1,x.\\u00FC", print);
+    test("𐊧 = 1", "\\u{102A7}=1", print);
+    // Regexes and template literals
+    test("x = /é😀/u", "x=/\\u00E9\\uD83D\\uDE00/u", print);
+    test("x = /[\\é]\\\\é/", "x=/[\\u00E9]\\\\\\u00E9/", print);
+    test("x = `é${a}😀`", "x=`\\u00E9${a}\\uD83D\\uDE00`", print);
+    test("x = `é${a}`", "x=`é${a}`", PrinterOptions::default());
+}
+
+#[test]
+fn comments() {
+    let source_text =
+        "/*! keep */ /* drop */ f(); function g() { /** @license MIT */ h() } // @preserve end\n";
+    test(source_text, "f();function g(){h()}", PrinterOptions::default());
+    let print = PrinterOptions { comments: true, ..PrinterOptions::default() };
+    test(
+        source_text,
+        "/*! keep */\nf();function g(){\n/** @license MIT */\nh()}\n// @preserve end",
+        print,
+    );
+    test("#!/usr/bin/env node\n//! keep\nf()", "#!/usr/bin/env node\n//! keep\nf()", print);
+}
+
+#[test]
+fn max_line_len() {
+    let print = PrinterOptions { max_line_len: 10, ..PrinterOptions::default() };
+    // Lines are only broken after a `,` or `;` outside of strings
+    test(
+        "foo(aaaaa, bbbbb, ccccc, ddddd); x = 'x,y,z,w,v,u'; bar(eeeee)",
+        "foo(aaaaa,bbbbb,\nccccc,ddddd),\nx='x,y,z,w,v,u',\nbar(eeeee)",
+        print,
+    );
+}
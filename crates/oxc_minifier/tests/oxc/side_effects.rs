@@ -0,0 +1,143 @@
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+use super::{test, test_same};
+
+#[test]
+fn pure_annotation() {
+    test("/*#__PURE__*/ foo()", "");
+    test("/* @__PURE__ */ foo()", "");
+    test("/*#__PURE__*/ new Foo()", "");
+    test("/*#__PURE__*/ (foo())", "");
+    test("/*#__PURE__*/ foo(), bar()", "bar()");
+    test("a(), /*#__PURE__*/ foo()", "a()");
+    // Arguments keep their side effects
+    test("/*#__PURE__*/ foo(bar())", "bar()");
+    test("/*#__PURE__*/ foo(1, bar(), baz())", "bar(),baz()");
+    test("/*#__PURE__*/ new Foo(bar())", "bar()");
+    test("/*#__PURE__*/ foo(/*#__PURE__*/ bar(baz()))", "baz()");
+    test("/*#__PURE__*/ foo(bar()), a()", "bar(),a()");
+    test("/*#__PURE__*/ foo(...a)", "/*#__PURE__*/foo(...a)");
+    // Only the outer call is annotated
+    test("/*#__PURE__*/ a().b()", "");
+    test("x = /*#__PURE__*/ a().b()", "x=/*#__PURE__*/a().b()");
+    test("/*#__PURE__ */ foo()", "");
+    // Comments which are not annotations
+    test("/* PURE */ foo()", "foo()");
+    test("// #__PURE__\nfoo()", "foo()");
+    test("/*#__PURE__*/ x.y; foo()", "x.y,foo()");
+    test_same("foo()");
+}
+
+#[test]
+fn pure_annotation_printed() {
+    test("x = /*#__PURE__*/ foo()", "x=/*#__PURE__*/foo()");
+    test("x = /*#__PURE__*/ new Foo(a)", "x=/*#__PURE__*/new Foo(a)");
+    test("x = a / /*#__PURE__*/ foo()", "x=a/ /*#__PURE__*/foo()");
+    test("export default /*#__PURE__*/ foo()", "export default /*#__PURE__*/foo()");
+    let source_type = SourceType::default();
+    let compress = CompressOptions { side_effects: false, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let source_text = "/*#__PURE__*/ (function () {})()";
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(minified, "/*#__PURE__*/(function(){})()");
+}
+
+#[test]
+fn unused_reads() {
+    test("var a; a, a.b", "var a;a.b");
+    test("function f(a) { a; typeof b }", "function f(a){}");
+    test("undefined, NaN, Infinity", "");
+    // Reading an undeclared variable throws a `ReferenceError`
+    test_same("undefinedVar");
+    test("undefinedVar, 1", "undefinedVar");
+    test_same("[a]");
+    // Reading a variable inside `with` may call a getter of the object
+    test("with (o) { x; y() }", "with(o)x,y()");
+    test("var x; with (o) { x, 1 }", "var x;with(o)x,1");
+}
+
+#[test]
+fn unused_pure_declarations() {
+    test("function f() { var x = /*#__PURE__*/ foo() }", "function f(){}");
+    test(
+        "function f() { var x = /*#__PURE__*/ foo(a()) }",
+        "function f(){var x=/*#__PURE__*/foo(a())}",
+    );
+    test("function f() { let x = new Date, y = Math.max(1, 2) }", "function f(){}");
+    test("function f() { let x = Math.PI }", "function f(){}");
+    test("function f() { let x = a.PI }", "function f(){let x=a.PI}");
+    test("function f() { let x = new Foo }", "function f(){let x=new Foo()}");
+    test("function f(a, b, c) { let x = a ? b : [c] }", "function f(a,b,c){}");
+    test("function f() { let x = a ? b : [c] }", "function f(){let x=a?b:[c]}");
+}
+
+#[test]
+fn builtins() {
+    test("Math.max(1, 2); String('a'); new Date(0); new Error(-1)", "");
+    test("Math.PI, Number.MAX_VALUE, JSON.x, a.b", "a.b");
+    test("({ a: 1 }); [1, , 'a']; a()", "a()");
+    // Arguments may be converted by user code
+    test_same("Math.floor(a)");
+    test_same("String(a)");
+    test_same("new Date(a)");
+    test_same("Math.max(1n)");
+    // Calls which throw
+    test_same("Array(-1)");
+    test_same("RegExp('(')");
+    test_same("Math.foo()");
+    // Shadowed builtins
+    test("function f(Math) { Math.log('x'); Math.PI }", "function f(Math){Math.log('x'),Math.PI}");
+    test("function f() { var String; String('x') }", "function f(){var String;String('x')}");
+    test("with (o) Math.max(1, 2), Math.PI", "with(o)Math.max(1,2),Math.PI");
+    test("x = () => Math.PI", "x=()=>Math.PI");
+    let source_type = SourceType::default().with_module(true);
+    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    let source_text = "import { Object } from './x'; Object('register'); Object.keys";
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(minified, "import {Object} from './x';Object('register'),Object.keys");
+}
+
+#[test]
+fn no_side_effects_annotation() {
+    test("/*@__NO_SIDE_EFFECTS__*/ function f() { a() } f(); f(b())", "function f(){a()}b()");
+    test("/*#__NO_SIDE_EFFECTS__*/ function f() {} f()", "function f(){}");
+    test("f(); /*@__NO_SIDE_EFFECTS__*/ function f() {}", "function f(){}");
+    test("const f = /*@__NO_SIDE_EFFECTS__*/ () => {}; f()", "const f=()=>{}");
+    test("/*@__NO_SIDE_EFFECTS__*/ const f = function () {}; f()", "const f=function(){}");
+    test("(/*@__NO_SIDE_EFFECTS__*/ function () { a() })()", "");
+    // Only the annotated function
+    test(
+        "/*@__NO_SIDE_EFFECTS__*/ function f() {} function g() {} g()",
+        "function f(){}function g(){}g()",
+    );
+    test("function f() {} f()", "function f(){}f()");
+}
+
+#[test]
+fn no_side_effects_annotation_in_module() {
+    let source_type = SourceType::default().with_module(true);
+    let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
+    for (source_text, expected) in [
+        ("/*@__NO_SIDE_EFFECTS__*/ export function f() {} f()", "export function f(){}"),
+        ("export /*@__NO_SIDE_EFFECTS__*/ function f() {} f()", "export function f(){}"),
+        ("/*@__NO_SIDE_EFFECTS__*/ export const f = () => {}; f()", "export const f=()=>{}"),
+        (
+            "/*@__NO_SIDE_EFFECTS__*/ export default function f() {} f()",
+            "export default function f(){}",
+        ),
+    ] {
+        let minified = Minifier::new(source_text, source_type, options.clone()).build().code;
+        assert_eq!(expected, minified, "for source {source_text}");
+    }
+}
+
+#[test]
+fn disabled() {
+    let source_type = SourceType::default();
+    let compress = CompressOptions { side_effects: false, ..CompressOptions::default() };
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let source_text = "/*#__PURE__*/ foo()";
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(minified, "/*#__PURE__*/foo()");
+}
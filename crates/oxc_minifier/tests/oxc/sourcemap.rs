@@ -0,0 +1,37 @@
+use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+fn source_map(source_text: &str, mangle: bool) -> (String, oxc_sourcemap::SourceMap) {
+    let source_type = SourceType::default();
+    let options = MinifierOptions { mangle, source_map: true, ..MinifierOptions::default() };
+    let ret = Minifier::new(source_text, source_type, options).with_source_name("test.js").build();
+    (ret.code, ret.source_map.unwrap())
+}
+
+#[test]
+fn disabled() {
+    let options = MinifierOptions::default();
+    let ret = Minifier::new("a", SourceType::default(), options).build();
+    assert!(ret.source_map.is_none());
+}
+
+#[test]
+fn mappings() {
+    let (code, map) = source_map("foo();\n  bar(baz);", false);
+    assert_eq!(code, "foo(),bar(baz)");
+    // `foo()` 0:0 -> 0:0, `bar(baz)` 0:6 -> 1:2, `baz` 0:10 -> 1:6
+    assert_eq!(map.mappings, "AAAA,MACE,IAAI");
+    assert_eq!(map.sources, vec!["test.js"]);
+    assert_eq!(map.sources_content, vec!["foo();\n  bar(baz);"]);
+    assert!(map.names.is_empty());
+}
+
+#[test]
+fn names() {
+    let (code, map) = source_map("function foo(bar) {\n  return bar;\n}", true);
+    // Top level bindings of scripts are global, they are not mangled
+    assert_eq!(code, "function foo(a){return a}");
+    // Mangled identifiers map back to their original names
+    assert_eq!(map.names, vec!["bar"]);
+    assert_eq!(map.mappings, "AAAA,SAAS,IAAIA,GACX,OAAOA");
+}
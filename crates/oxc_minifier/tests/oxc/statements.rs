@@ -0,0 +1,83 @@
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+use super::{test, test_same};
+
+#[test]
+fn conditionals() {
+    test("if (a) b()", "a&&b()");
+    test("if (!a) b()", "a||b()");
+    test("if (a) b = 1", "a&&(b=1)");
+    test("if (a) b(); else c()", "a?b():c()");
+    test("if (!a) b(); else c()", "a?c():b()");
+    test("if (a) b(); else if (c) d(); else e()", "a?b():c?d():e()");
+    test("if (a) { if (b) c() } else d()", "a?b&&c():d()");
+    test("if (a(), b) c()", "a(),b&&c()");
+    test("if (a()) {}", "a()");
+    test_same("function f(){if(a)b(); else return}");
+}
+
+#[test]
+fn sequences() {
+    test("a(); b(); c()", "a(),b(),c()");
+    test("function f() { a(); return b }", "function f(){return a(),b}");
+    test("function f() { a(); throw b }", "function f(){throw a(),b}");
+    test("a(); if (b) c(); else d = 1", "a(),b?c():d=1");
+    test("a(); switch (b) {}", "switch(a(),b){}");
+    test("a(); var x = 1; b()", "a();var x=1;b()");
+}
+
+#[test]
+fn if_return() {
+    test("function f() { if (a) return b; else return c }", "function f(){return a?b:c}");
+    test("function f() { if (a) return b; return c }", "function f(){return a?b:c}");
+    test("function f() { if (!a) return b; return c }", "function f(){return a?c:b}");
+    test(
+        "function f() { if (a) return b; if (c) return d; return e }",
+        "function f(){return a?b:c?d:e}",
+    );
+    test("function f() { a(); if (b) return c; return d }", "function f(){return a(),b?c:d}");
+    test("function f() { if (a) return b; return }", "function f(){return a?b:void 0}");
+    test("function f() { if (a) return; return }", "function f(){if(a)return}");
+}
+
+#[test]
+fn else_after_jump() {
+    test(
+        "function f() { if (a) { b(); return c } else { d() } e() }",
+        "function f(){if(a)return b(),c;d(),e()}",
+    );
+    test(
+        "for (;;) { if (a) { b(); continue } else c(); d() }",
+        "for(;;){if(a){b();continue}c(),d()}",
+    );
+    // Lexical declarations of the alternate stay in their block
+    test(
+        "function f() { if (a) { return b } else { let x = g(); h(x) } }",
+        "function f(){if(a)return b;{let x=g();h(x)}}",
+    );
+}
+
+#[test]
+fn trailing_return() {
+    test("function f() { a(); return }", "function f(){a()}");
+    test("function f() { return void 0 }", "function f(){}");
+    test("function f() { return undefined }", "function f(){}");
+    test("x = () => { a(); return }", "x=()=>{a()}");
+    test_same("function f(){return a}");
+}
+
+#[test]
+fn disabled() {
+    let source_type = SourceType::default();
+    let compress = CompressOptions {
+        conditionals: false,
+        sequences: false,
+        if_return: false,
+        ..CompressOptions::default()
+    };
+    let options = MinifierOptions { mangle: false, compress, ..MinifierOptions::default() };
+    let source_text = "function f() { a(); if (b) c(); if (d) return e; else return; }";
+    let minified = Minifier::new(source_text, source_type, options).build().code;
+    assert_eq!(minified, "function f(){a();if(b)c();if(d)return e; else return}");
+}
@@ -372,3 +372,51 @@ impl<'a> Parser<'a> {
         self.ast.jsx_text(self.end_span(span), value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::{Expression, JSXAttributeItem, JSXAttributeName, Statement};
+    use oxc_span::SourceType;
+
+    use crate::Parser;
+
+    /// Returns the name and source text of each attribute name of the first JSX element.
+    fn attribute_names(source: &str) -> Vec<(String, String)> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_jsx(true);
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty(), "{source}");
+        let Some(Statement::ExpressionStatement(stmt)) = ret.program.body.first() else {
+            panic!("expected an expression statement in {source}")
+        };
+        let Expression::JSXElement(element) = &stmt.expression else {
+            panic!("expected a jsx element in {source}")
+        };
+        element
+            .opening_element
+            .attributes
+            .iter()
+            .map(|attr| {
+                let JSXAttributeItem::Attribute(attr) = attr else { unreachable!() };
+                let JSXAttributeName::Identifier(ident) = &attr.name else { unreachable!() };
+                (ident.name.to_string(), ident.span.source_text(source).to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dashed_identifier_after_whitespace() {
+        let expected = vec![("data-foo".to_string(), "data-foo".to_string())];
+        assert_eq!(attribute_names("<div data-foo />"), expected);
+        assert_eq!(attribute_names("<div   data-foo />"), expected);
+        assert_eq!(attribute_names("<div /* c */ data-foo />"), expected);
+        assert_eq!(
+            attribute_names("<div a aria-label='x' />"),
+            vec![
+                ("a".to_string(), "a".to_string()),
+                ("aria-label".to_string(), "aria-label".to_string())
+            ]
+        );
+    }
+}
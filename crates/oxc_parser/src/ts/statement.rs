@@ -34,7 +34,7 @@ impl<'a> Parser<'a> {
 
         let id = self.parse_binding_identifier()?;
         let members = TSEnumMemberList::parse(self)?.members;
-        Ok(self.ast.ts_enum_declaration(span, id, members, modifiers))
+        Ok(self.ast.ts_enum_declaration(self.end_span(span), id, members, modifiers))
     }
 
     pub(crate) fn parse_ts_enum_member(&mut self) -> Result<TSEnumMember<'a>> {
@@ -111,7 +111,13 @@ impl<'a> Parser<'a> {
         let annotation = self.parse_ts_type()?;
 
         self.asi()?;
-        Ok(self.ast.ts_type_alias_declaration(span, id, annotation, params, modifiers))
+        Ok(self.ast.ts_type_alias_declaration(
+            self.end_span(span),
+            id,
+            annotation,
+            params,
+            modifiers,
+        ))
     }
 
     /** ---------------------  Interface  ------------------------ */
@@ -394,9 +400,9 @@ impl<'a> Parser<'a> {
         is_export: bool,
     ) -> Result<Declaration<'a>> {
         let import_kind = if !self.peek_at(Kind::Eq) && self.eat(Kind::Type) {
-            ImportOrExportKind::Value
-        } else {
             ImportOrExportKind::Type
+        } else {
+            ImportOrExportKind::Value
         };
 
         let id = self.parse_binding_identifier()?;
@@ -560,3 +566,57 @@ impl<'a> Parser<'a> {
         Modifier { span, kind: modifier_kind }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::{Declaration, ImportOrExportKind, Statement};
+    use oxc_span::{GetSpan, SourceType};
+
+    use crate::Parser;
+
+    fn parse_declaration(source: &str, f: impl FnOnce(&Declaration)) {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty(), "{source}");
+        let Some(Statement::Declaration(decl)) = ret.program.body.first() else {
+            panic!("expected a declaration in {source}")
+        };
+        f(decl);
+    }
+
+    fn import_kind(source: &str) -> ImportOrExportKind {
+        let mut kind = None;
+        parse_declaration(source, |decl| {
+            let Declaration::TSImportEqualsDeclaration(decl) = decl else {
+                panic!("expected an import equals declaration in {source}")
+            };
+            kind = Some(decl.import_kind);
+        });
+        kind.unwrap()
+    }
+
+    #[test]
+    fn import_equals_kind() {
+        assert_eq!(import_kind("import type A = require('a')"), ImportOrExportKind::Type);
+        assert_eq!(import_kind("import A = require('a')"), ImportOrExportKind::Value);
+        assert_eq!(import_kind("import A = B.C"), ImportOrExportKind::Value);
+        // `type` is the binding name here
+        assert_eq!(import_kind("import type = require('a')"), ImportOrExportKind::Value);
+    }
+
+    #[test]
+    fn declaration_span() {
+        let check = |source: &str| {
+            parse_declaration(source, |decl| {
+                let span = decl.span();
+                assert_eq!((span.start, span.end as usize), (0, source.len()), "{source}");
+            });
+        };
+        check("enum A { B, C }");
+        check("const enum A {}");
+        check("type A = string;");
+        check("type A<T> = T[]");
+    }
+}
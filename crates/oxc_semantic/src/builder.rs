@@ -144,7 +144,9 @@ impl<'a> SemanticBuilder<'a> {
         SemanticBuilderReturn { semantic, errors: self.errors.into_inner() }
     }
 
-    pub fn build2(self) -> Semantic<'a> {
+    pub fn build2(mut self) -> Semantic<'a> {
+        // The root scope is never left by the visitors calling `build2`
+        self.resolve_references_for_current_scope();
         Semantic {
             source_text: self.source_text,
             source_type: self.source_type,
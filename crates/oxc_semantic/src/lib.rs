@@ -86,6 +86,10 @@ impl<'a> Semantic<'a> {
         &self.symbols
     }
 
+    pub fn symbols_mut(&mut self) -> &mut SymbolTable {
+        &mut self.symbols
+    }
+
     pub fn unused_labels(&self) -> &Vec<AstNodeId> {
         &self.unused_labels
     }
@@ -95,4 +95,9 @@ impl SymbolTable {
     pub fn get_resolved_references(&self, symbol_id: SymbolId) -> &Vec<ReferenceId> {
         &self.resolved_references[symbol_id]
     }
+
+    /// Remove a reference which no longer exists in the AST, e.g. after inlining a constant
+    pub fn delete_resolved_reference(&mut self, symbol_id: SymbolId, reference_id: ReferenceId) {
+        self.resolved_references[symbol_id].retain(|id| *id != reference_id);
+    }
 }
@@ -0,0 +1,251 @@
+use rustc_hash::FxHashMap;
+
+use crate::{vlq, SourceMap};
+
+/// Incrementally builds a [SourceMap] for a single original source file.
+///
+/// Code generators call [SourceMapBuilder::add_mapping] with everything they have printed so
+/// far, each time they are about to print a node that carries an original span.
+/// Generated positions are computed lazily from the printed text,
+/// so code generators do not need to track lines and columns themselves.
+///
+/// Lines and columns are zero based, columns are counted in UTF-16 code units as required by the spec.
+pub struct SourceMapBuilder {
+    source_name: String,
+    source_text: String,
+
+    /// Byte offset of the start of each line in `source_text`
+    line_offsets: Vec<u32>,
+
+    names: Vec<String>,
+    names_map: FxHashMap<String, u32>,
+
+    mappings: String,
+
+    /* Generated position of `generated_offset` */
+    generated_offset: usize,
+    generated_line: u32,
+    generated_column: u32,
+
+    /// Cached original position `(line, byte offset, utf16 column)` for speeding up lookups
+    /// of consecutive offsets on the same line, which is the common case for long minified lines.
+    original_cache: (usize, u32, u32),
+
+    /* Previous segment values, segments are encoded relative to these */
+    prev_generated_line: u32,
+    prev_generated_column: u32,
+    prev_original_line: u32,
+    prev_original_column: u32,
+    prev_name_index: u32,
+    has_segment_on_line: bool,
+    last_generated_position: Option<(u32, u32)>,
+}
+
+impl SourceMapBuilder {
+    pub fn new(source_name: &str, source_text: &str) -> Self {
+        Self {
+            source_name: source_name.to_string(),
+            source_text: source_text.to_string(),
+            line_offsets: compute_line_offsets(source_text),
+            names: vec![],
+            names_map: FxHashMap::default(),
+            mappings: String::new(),
+            generated_offset: 0,
+            generated_line: 0,
+            generated_column: 0,
+            original_cache: (0, 0, 0),
+            prev_generated_line: 0,
+            prev_generated_column: 0,
+            prev_original_line: 0,
+            prev_original_column: 0,
+            prev_name_index: 0,
+            has_segment_on_line: false,
+            last_generated_position: None,
+        }
+    }
+
+    /// Add a mapping from the end of `output` to `original_offset` (a byte offset into the original source).
+    ///
+    /// `output` must be the complete generated text printed so far,
+    /// and must only grow between calls.
+    /// Only the first mapping for a generated position is kept.
+    pub fn add_mapping(&mut self, output: &str, original_offset: u32, name: Option<&str>) {
+        self.advance_generated_position(output);
+
+        let generated = (self.generated_line, self.generated_column);
+        if self.last_generated_position == Some(generated) {
+            return;
+        }
+        self.last_generated_position = Some(generated);
+
+        let (original_line, original_column) = self.original_position(original_offset);
+        let name_index = name.map(|name| self.add_name(name));
+
+        while self.prev_generated_line < self.generated_line {
+            self.mappings.push(';');
+            self.prev_generated_line += 1;
+            self.prev_generated_column = 0;
+            self.has_segment_on_line = false;
+        }
+        if self.has_segment_on_line {
+            self.mappings.push(',');
+        }
+        self.has_segment_on_line = true;
+
+        let out = &mut self.mappings;
+        vlq::encode(i64::from(self.generated_column) - i64::from(self.prev_generated_column), out);
+        // There is only ever one source, so the source index delta is always zero.
+        vlq::encode(0, out);
+        vlq::encode(i64::from(original_line) - i64::from(self.prev_original_line), out);
+        vlq::encode(i64::from(original_column) - i64::from(self.prev_original_column), out);
+        if let Some(name_index) = name_index {
+            vlq::encode(i64::from(name_index) - i64::from(self.prev_name_index), out);
+            self.prev_name_index = name_index;
+        }
+
+        self.prev_generated_column = self.generated_column;
+        self.prev_original_line = original_line;
+        self.prev_original_column = original_column;
+    }
+
+    pub fn into_source_map(self) -> SourceMap {
+        SourceMap {
+            version: 3,
+            file: None,
+            sources: vec![self.source_name],
+            sources_content: vec![self.source_text],
+            names: self.names,
+            mappings: self.mappings,
+        }
+    }
+
+    fn add_name(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.names_map.get(name) {
+            return *index;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.names_map.insert(name.to_string(), index);
+        index
+    }
+
+    fn advance_generated_position(&mut self, output: &str) {
+        let Some(text) = output.get(self.generated_offset..) else { return };
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if is_line_terminator(c) {
+                // `\r\n` is a single line break
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                self.generated_line += 1;
+                self.generated_column = 0;
+            } else {
+                #[allow(clippy::cast_possible_truncation)]
+                let len = c.len_utf16() as u32;
+                self.generated_column += len;
+            }
+        }
+        self.generated_offset = output.len();
+    }
+
+    fn original_position(&mut self, offset: u32) -> (u32, u32) {
+        let line = match self.line_offsets.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let (cached_line, cached_offset, cached_column) = self.original_cache;
+        let (start, mut column) = if cached_line == line && cached_offset <= offset {
+            (cached_offset, cached_column)
+        } else {
+            (self.line_offsets[line], 0)
+        };
+        if let Some(text) = self.source_text.get(start as usize..offset as usize) {
+            #[allow(clippy::cast_possible_truncation)]
+            let len = text.chars().map(char::len_utf16).sum::<usize>() as u32;
+            column += len;
+        }
+        self.original_cache = (line, offset, column);
+        #[allow(clippy::cast_possible_truncation)]
+        (line as u32, column)
+    }
+}
+
+fn is_line_terminator(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+fn compute_line_offsets(source_text: &str) -> Vec<u32> {
+    let mut offsets = vec![0];
+    let mut chars = source_text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if is_line_terminator(c) {
+            let mut end = i + c.len_utf8();
+            if c == '\r' && matches!(chars.peek(), Some((_, '\n'))) {
+                chars.next();
+                end += 1;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            offsets.push(end as u32);
+        }
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod test {
+    use super::SourceMapBuilder;
+
+    #[test]
+    fn single_line() {
+        let source = "let  foo = 1;\nfoo;";
+        let mut builder = SourceMapBuilder::new("test.js", source);
+        builder.add_mapping("", 0, None);
+        builder.add_mapping("let ", 5, Some("foo"));
+        builder.add_mapping("let a=1;", 14, Some("foo"));
+        let map = builder.into_source_map();
+        assert_eq!(map.mappings, "AAAA,IAAKA,IACLA");
+        assert_eq!(map.names, vec!["foo"]);
+        assert_eq!(map.sources, vec!["test.js"]);
+        assert_eq!(map.sources_content, vec![source]);
+    }
+
+    #[test]
+    fn multiple_lines() {
+        let mut builder = SourceMapBuilder::new("test.js", "a;\r\nb;");
+        builder.add_mapping("", 0, None);
+        builder.add_mapping("a;\n\n", 4, None);
+        let map = builder.into_source_map();
+        assert_eq!(map.mappings, "AAAA;;AACA");
+    }
+
+    #[test]
+    fn duplicate_generated_position() {
+        let mut builder = SourceMapBuilder::new("test.js", "(a)");
+        builder.add_mapping("", 0, None);
+        builder.add_mapping("", 1, Some("a"));
+        let map = builder.into_source_map();
+        assert_eq!(map.mappings, "AAAA");
+        assert!(map.names.is_empty());
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let source = "'😀';x";
+        let mut builder = SourceMapBuilder::new("test.js", source);
+        builder.add_mapping("'😀';", 7, None);
+        let map = builder.into_source_map();
+        assert_eq!(map.mappings, "KAAK");
+    }
+
+    #[test]
+    fn json() {
+        let builder = SourceMapBuilder::new("test.js", "\"");
+        let json = builder.into_source_map().to_json_string();
+        assert_eq!(
+            json,
+            r#"{"version":3,"sources":["test.js"],"sourcesContent":["\""],"names":[],"mappings":""}"#
+        );
+    }
+}
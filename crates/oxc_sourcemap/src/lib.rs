@@ -0,0 +1,9 @@
+//! Source Map v3
+//!
+//! Spec: <https://sourcemaps.info/spec.html>
+
+mod builder;
+mod source_map;
+mod vlq;
+
+pub use crate::{builder::SourceMapBuilder, source_map::SourceMap};
@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+/// A Source Map v3 document.
+///
+/// All fields are public so callers can fill in `file` or rename `sources`
+/// after the map has been generated.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMap {
+    pub version: u32,
+
+    /// Name of the generated file this source map is associated with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// Original source file names, referenced by index from `mappings`.
+    pub sources: Vec<String>,
+
+    /// Original source contents, in the same order as `sources`.
+    pub sources_content: Vec<String>,
+
+    /// Original symbol names, referenced by index from `mappings`.
+    pub names: Vec<String>,
+
+    /// Base64 VLQ encoded mappings.
+    pub mappings: String,
+}
+
+impl SourceMap {
+    /// Serialize into the JSON representation.
+    ///
+    /// # Panics
+    ///
+    /// Never, all fields are plain strings and numbers.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("source map serialization cannot fail")
+    }
+}
@@ -0,0 +1,51 @@
+//! Base64 VLQ encoding used by the `mappings` field.
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const VLQ_BASE_SHIFT: u32 = 5;
+const VLQ_BASE: u32 = 1 << VLQ_BASE_SHIFT;
+const VLQ_BASE_MASK: u32 = VLQ_BASE - 1;
+const VLQ_CONTINUATION_BIT: u32 = VLQ_BASE;
+
+/// Encode a signed integer as Base64 VLQ and append it to `out`.
+///
+/// The sign is stored in the least significant bit,
+/// then the value is split into 5-bit groups, least significant group first.
+pub fn encode(value: i64, out: &mut String) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let mut vlq = if value < 0 { ((-value) << 1) | 1 } else { value << 1 } as u64;
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut digit = (vlq as u32) & VLQ_BASE_MASK;
+        vlq >>= VLQ_BASE_SHIFT;
+        if vlq > 0 {
+            digit |= VLQ_CONTINUATION_BIT;
+        }
+        out.push(BASE64_CHARS[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::encode;
+
+    fn vlq(value: i64) -> String {
+        let mut s = String::new();
+        encode(value, &mut s);
+        s
+    }
+
+    #[test]
+    fn encode_values() {
+        assert_eq!(vlq(0), "A");
+        assert_eq!(vlq(1), "C");
+        assert_eq!(vlq(-1), "D");
+        assert_eq!(vlq(15), "e");
+        assert_eq!(vlq(16), "gB");
+        assert_eq!(vlq(-16), "hB");
+        assert_eq!(vlq(1000), "w+B");
+    }
+}
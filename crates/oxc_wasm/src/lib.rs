@@ -195,8 +195,14 @@ impl Oxc {
         }
 
         if run_options.format() {
-            let formatter_options = FormatterOptions { indentation: formatter_options.indentation };
-            let printed = Formatter::new(source_text.len(), formatter_options).build(program);
+            let formatter_options = FormatterOptions {
+                indentation: formatter_options.indentation,
+                ..FormatterOptions::default()
+            };
+            let mut formatter = Formatter::new(source_text.len(), formatter_options);
+            formatter.with_source_text(source_text);
+            formatter.with_comments(source_text, &ret.trivias);
+            let printed = formatter.build(program).code;
             self.formatted_text = printed;
         }
 
@@ -210,7 +216,7 @@ impl Oxc {
             let hir = allocator.alloc(ast_lower_ret.program);
             let semantic = ast_lower_ret.semantic;
 
-            let mut printer = Printer::new(self.source_text.len(), PrinterOptions);
+            let mut printer = Printer::new(self.source_text.len(), PrinterOptions::default());
             let _semantic =
                 Compressor::new(&allocator, semantic, CompressOptions::default()).build(hir);
             if minifier_options.mangle() {
@@ -218,7 +224,7 @@ impl Oxc {
                 printer.with_mangler(mangler);
             }
 
-            self.minified_text = printer.build(hir);
+            self.minified_text = printer.build(hir).code;
         }
 
         if run_options.type_check() {
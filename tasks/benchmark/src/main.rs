@@ -97,7 +97,7 @@ fn bench_minifier(criterion: &mut Criterion, files: &[&TestFile]) {
                 let options = MinifierOptions::default();
                 b.iter(|| {
                     let _minified =
-                        Minifier::new(black_box(source_text), source_type, options).build();
+                        Minifier::new(black_box(source_text), source_type, options.clone()).build();
                 });
             },
         );
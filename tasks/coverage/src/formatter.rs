@@ -1,14 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use oxc_allocator::Allocator;
-use oxc_formatter::{Formatter, FormatterOptions};
-use oxc_parser::Parser;
+use oxc_formatter::{check, FormatterOptions};
 use oxc_span::SourceType;
 
 use crate::{
     babel::BabelCase,
     suite::{Case, TestResult},
     test262::{Test262Case, TestFlag},
+    typescript::TypeScriptCase,
 };
 
 pub struct FormatterTest262Case {
@@ -80,15 +79,44 @@ impl Case for FormatterBabelCase {
     }
 }
 
+pub struct FormatterTypeScriptCase {
+    base: TypeScriptCase,
+}
+
+impl Case for FormatterTypeScriptCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        Self { base: TypeScriptCase::new(path, code) }
+    }
+
+    fn code(&self) -> &str {
+        self.base.code()
+    }
+
+    fn path(&self) -> &Path {
+        self.base.path()
+    }
+
+    fn test_result(&self) -> &TestResult {
+        self.base.test_result()
+    }
+
+    fn skip_test_case(&self) -> bool {
+        self.base.skip_test_case() || self.base.should_fail()
+    }
+
+    fn run(&mut self) {
+        let source_text = self.base.code();
+        let source_type = self.base.source_type();
+        let formatter_options = FormatterOptions::default();
+        let result = get_result(source_text, source_type, formatter_options);
+        self.base.set_result(result);
+    }
+}
+
+/// Formatting must keep the AST and be idempotent, see [oxc_formatter::check]
 fn get_result(source_text: &str, source_type: SourceType, options: FormatterOptions) -> TestResult {
-    let allocator = Allocator::default();
-    let program1 = Parser::new(&allocator, source_text, source_type).parse().program;
-    let source_text1 = Formatter::new(source_text.len(), options).build(&program1);
-    let program2 = Parser::new(&allocator, &source_text1, source_type).parse().program;
-    let source_text2 = Formatter::new(source_text1.len(), options).build(&program2);
-    if source_text1 == source_text2 {
-        TestResult::Passed
-    } else {
-        TestResult::ParseError(String::new(), false)
+    match check(source_text, source_type, options) {
+        Ok(_) => TestResult::Passed,
+        Err(error) => TestResult::ParseError(format!("{error}\n"), false),
     }
 }
@@ -4,6 +4,7 @@ mod babel;
 mod formatter;
 mod minifier;
 mod misc;
+mod prettier;
 mod suite;
 mod test262;
 mod typescript;
@@ -12,9 +13,10 @@ use std::path::PathBuf;
 
 use crate::{
     babel::{BabelCase, BabelSuite},
-    formatter::{FormatterBabelCase, FormatterTest262Case},
+    formatter::{FormatterBabelCase, FormatterTest262Case, FormatterTypeScriptCase},
     minifier::{MinifierBabelCase, MinifierTest262Case},
     misc::{MiscCase, MiscSuite},
+    prettier::{PrettierCase, PrettierSuite},
     suite::Suite,
     test262::{Test262Case, Test262Suite},
     typescript::{TypeScriptCase, TypeScriptSuite},
@@ -55,6 +57,8 @@ impl AppArgs {
     pub fn run_formatter(&self) {
         Test262Suite::<FormatterTest262Case>::new().run("formatter_test262", self);
         BabelSuite::<FormatterBabelCase>::new().run("formatter_babel", self);
+        TypeScriptSuite::<FormatterTypeScriptCase>::new().run("formatter_typescript", self);
+        PrettierSuite::<PrettierCase>::new().run("formatter_prettier", self);
     }
 
     pub fn run_minifier(&self) {
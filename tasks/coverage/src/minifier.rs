@@ -80,8 +80,8 @@ impl Case for MinifierBabelCase {
 // Test minification by minifying twice because it is a idempotent
 fn get_result(source_text: &str, source_type: SourceType) -> TestResult {
     let options = MinifierOptions::default();
-    let source_text1 = Minifier::new(source_text, source_type, options).build();
-    let source_text2 = Minifier::new(&source_text1, source_type, options).build();
+    let source_text1 = Minifier::new(source_text, source_type, options.clone()).build().code;
+    let source_text2 = Minifier::new(&source_text1, source_type, options).build().code;
     if source_text1 == source_text2 {
         TestResult::Passed
     } else {
@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use oxc_allocator::Allocator;
+use oxc_formatter::{Formatter, FormatterOptions, QuoteStyle, TrailingComma};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::{
+    project_root,
+    suite::{Case, Suite, TestResult},
+};
+
+const FIXTURES_PATH: &str = "tasks/coverage/prettier/tests/format/js";
+
+const SNAPSHOT_SEPARATOR: &str =
+    "================================================================================";
+
+pub struct PrettierSuite<T: Case> {
+    test_root: PathBuf,
+    test_cases: Vec<T>,
+}
+
+impl<T: Case> PrettierSuite<T> {
+    pub fn new() -> Self {
+        Self { test_root: project_root().join(FIXTURES_PATH), test_cases: vec![] }
+    }
+}
+
+impl<T: Case> Suite<T> for PrettierSuite<T> {
+    fn get_test_root(&self) -> &Path {
+        &self.test_root
+    }
+
+    fn skip_test_path(&self, path: &Path) -> bool {
+        let is_js = path.extension().is_some_and(|ext| ext == "js");
+        let is_spec = path.file_name().is_some_and(|name| name == "jsfmt.spec.js");
+        let is_snapshot = path.components().any(|c| c.as_os_str() == "__snapshots__");
+        !is_js || is_spec || is_snapshot
+    }
+
+    fn save_test_cases(&mut self, cases: Vec<T>) {
+        self.test_cases = cases;
+    }
+
+    fn get_test_cases(&self) -> &Vec<T> {
+        &self.test_cases
+    }
+}
+
+/// A formatted output from `__snapshots__/jsfmt.spec.js.snap`
+#[derive(Debug)]
+struct PrettierSnapshot {
+    options: FormatterOptions,
+    output: String,
+}
+
+pub struct PrettierCase {
+    path: PathBuf,
+    code: String,
+    snapshots: Vec<PrettierSnapshot>,
+    result: TestResult,
+}
+
+impl PrettierCase {
+    /// Read all snapshots of this file with options supported by the formatter.
+    ///
+    /// A snapshot looks like
+    ///
+    /// ```text
+    /// exports[`file.js - {"semi":false} format 1`] = `
+    /// ====================================options=====================================
+    /// parsers: ["babel", "flow", "typescript"]
+    /// printWidth: 80
+    /// semi: false
+    ///                                                                                 | printWidth
+    /// =====================================input======================================
+    /// ...
+    /// =====================================output=====================================
+    /// ...
+    ///
+    /// ================================================================================
+    /// `;
+    /// ```
+    fn read_snapshots(path: &Path) -> Vec<PrettierSnapshot> {
+        let Some(file_name) = path.file_name().and_then(std::ffi::OsStr::to_str) else {
+            return vec![];
+        };
+        let dir = project_root().join(FIXTURES_PATH).join(path);
+        let snapshot_path = dir.with_file_name("__snapshots__").join("jsfmt.spec.js.snap");
+        let Ok(snapshot) = fs::read_to_string(snapshot_path) else { return vec![] };
+
+        let mut snapshots = vec![];
+        let prefix = format!("{file_name} ");
+        for entry in snapshot.split("exports[`").skip(1).filter(|entry| entry.starts_with(&prefix))
+        {
+            let entry = unescape_template(entry);
+            let Some((_, rest)) = entry.split_once("=options=") else { continue };
+            let Some((options, rest)) = rest.split_once("=input=") else { continue };
+            let Some((_, output)) = rest.split_once("=output=") else { continue };
+            let Some(options) = parse_options(options) else { continue };
+            // Skip the rest of the header line, the output ends with an empty line and the separator
+            let output = output.trim_start_matches('=').trim_start_matches('\n');
+            let Some(end) = output.rfind(SNAPSHOT_SEPARATOR) else { continue };
+            let output = output[..end].strip_suffix('\n').unwrap_or(&output[..end]);
+            snapshots.push(PrettierSnapshot { options, output: output.to_string() });
+        }
+        snapshots
+    }
+}
+
+/// Parse the options section of a snapshot,
+/// returns `None` for snapshots using options the formatter does not support.
+fn parse_options(text: &str) -> Option<FormatterOptions> {
+    let mut options = FormatterOptions { indentation: 2, ..FormatterOptions::default() };
+    let mut has_babel_parser = false;
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(": ") else { continue };
+        match key {
+            "parsers" => has_babel_parser = value.contains("\"babel\""),
+            "printWidth" => options.print_width = value.parse().ok()?,
+            "tabWidth" => options.indentation = value.parse().ok()?,
+            "semi" => options.semi = value.parse().ok()?,
+            "singleQuote" => {
+                let single_quote: bool = value.parse().ok()?;
+                options.quote_style =
+                    if single_quote { QuoteStyle::Single } else { QuoteStyle::Double };
+            }
+            "bracketSpacing" => options.bracket_spacing = value.parse().ok()?,
+            "trailingComma" => {
+                options.trailing_comma = match value.trim_matches('"') {
+                    "none" => TrailingComma::None,
+                    "es5" => TrailingComma::Es5,
+                    "all" => TrailingComma::All,
+                    _ => return None,
+                };
+            }
+            _ => return None,
+        }
+    }
+    has_babel_parser.then_some(options)
+}
+
+/// Snapshots are stored in template literals
+fn unescape_template(text: &str) -> String {
+    text.replace("\\`", "`").replace("\\${", "${").replace("\\\\", "\\")
+}
+
+impl Case for PrettierCase {
+    fn new(path: PathBuf, code: String) -> Self {
+        let snapshots = Self::read_snapshots(&path);
+        Self { path, code, snapshots, result: TestResult::ToBeRun }
+    }
+
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn test_result(&self) -> &TestResult {
+        &self.result
+    }
+
+    fn skip_test_case(&self) -> bool {
+        // Cursor and range formatting tests
+        self.snapshots.is_empty()
+            || self.code.contains("<|>")
+            || self.code.contains("<<<PRETTIER_RANGE")
+    }
+
+    fn run(&mut self) {
+        let source_type = SourceType::from_path(&self.path).unwrap().with_module(true);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, &self.code, source_type).parse();
+        if !ret.errors.is_empty() {
+            self.result = TestResult::ParseError(String::new(), ret.panicked);
+            return;
+        }
+        for snapshot in &self.snapshots {
+            let mut formatter = Formatter::new(self.code.len(), snapshot.options);
+            formatter.with_source_text(&self.code);
+            formatter.with_comments(&self.code, &ret.trivias);
+            let code = formatter.build(&ret.program).code;
+            if code != snapshot.output {
+                self.result = TestResult::Mismatch(code, snapshot.output.clone());
+                return;
+            }
+        }
+        self.result = TestResult::Passed;
+    }
+}
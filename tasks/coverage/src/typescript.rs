@@ -97,17 +97,26 @@ impl Case for TypeScriptCase {
     }
 
     fn run(&mut self) {
+        self.result = self.execute(self.source_type());
+    }
+}
+
+impl TypeScriptCase {
+    pub fn source_type(&self) -> SourceType {
         let compiler_options = &self.meta.options;
         let is_module = ["esnext", "es2022", "es2020", "es2015"]
             .into_iter()
             .any(|module| compiler_options.modules.contains(&module.to_string()));
-        let source_type = SourceType::from_path(self.path())
+        SourceType::from_path(self.path())
             .unwrap()
             .with_script(true)
             .with_module(is_module)
             .with_jsx(!compiler_options.jsx.is_empty())
-            .with_typescript_definition(compiler_options.declaration);
-        self.result = self.execute(source_type);
+            .with_typescript_definition(compiler_options.declaration)
+    }
+
+    pub fn set_result(&mut self, result: TestResult) {
+        self.result = result;
     }
 }
 
@@ -1,7 +1,9 @@
 use std::io;
 
-use oxc_minsize::run;
+use oxc_minsize::{run, run_rules};
+use pico_args::Arguments;
 
 fn main() -> Result<(), io::Error> {
-    run()
+    let mut args = Arguments::from_env();
+    if args.contains("--rules") { run_rules() } else { run() }
 }